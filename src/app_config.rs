@@ -1,5 +1,6 @@
 // llmdoc/src/app_config.rs
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use crate::core::errors::{Error, Result}; // Use custom Result and Error
 use toml;
@@ -22,6 +23,30 @@ pub struct Config {
     pub embeddings: EmbeddingsConfig,
     /// Configuration for export settings.
     pub export: ExportConfig,
+    /// Apply pending schema migrations automatically on startup.
+    #[serde(default = "default_auto_migrate")]
+    pub auto_migrate: bool,
+    /// Retention policy for `db backup`.
+    #[serde(default)]
+    pub backup: BackupConfig,
+    /// Where entity data is stored. `database_url = ":memory:"` also selects `memory`.
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// Task statuses, the transitions allowed between them, and fields required on entry.
+    #[serde(default)]
+    pub workflow: WorkflowConfig,
+    /// Prefixes of the sequential keys (`TASK-42`) given to new entities.
+    #[serde(default)]
+    pub keys: KeysConfig,
+    /// Label registry policy.
+    #[serde(default)]
+    pub labels: LabelsConfig,
+    /// Sprint capacity planning.
+    #[serde(default)]
+    pub capacity: CapacityConfig,
+    /// Sprint lifecycle settings.
+    #[serde(default)]
+    pub sprints: SprintsConfig,
     // Add other configuration fields as needed
 }
 
@@ -60,6 +85,129 @@ pub struct ExportConfig {
     pub default_format: String,
 }
 
+/// Storage backend for entity data.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// SQLite database file at `database_url`.
+    #[default]
+    Sqlite,
+    /// Everything kept in memory; nothing is written to disk and data is lost on exit.
+    Memory,
+}
+
+/// Retention policy for manual backups.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Keep the newest backup of each of this many most recent days.
+    pub keep_daily: u32,
+    /// Keep the newest backup of each of this many most recent weeks.
+    pub keep_weekly: u32,
+}
+
+/// Task status workflow. Statuses and task types are written as for
+/// `task update --status` and `task list --type`, e.g. `in_progress` or `bug`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WorkflowConfig {
+    /// Statuses tasks may be in.
+    pub states: Vec<String>,
+    /// Status -> statuses it may move to next.
+    pub transitions: BTreeMap<String, Vec<String>>,
+    /// Status -> task fields that must be set before a task enters it, e.g. `story_points`.
+    pub required_fields: BTreeMap<String, Vec<String>>,
+    /// Task type -> replacements for any of the above, for that type only.
+    pub types: BTreeMap<String, WorkflowVariant>,
+}
+
+/// Per-task-type workflow; unset parts fall back to the main workflow.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WorkflowVariant {
+    pub states: Option<Vec<String>>,
+    pub transitions: Option<BTreeMap<String, Vec<String>>>,
+    pub required_fields: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// Key prefix per entity type. Numbering continues across a prefix change.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub task: String,
+    pub sprint: String,
+    pub adr: String,
+    pub component: String,
+    pub user_story: String,
+}
+
+impl KeysConfig {
+    /// Checks every prefix is an uppercase word such as `TASK` or `BUG2`.
+    pub fn validate(&self) -> Result<(), Error> {
+        for prefix in [&self.task, &self.sprint, &self.adr, &self.component, &self.user_story] {
+            if !crate::core::models::validation::is_valid_key_prefix(prefix) {
+                return Err(Error::ConfigError(format!(
+                    "Invalid key prefix '{}': use uppercase letters and digits, starting with a letter",
+                    prefix
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How task labels relate to the label registry.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LabelsConfig {
+    /// Reject tasks carrying labels that are not registered with `label add`.
+    pub strict: bool,
+}
+
+/// Sprint capacity planning.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CapacityConfig {
+    /// Story points a member finishes in one fully focused day.
+    pub points_per_day: f64,
+    /// Focus factor for members whose availability does not give one.
+    pub focus_factor: f64,
+    /// Reject sprint assignments that take a member over capacity, instead of warning.
+    pub enforce: bool,
+}
+
+impl CapacityConfig {
+    /// Checks points per day is positive and the focus factor is a share of a day.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.points_per_day <= 0.0 {
+            return Err(Error::ConfigError(format!("capacity.points_per_day must be positive, not {}", self.points_per_day)));
+        }
+        if !(self.focus_factor > 0.0 && self.focus_factor <= 1.0) {
+            return Err(Error::ConfigError(format!("capacity.focus_factor must be more than 0 and at most 1, not {}", self.focus_factor)));
+        }
+        Ok(())
+    }
+}
+
+impl Default for CapacityConfig {
+    fn default() -> Self {
+        Self { points_per_day: 1.0, focus_factor: 0.8, enforce: false }
+    }
+}
+
+/// Sprint lifecycle settings.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SprintsConfig {
+    /// Flag tasks carried over from more than this many completed sprints.
+    pub carry_over_limit: u32,
+}
+
+impl Default for SprintsConfig {
+    fn default() -> Self {
+        Self { carry_over_limit: 2 }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -70,10 +218,22 @@ impl Default for Config {
             schema_dir: shellexpand::tilde("~/.llmdocs/schemas").into_owned().into(),
             embeddings: EmbeddingsConfig::default(),
             export: ExportConfig::default(),
+            auto_migrate: default_auto_migrate(),
+            backup: BackupConfig::default(),
+            backend: StorageBackend::default(),
+            workflow: WorkflowConfig::default(),
+            keys: KeysConfig::default(),
+            labels: LabelsConfig::default(),
+            capacity: CapacityConfig::default(),
+            sprints: SprintsConfig::default(),
         }
     }
 }
 
+fn default_auto_migrate() -> bool {
+    true
+}
+
 impl Default for EmbeddingsConfig {
     fn default() -> Self {
         EmbeddingsConfig {
@@ -94,6 +254,45 @@ impl Default for HttpEmbeddingsConfig {
     }
 }
 
+impl Default for BackupConfig {
+    fn default() -> Self {
+        BackupConfig {
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        let list = |states: &[&str]| states.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        WorkflowConfig {
+            states: list(&["todo", "in_progress", "blocked", "done", "cancelled"]),
+            transitions: BTreeMap::from([
+                ("todo".to_string(), list(&["in_progress", "blocked", "done", "cancelled"])),
+                ("in_progress".to_string(), list(&["todo", "blocked", "done", "cancelled"])),
+                ("blocked".to_string(), list(&["todo", "in_progress", "cancelled"])),
+                ("done".to_string(), list(&["todo", "in_progress"])), // Reopen
+                ("cancelled".to_string(), list(&["todo"])),            // Back to the backlog
+            ]),
+            required_fields: BTreeMap::new(),
+            types: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        KeysConfig {
+            task: "TASK".to_string(),
+            sprint: "SPRINT".to_string(),
+            adr: "ADR".to_string(),
+            component: "COMP".to_string(),
+            user_story: "US".to_string(),
+        }
+    }
+}
+
 // NativeEmbeddingsConfig does not have a sensible default without a model_path
 
 impl Default for ExportConfig {
@@ -111,6 +310,7 @@ impl Config {
     /// Otherwise, it tries default locations:
     /// 1. ./llmdocs.toml (current directory)
     /// 2. ~/.config/llmdocs/config.toml
+    ///
     /// If no file is found or loading fails, it returns an error.
     /// Use `Config::load(None).unwrap_or_default()` for a fallback.
     pub fn load(custom_path: Option<PathBuf>) -> Result<Self, Error> { // Change return type
@@ -174,12 +374,10 @@ impl Config {
             self.database_url_as_path().and_then(|p| p.parent().map(|path_ref| path_ref.to_path_buf())),
         ];
 
-        for dir_option in dirs_to_check {
-            if let Some(dir_path_buf) = dir_option { // dir_path_buf is now Option<PathBuf>
-                if !dir_path_buf.exists() {
-                    tracing::info!("Creating directory: {:?}", dir_path_buf);
-                    std::fs::create_dir_all(&dir_path_buf)?; // Pass as reference
-                }
+        for dir_path_buf in dirs_to_check.into_iter().flatten() {
+            if !dir_path_buf.exists() {
+                tracing::info!("Creating directory: {:?}", dir_path_buf);
+                std::fs::create_dir_all(&dir_path_buf)?; // Pass as reference
             }
         }
         Ok(())
@@ -202,7 +400,7 @@ impl Config {
         let toml_string = toml::to_string_pretty(self)
             .map_err(|e| Error::ConfigError(format!("Failed to serialize config to TOML: {}", e)))?; // Map to ConfigError
         std::fs::write(path, toml_string)
-            .map_err(|e| Error::IoError(e))?; // Map to IoError
+            .map_err(Error::IoError)?; // Map to IoError
         tracing::info!("Configuration saved to: {:?}", path);
         Ok(())
     }
@@ -0,0 +1,67 @@
+// llmdoc/src/cli/commands/comment_cmds.rs
+
+use clap::Subcommand;
+use std::sync::Arc;
+use colored::*;
+
+use crate::core::errors::{Error, Result};
+use crate::core::models::Comment;
+use crate::services::comment_service::{CommentService, CommentServiceTrait};
+use crate::services::key_service::{KeyService, KeyServiceTrait};
+use crate::cli::output::{print_comment_thread, OutputFormat};
+
+#[derive(Subcommand, Debug)]
+pub enum CommentCommands {
+    /// Comment on a task, sprint, ADR, component or user story
+    Add {
+        entity_id: String,
+
+        /// Comment text (markdown)
+        body: String,
+
+        #[arg(short, long)]
+        author: Option<String>,
+
+        /// ID of the comment this one answers
+        #[arg(long)]
+        reply_to: Option<String>,
+    },
+
+    /// Show the comment thread on an entity
+    List {
+        entity_id: String,
+
+        #[arg(short, long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+pub async fn execute(cmd: CommentCommands, service: Arc<CommentService>, keys: Arc<KeyService>) -> Result<()> {
+    match cmd {
+        CommentCommands::Add { entity_id, body, author, reply_to } => {
+            let mut comment = Comment::new(entity_id, body, author);
+            comment.reply_to = reply_to;
+            let comment = service.add_comment(comment).await?;
+            println!(
+                "{} Added comment {} on {} {}",
+                "✓".green(),
+                comment.id.dimmed(),
+                comment.entity_type,
+                comment.entity_id.cyan()
+            );
+            Ok(())
+        }
+
+        CommentCommands::List { entity_id, format } => {
+            let comments = service.list_comments(&keys.resolve_any(&entity_id).await?).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_comment_thread(&comments),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&comments)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for comments".to_string()));
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -5,58 +5,119 @@ use std::sync::Arc;
 use colored::*;
 use dialoguer::Confirm;
 
+use crate::app_config::Config;
 use crate::core::errors::{Error, Result}; // Import the errors module and its Result type
+use crate::core::backup;
 use crate::core::database::DbConnection;
+use crate::core::schema;
+use crate::services::stats_service::{StatsService, StatsServiceTrait};
+use crate::cli::output::{print_backup_table, print_database_stats, print_integrity_report, print_migration_status, OutputFormat};
 use std::path::PathBuf;
 
 #[derive(Subcommand, Debug)]
 pub enum DbCommands {
     /// Create a backup
     Backup {
+        /// Write the backup to this path instead of the managed backup directory
         #[arg(long)]
         output: Option<PathBuf>,
+        
+        /// List existing backups instead of creating one
+        #[arg(long)]
+        list: bool,
     },
     
     /// Restore from backup
     Restore {
         backup_file: PathBuf,
+        
+        #[arg(long)]
+        force: bool,
     },
     
     /// Show database statistics
-    Stats,
+    Stats {
+        #[arg(short, long, value_enum)]
+        format: Option<OutputFormat>,
+    },
     
-    /// Verify database integrity
-    Verify,
+    /// Verify database integrity and cross-entity references
+    Verify {
+        #[arg(short, long, value_enum)]
+        format: Option<OutputFormat>,
+        
+        /// Remove or null links to items that do not exist
+        #[arg(long)]
+        fix: bool,
+    },
     
     /// Run migrations
-    Migrate,
+    Migrate {
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MigrateAction {
+    /// Show applied and pending migrations
+    Status,
+
+    /// Apply pending migrations (default)
+    Up {
+        /// Stop at this schema version instead of the latest
+        #[arg(long)]
+        to: Option<u32>,
+    },
+
+    /// Roll back migrations down to a schema version
+    Down {
+        #[arg(long)]
+        to: u32,
+
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 pub async fn execute(
     cmd: DbCommands,
-    _db: Arc<DbConnection>,
+    db: Arc<DbConnection>,
+    stats_service: Arc<StatsService>,
+    config: &Config,
 ) -> Result<()> {
     match cmd {
-        DbCommands::Backup { output: _ } => {
-            // Backup functionality needs to be implemented in DbConnection or a dedicated service
-            println!("{} Database backup not yet implemented.", "⚠".yellow());
-            // let backup_path = if let Some(p) = output {
-            //     p
-            // } else {
-            //     db.config().backup_dir.join(format!("llmdocs_backup_{}.db", Utc::now().format("%Y%m%d_%H%M%S")))
-            // };
-            //
-            // println!("Creating backup at {}...", backup_path.display());
-            // let final_path = db.backup().await?;
-            // println!("{} Database backed up to {}", "✓".green(), final_path.display());
+        DbCommands::Backup { output, list } => {
+            if list {
+                print_backup_table(&db.list_backups().await?);
+                return Ok(());
+            }
+            
+            if let Some(path) = output {
+                println!("Creating backup at {}...", path.display());
+                db.backup_to(path.clone()).await?;
+                println!("{} Database backed up to {}", "✓".green(), path.display());
+                return Ok(());
+            }
+            
+            let (record, pruned) = db.backup(&config.backup).await?;
+            println!("{} Database backed up to {}", "✓".green(), record.file.cyan());
+            println!("  sha256: {}", record.sha256.dimmed());
+            if !pruned.is_empty() {
+                println!("{} Pruned {} old backup(s) per retention policy (keep {} daily, {} weekly)",
+                    "ℹ".blue(), pruned.len(), config.backup.keep_daily, config.backup.keep_weekly);
+            }
             Ok(())
         }
-        DbCommands::Restore { backup_file } => {
+        DbCommands::Restore { backup_file, force } => {
             if !backup_file.exists() {
-                return Err(Error::NotFound(format!("Backup file not found: {}", backup_file.display())).into());
+                return Err(Error::NotFound(format!("Backup file not found: {}", backup_file.display())));
             }
             
-            if !Confirm::new()
+            let version = backup::verify_backup(&backup_file)?;
+            println!("{} Backup verified (integrity ok, schema version {})", "✓".green(), version);
+            
+            if !force && !Confirm::new()
                 .with_prompt(format!("Are you sure you want to restore from {}? This will overwrite the current database.", backup_file.display()))
                 .default(false)
                 .interact()?
@@ -66,43 +127,72 @@ pub async fn execute(
             }
             
             println!("Restoring database from {}...", backup_file.display());
-            // This is a simplified restore. A real restore might involve stopping the app,
-            // replacing the file, and restarting. For now, we'll just copy.
-            // Restore functionality needs to be implemented in DbConnection or a dedicated service
-            println!("{} Database restore not yet implemented.", "⚠".yellow());
-            // tokio::fs::copy(&backup_file, &db.config().path).await?;
-            // println!("{} Database restored successfully!", "✓".green());
+            let version = db.restore(backup_file).await?;
+            println!("{} Database restored successfully (schema version {}).", "✓".green(), version);
+            if version < schema::latest_version() {
+                println!("{} Run `llmdocs db migrate` to upgrade it to version {}.", "ℹ".blue(), schema::latest_version());
+            }
             Ok(())
         }
-        DbCommands::Stats => {
-            // Stats functionality needs to be implemented in DbConnection or a dedicated service
-            println!("{} Database stats not yet implemented.", "⚠".yellow());
-            // let stats = db.stats().await?;
-            // println!("📊 Database Statistics:");
-            // println!("  Tasks: {}", stats.task_count);
-            // println!("  Sprints: {}", stats.sprint_count);
-            // println!("  Components: {}", stats.component_count);
-            // println!("  Database Size: {} bytes", stats.database_size);
-            // if let Some(last_backup) = stats.last_backup {
-            //     println!("  Last Backup: {}", last_backup.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"));
-            // } else {
-            //     println!("  Last Backup: Never");
-            // }
+        DbCommands::Stats { format } => {
+            let stats = stats_service.get_stats().await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_database_stats(&stats),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for db stats".to_string()));
+                }
+            }
             Ok(())
         }
-        DbCommands::Verify => {
-            // Health check functionality needs to be implemented in DbConnection or a dedicated service
-            println!("{} Database integrity check not yet implemented.", "⚠".yellow());
-            // db.health_check().await?;
-            // println!("{} Database integrity check passed!", "✓".green());
+        DbCommands::Verify { format, fix } => {
+            let report = db.verify(Some(config.schema_dir.clone()), fix).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_integrity_report(&report),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for db verify".to_string()));
+                }
+            }
+            let remaining = report.issues.len() - report.fixed;
+            if remaining > 0 {
+                return Err(Error::ValidationError(format!("{} integrity issue(s) remain", remaining)));
+            }
             Ok(())
         }
-        DbCommands::Migrate => {
-            println!("Running database migrations...");
-            // Migrations are now run at application startup in llmdoc/src/cli/commands/mod.rs
-            println!("{} Database migrations are run at application startup.", "ℹ".blue());
-            // db.run_migrations().await?;
-            // println!("{} Database migrations completed!", "✓".green());
+        DbCommands::Migrate { action } => {
+            match action.unwrap_or(MigrateAction::Up { to: None }) {
+                MigrateAction::Status => {
+                    let current = db.schema_version().await?;
+                    print_migration_status(&db.migration_status().await?, current, schema::latest_version());
+                }
+                MigrateAction::Up { to } => {
+                    println!("Running database migrations...");
+                    let applied = db.migrate_up(to).await?;
+                    if applied.is_empty() {
+                        println!("{} Database schema is already up to date (version {}).", "ℹ".blue(), db.schema_version().await?);
+                    } else {
+                        println!("{} Applied {} migration(s); schema is now at version {}.", "✓".green(), applied.len(), db.schema_version().await?);
+                    }
+                }
+                MigrateAction::Down { to, force } => {
+                    let current = db.schema_version().await?;
+                    if to >= current {
+                        println!("{} Schema is already at version {}; nothing to roll back.", "ℹ".blue(), current);
+                        return Ok(());
+                    }
+                    if !force && !Confirm::new()
+                        .with_prompt(format!("Roll back schema from version {} to {}? Data in dropped tables will be lost (a backup is taken first).", current, to))
+                        .default(false)
+                        .interact()?
+                    {
+                        println!("Rollback cancelled.");
+                        return Ok(());
+                    }
+                    let reverted = db.migrate_down(to).await?;
+                    println!("{} Reverted {} migration(s); schema is now at version {}.", "✓".green(), reverted.len(), to);
+                }
+            }
             Ok(())
         }
     }
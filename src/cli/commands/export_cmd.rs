@@ -4,29 +4,31 @@ use std::path::PathBuf;
 use colored::*;
 
 use crate::core::errors::{Error, Result};
-use crate::services::export_service::ExportService;
+use crate::services::export_service::{ExportService, ExportServiceTrait};
 use crate::cli::output::ExportFormat;
 
 pub async fn export(
-    _service: &ExportService,
+    service: &ExportService,
     output: PathBuf,
     format: ExportFormat,
-    _include_history: bool, // Marked as unused for now
+    include_history: bool,
 ) -> Result<()> {
     println!("Exporting data to {} in {:?} format...", output.display(), format);
-    match format {
+    let path = match format {
         ExportFormat::Markdown => {
-            // service.export_markdown(&output).await?; // Not yet implemented in service
-            println!("{} Markdown export not yet implemented.", "⚠".yellow());
+            let path = output.join("tasks.md");
+            service.export_all_tasks_to_markdown(&path.to_string_lossy(), include_history).await?;
+            path
         }
         ExportFormat::Json => {
-            // service.export_json(&output).await?; // Not yet implemented in service
-            println!("{} JSON export not yet implemented.", "⚠".yellow());
+            let path = output.join("llmdocs.json");
+            service.export_all_to_json(&path.to_string_lossy(), include_history).await?;
+            path
         }
         ExportFormat::Html => {
             return Err(Error::OperationFailed("HTML export not yet implemented".into()));
         }
-    }
-    println!("{} Data export process completed (check warnings for unimplemented formats).", "✓".green());
+    };
+    println!("{} Exported to {}", "✓".green(), path.display());
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,29 @@
+// llmdoc/src/cli/commands/history_cmd.rs
+
+use std::sync::Arc;
+use colored::*;
+
+use crate::core::errors::Result;
+use crate::services::history_service::{HistoryService, HistoryServiceTrait};
+use crate::services::key_service::{KeyService, KeyServiceTrait};
+use crate::cli::output::{print_history, print_history_csv, OutputFormat};
+
+pub async fn history(service: Arc<HistoryService>, keys: Arc<KeyService>, entity_id: String, format: Option<OutputFormat>) -> Result<()> {
+    let entity_id = keys.resolve_any(&entity_id).await?;
+    let history = service.get_history(&entity_id).await?;
+
+    match format.unwrap_or(OutputFormat::Table) {
+        OutputFormat::Table => {
+            match history.first() {
+                Some(first) => {
+                    println!("{} {}", first.entity_type.dimmed(), entity_id.cyan().bold());
+                    print_history(&history);
+                }
+                None => eprintln!("{} No history recorded for {}", "✗".red(), entity_id),
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&history)?),
+        OutputFormat::Csv => print_history_csv(&history)?,
+    }
+    Ok(())
+}
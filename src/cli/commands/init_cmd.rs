@@ -2,23 +2,22 @@
 
 use crate::app_config::Config;
 use crate::core::errors::Result; // Import the errors module and its Result type
-use std::path::PathBuf;
+use crate::core::models::task_template::BUILTIN_TEMPLATES;
+use std::path::Path;
 use colored::*;
 use dialoguer::Confirm;
 
-pub async fn init(config: &Config, db_path: &PathBuf, force: bool) -> Result<()> {
+pub async fn init(config: &Config, db_path: &Path, force: bool) -> Result<()> {
     let llmdocs_dir = db_path.parent().unwrap();
     
     // Check if already initialized
-    if llmdocs_dir.exists() && !force {
-        if !Confirm::new()
-            .with_prompt("LLMDocs directory already exists. Overwrite?")
-            .default(false)
-            .interact()? 
-        {
-            println!("Initialization cancelled");
-            return Ok(());
-        }
+    if llmdocs_dir.exists() && !force && !Confirm::new()
+        .with_prompt("LLMDocs directory already exists. Overwrite?")
+        .default(false)
+        .interact()?
+    {
+        println!("Initialization cancelled");
+        return Ok(());
     }
     
     // Create directory structure
@@ -46,12 +45,21 @@ pub async fn init(config: &Config, db_path: &PathBuf, force: bool) -> Result<()>
         ("component.json", include_str!("../../../schemas/component.json")),
         ("adr.json", include_str!("../../../schemas/adr.json")),
         ("user_story.json", include_str!("../../../schemas/user_story.json")),
+        ("comment.json", include_str!("../../../schemas/comment.json")),
     ];
     
     for (name, content) in schemas {
         std::fs::write(llmdocs_dir.join("schemas").join(name), content)?;
     }
     
+    // Write the built-in task templates, keeping any that were customised
+    for (name, content) in BUILTIN_TEMPLATES {
+        let path = templates_dir.join(format!("{}.toml", name));
+        if !path.exists() {
+            std::fs::write(path, content)?;
+        }
+    }
+    
     // Create default config
     config.save(&llmdocs_dir.join("config.toml"))?;
     
@@ -0,0 +1,131 @@
+// llmdoc/src/cli/commands/label_cmds.rs
+
+use clap::Subcommand;
+use std::sync::Arc;
+use colored::*;
+
+use crate::core::errors::{Error, Result};
+use crate::core::models::Label;
+use crate::services::label_service::{LabelService, LabelServiceTrait};
+use crate::cli::output::{print_label_table, print_label_stats, OutputFormat};
+
+#[derive(Subcommand, Debug)]
+pub enum LabelCommands {
+    /// Register a label
+    Add {
+        name: String,
+
+        #[arg(long)]
+        description: Option<String>,
+
+        /// A color name such as red or cyan, or #rrggbb
+        #[arg(long)]
+        color: Option<String>,
+    },
+
+    /// Change a label's description or color
+    Edit {
+        name: String,
+
+        #[arg(long)]
+        description: Option<String>,
+
+        #[arg(long)]
+        color: Option<String>,
+    },
+
+    /// Unregister a label; tasks keep it
+    Remove {
+        name: String,
+    },
+
+    /// List registered labels
+    List {
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Rename a label on every task
+    Rename {
+        from: String,
+        to: String,
+    },
+
+    /// Replace one or more labels with another on every task
+    Merge {
+        /// Labels to merge away
+        #[arg(required = true)]
+        sources: Vec<String>,
+
+        /// Label they become
+        #[arg(long)]
+        into: String,
+    },
+
+    /// How many open and closed tasks use each label
+    Stats {
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+pub async fn execute(cmd: LabelCommands, service: Arc<LabelService>) -> Result<()> {
+    match cmd {
+        LabelCommands::Add { name, description, color } => {
+            let label = service.add_label(Label::new(name, description, color)).await?;
+            println!("{} Label {} registered", "✓".green(), label.name.cyan());
+            Ok(())
+        }
+
+        LabelCommands::Edit { name, description, color } => {
+            let label = service.edit_label(&name, description, color).await?;
+            println!("{} Label {} updated", "✓".green(), label.name.cyan());
+            Ok(())
+        }
+
+        LabelCommands::Remove { name } => {
+            let still_used = service.remove_label(&name).await?;
+            println!("{} Label {} unregistered", "✓".green(), name.cyan());
+            if still_used > 0 {
+                println!("  {} {} task(s) still carry it", "!".yellow(), still_used);
+            }
+            Ok(())
+        }
+
+        LabelCommands::List { format } => {
+            let labels = service.list_labels().await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_label_table(&labels),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&labels)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for labels".to_string()));
+                }
+            }
+            Ok(())
+        }
+
+        LabelCommands::Rename { from, to } => {
+            let changed = service.rename_label(&from, &to, None).await?;
+            println!("{} Renamed {} to {} on {} task(s)", "✓".green(), from.cyan(), to.cyan(), changed.len());
+            Ok(())
+        }
+
+        LabelCommands::Merge { sources, into } => {
+            let changed = service.merge_labels(sources.clone(), &into, None).await?;
+            println!("{} Merged {} into {} on {} task(s)", "✓".green(), sources.join(", ").cyan(), into.cyan(), changed.len());
+            Ok(())
+        }
+
+        LabelCommands::Stats { format } => {
+            let stats = service.label_stats().await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_label_stats(&stats),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for label stats".to_string()));
+                }
+            }
+            Ok(())
+        }
+    }
+}
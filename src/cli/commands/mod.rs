@@ -7,6 +7,7 @@ use crate::core::errors::{self, Result}; // Import the errors module and its Res
 use std::sync::Arc;
 use std::str::FromStr; // Import FromStr trait
 use crate::core::database::DbConnection;
+use crate::core::workflow::Workflow;
 use crate::embeddings::{EmbeddingProvider, EmbeddingProviderProviderType};
 use crate::embeddings::http_provider::HttpEmbeddingProvider;
 use crate::services::{
@@ -14,6 +15,17 @@ use crate::services::{
     sprint_service::SprintService,
     search_service::{SearchService, SearchServiceTrait},
     export_service::{ExportService, ExportServiceTrait},
+    history_service::{HistoryService, HistoryServiceTrait},
+    dependency_service::{DependencyService, DependencyServiceTrait},
+    hierarchy_service::{HierarchyService, HierarchyServiceTrait},
+    time_tracking_service::{TimeTrackingService, TimeTrackingServiceTrait},
+    comment_service::{CommentService, CommentServiceTrait},
+    key_service::{KeyService, KeyServiceTrait},
+    template_service::{TemplateService, TemplateServiceTrait},
+    label_service::{LabelService, LabelServiceTrait},
+    capacity_service::{CapacityService, CapacityServiceTrait},
+    stats_service::{StatsService, StatsServiceTrait},
+    trash_service::{TrashService, TrashServiceTrait},
     component_service::{ComponentService, ComponentServiceTrait},
     adr_service::{AdrService, AdrServiceTrait},
     user_story_service::{UserStoryService, UserStoryServiceTrait},
@@ -27,6 +39,17 @@ pub struct Services {
     pub sprint_service: Arc<SprintService>,
     pub search_service: Arc<SearchService>,
     pub export_service: Arc<ExportService>,
+    pub history_service: Arc<HistoryService>,
+    pub dependency_service: Arc<DependencyService>,
+    pub hierarchy_service: Arc<HierarchyService>,
+    pub time_tracking_service: Arc<TimeTrackingService>,
+    pub comment_service: Arc<CommentService>,
+    pub key_service: Arc<KeyService>,
+    pub template_service: Arc<TemplateService>,
+    pub label_service: Arc<LabelService>,
+    pub capacity_service: Arc<CapacityService>,
+    pub trash_service: Arc<TrashService>,
+    pub stats_service: Arc<StatsService>,
     pub component_service: Arc<ComponentService>,
     pub adr_service: Arc<AdrService>,
     pub user_story_service: Arc<UserStoryService>,
@@ -56,19 +79,55 @@ impl Services {
             EmbeddingProviderProviderType::None => None,
         };
 
-        let task_service = Arc::new(TaskService::new(db_connection.clone()));
-        let sprint_service = Arc::new(SprintService::new(db_connection.clone()));
+        let workflow = Workflow::from_config(&config.workflow)?;
+        config.keys.validate()?;
+        config.capacity.validate()?;
+        let task_service = Arc::new(
+            TaskService::new(db_connection.clone())
+                .with_workflow(workflow)
+                .with_key_prefix(&config.keys.task)
+                .with_strict_labels(config.labels.strict)
+                .with_capacity(config.capacity.clone()),
+        );
+        let sprint_service = Arc::new(
+            SprintService::new(db_connection.clone())
+                .with_key_prefix(&config.keys.sprint)
+                .with_carry_over_limit(config.sprints.carry_over_limit)
+                .with_capacity(config.capacity.clone()),
+        );
         let search_service = Arc::new(SearchService::new(db_connection.clone()));
         let export_service = Arc::new(ExportService::new(db_connection.clone()));
-        let component_service = Arc::new(ComponentService::new(db_connection.clone()));
-        let adr_service = Arc::new(AdrService::new(db_connection.clone()));
-        let user_story_service = Arc::new(UserStoryService::new(db_connection.clone()));
+        let history_service = Arc::new(HistoryService::new(db_connection.clone()));
+        let dependency_service = Arc::new(DependencyService::new(db_connection.clone()));
+        let hierarchy_service = Arc::new(HierarchyService::new(db_connection.clone()));
+        let time_tracking_service = Arc::new(TimeTrackingService::new(db_connection.clone()));
+        let comment_service = Arc::new(CommentService::new(db_connection.clone()));
+        let key_service = Arc::new(KeyService::new(db_connection.clone()));
+        let template_service = Arc::new(TemplateService::new(db_connection.clone()));
+        let label_service = Arc::new(LabelService::new(db_connection.clone()));
+        let capacity_service = Arc::new(CapacityService::new(db_connection.clone()).with_config(config.capacity.clone()));
+        let trash_service = Arc::new(TrashService::new(db_connection.clone()));
+        let stats_service = Arc::new(StatsService::new(db_connection.clone()));
+        let component_service = Arc::new(ComponentService::new(db_connection.clone()).with_key_prefix(&config.keys.component));
+        let adr_service = Arc::new(AdrService::new(db_connection.clone()).with_key_prefix(&config.keys.adr));
+        let user_story_service = Arc::new(UserStoryService::new(db_connection.clone()).with_key_prefix(&config.keys.user_story));
 
         Ok(Self {
             task_service,
             sprint_service,
             search_service,
             export_service,
+            history_service,
+            dependency_service,
+            hierarchy_service,
+            time_tracking_service,
+            comment_service,
+            key_service,
+            template_service,
+            label_service,
+            capacity_service,
+            trash_service,
+            stats_service,
             component_service,
             adr_service,
             user_story_service,
@@ -79,10 +138,14 @@ impl Services {
 
 pub mod task_cmds;
 pub mod sprint_cmds;
+pub mod trash_cmds;
+pub mod comment_cmds;
+pub mod label_cmds;
 pub mod search_cmds;
 pub mod db_cmds;
 pub mod init_cmd;
 pub mod export_cmd;
+pub mod history_cmd;
 pub mod import_cmd;
 pub mod watch_cmd;
 pub mod migrate_cmd;
@@ -121,6 +184,14 @@ pub enum Commands {
     #[command(subcommand)]
     Sprint(sprint_cmds::SprintCommands),
     
+    /// Comment threads on tasks, sprints, ADRs, components and user stories
+    #[command(subcommand)]
+    Comment(comment_cmds::CommentCommands),
+    
+    /// Label registry: add, rename, merge and usage stats
+    #[command(subcommand)]
+    Label(label_cmds::LabelCommands),
+    
     /// Search commands
     #[command(subcommand)]
     Search(search_cmds::SearchCommands),
@@ -137,6 +208,18 @@ pub enum Commands {
         include_history: bool,
     },
     
+    /// Show the change history of a task, sprint, ADR, component or user story
+    History {
+        entity_id: String,
+
+        #[arg(short, long, value_enum)]
+        format: Option<output::OutputFormat>,
+    },
+    
+    /// List, restore or purge deleted items
+    #[command(subcommand)]
+    Trash(trash_cmds::TrashCommands),
+    
     /// Import data
     Import {
         file: PathBuf,
@@ -167,12 +250,13 @@ pub enum Commands {
 
 pub async fn execute(cli: Cli, config: &Config) -> Result<()> {
     // Initialize database connection
-    let db_connection = Arc::new(crate::core::database::DbConnection::new( // Wrap in Arc
-        &config.database_url
-    ).await?);
+    let db_connection = Arc::new(DbConnection::from_config(config).await?);
 
-    // Run migrations
-    db_connection.run_migrations().await?;
+    // Run migrations, unless the user is managing them explicitly via `db migrate`
+    let managing_migrations = matches!(cli.command, Commands::Db(db_cmds::DbCommands::Migrate { .. }));
+    if config.auto_migrate && !managing_migrations {
+        db_connection.run_migrations().await?;
+    }
 
     // Initialize all services
     let services = Services::new(config, db_connection.clone()).await?;
@@ -182,10 +266,16 @@ pub async fn execute(cli: Cli, config: &Config) -> Result<()> {
             init_cmd::init(config, &cli.database, force).await
         }
         Commands::Task(task_cmd) => {
-            task_cmds::execute(task_cmd, services.task_service, config).await
+            task_cmds::execute(task_cmd, &services, config).await
         }
         Commands::Sprint(sprint_cmd) => {
-            sprint_cmds::execute(sprint_cmd, services.sprint_service).await
+            sprint_cmds::execute(sprint_cmd, services.sprint_service, services.capacity_service, services.key_service).await
+        }
+        Commands::Comment(comment_cmd) => {
+            comment_cmds::execute(comment_cmd, services.comment_service, services.key_service).await
+        }
+        Commands::Label(label_cmd) => {
+            label_cmds::execute(label_cmd, services.label_service).await
         }
         Commands::Search(search_cmd) => {
             if let Some(_provider) = services.embedding_provider {
@@ -198,11 +288,17 @@ pub async fn execute(cli: Cli, config: &Config) -> Result<()> {
         Commands::Export { output, format, include_history } => {
             export_cmd::export(&services.export_service, output, format, include_history).await
         }
+        Commands::History { entity_id, format } => {
+            history_cmd::history(services.history_service, services.key_service, entity_id, format).await
+        }
+        Commands::Trash(trash_cmd) => {
+            trash_cmds::execute(trash_cmd, services.trash_service, services.key_service).await
+        }
         Commands::Import { file, merge } => {
             import_cmd::import(db_connection.clone(), file, merge).await
         }
         Commands::Db(db_cmd) => {
-            db_cmds::execute(db_cmd, db_connection.clone()).await
+            db_cmds::execute(db_cmd, db_connection.clone(), services.stats_service, config).await
         }
         Commands::Watch { interval } => {
             watch_cmd::watch(db_connection.clone(), interval).await
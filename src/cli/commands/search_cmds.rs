@@ -6,7 +6,7 @@ use colored::*;
 
 use crate::core::errors::Result; // Import the errors module and its Result type
 use crate::services::search_service::SearchServiceTrait; // Add this line
-use crate::cli::output::print_task_table; // Use print_task_table for now, as print_search_results is not defined
+use crate::cli::output::{print_comment_table, print_task_table}; // Use print_task_table for now, as print_search_results is not defined
 
 #[derive(Subcommand, Debug)]
 pub enum SearchCommands {
@@ -46,9 +46,14 @@ pub async fn execute(
             print_task_table(&results); // Using print_task_table as a placeholder
             Ok(())
         }
-        SearchCommands::Text { query, limit: _limit } => {
+        SearchCommands::Text { query, limit } => {
             let results = service.search_tasks(&query).await?; // Simplified for now
             print_task_table(&results); // Using print_task_table as a placeholder
+            let comments = service.search_comments(&query, limit).await?;
+            if !comments.is_empty() {
+                println!("\n{}", "Comments".cyan().bold());
+                print_comment_table(&comments);
+            }
             Ok(())
         }
         SearchCommands::UpdateEmbeddings { force: _force } => {
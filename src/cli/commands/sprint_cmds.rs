@@ -3,16 +3,24 @@
 use clap::Subcommand;
 use std::sync::Arc;
 use colored::*;
-use dialoguer::Input;
+use dialoguer::{Confirm, Input, Select};
 use serde_json;
-use chrono::{Utc, NaiveDate};
-use crate::services::sprint_service::SprintServiceTrait;
+use chrono::{Local, Utc, NaiveDate};
+use crate::services::sprint_service::{CarryOverTarget, SprintPlan, SprintServiceTrait};
+use crate::services::key_service::{KeyService, KeyServiceTrait};
 
 use crate::core::errors::{Error, Result};
 use crate::core::models::{
     sprint::{Sprint, SprintStatus, Retrospective},
 };
-use crate::cli::output::{print_sprint_table, print_sprint_report, parse_sprint_status};
+use crate::cli::output::{
+    print_sprint_table, print_sprint_report, print_burn_charts, print_burndown_csv, print_dangling_references,
+    print_velocity_report, print_velocity_csv, print_forecast, print_capacity_report, print_capacity_warnings,
+    print_sprint_calendar, parse_sprint_status, parse_date, OutputFormat,
+};
+use crate::services::capacity_service::{CapacityService, CapacityServiceTrait};
+use crate::core::forecast::ForecastTarget;
+use crate::core::dependency_graph::is_finished;
 
 #[derive(Subcommand, Debug)]
 pub enum SprintCommands {
@@ -28,8 +36,38 @@ pub enum SprintCommands {
         end: Option<String>,
     },
     
+    /// Create a run of back-to-back sprints
+    Plan {
+        /// Length of each sprint, in days (10d) or weeks (2w)
+        #[arg(long, default_value = "2w")]
+        cadence: String,
+
+        #[arg(long)]
+        count: u32,
+
+        /// First day of the first sprint; defaults to the day after the last sprint ends
+        #[arg(long)]
+        start: Option<String>,
+
+        /// Name pattern; the placeholder shown in the default becomes the sprint number
+        // clap turns a literal {n} in help text into a line break, so it is not spelled out here
+        #[arg(long, default_value = "Sprint {n}")]
+        name_pattern: String,
+
+        /// Number of the first sprint; defaults to one more than the number of sprints
+        #[arg(long)]
+        first_number: Option<u32>,
+    },
+
     /// Get current sprint
     Current,
+
+    /// Past, current and upcoming sprints in date order, with gaps between them
+    Calendar {
+        /// Only the most recent past sprints
+        #[arg(long)]
+        past: Option<usize>,
+    },
     
     /// List all sprints
     List {
@@ -48,17 +86,121 @@ pub enum SprintCommands {
         
         #[arg(long)]
         retrospective: bool,
+
+        /// Where unfinished tasks go: next (the next planned sprint), backlog, none, or a sprint ID.
+        /// Asked for if there are unfinished tasks and this is not given
+        #[arg(long, value_name = "TARGET")]
+        carry_to: Option<String>,
     },
     
     /// Get sprint report
     Report {
         sprint_id: String,
+
+        /// Also draw the burndown and burnup charts
+        #[arg(long)]
+        chart: bool,
+
+        /// Table, JSON (the report with its day-by-day series) or CSV (the series only)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Story points completed per sprint, with rolling averages
+    Velocity {
+        /// Only the most recent completed sprints
+        #[arg(long)]
+        last: Option<usize>,
+
+        /// Sprints in each rolling average
+        #[arg(long, default_value_t = 3)]
+        window: usize,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Forecast how many sprints the open work of an epic or label will take
+    Forecast {
+        /// Epic (or any parent task) whose open work to forecast
+        #[arg(long, conflicts_with = "label", required_unless_present = "label")]
+        epic: Option<String>,
+
+        /// Label whose open tasks to forecast
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Draw on the velocity of only the most recent completed sprints
+        #[arg(long)]
+        last: Option<usize>,
+
+        /// Simulated runs
+        #[arg(long, default_value_t = 10_000)]
+        runs: u32,
+
+        /// Seed for repeatable results
+        #[arg(long)]
+        seed: Option<u64>,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Member availability and planned load against capacity
+    Capacity {
+        #[command(subcommand)]
+        action: CapacityAction,
+    },
+
+    /// Delete a sprint (soft delete)
+    Delete {
+        sprint_id: String,
+        
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CapacityAction {
+    /// Each member's capacity and load in a sprint
+    Show {
+        sprint_id: String,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Record how much of a sprint a member is available
+    Set {
+        sprint_id: String,
+        member: String,
+
+        /// Working days in the sprint, before leave
+        #[arg(long)]
+        days: Option<f64>,
+
+        /// Share of a day spent on sprint work, e.g. 0.7
+        #[arg(long)]
+        focus: Option<f64>,
+
+        /// Planned days off
+        #[arg(long)]
+        leave: Option<f64>,
+    },
+
+    /// Forget a member's availability for a sprint
+    Remove {
+        sprint_id: String,
+        member: String,
     },
 }
 
 pub async fn execute(
     cmd: SprintCommands,
     service: Arc<crate::services::sprint_service::SprintService>,
+    capacity: Arc<CapacityService>,
+    keys: Arc<KeyService>,
 ) -> Result<()> {
     match cmd {
         SprintCommands::Create { name, start, end } => {
@@ -90,6 +232,7 @@ pub async fn execute(
             
             let new_sprint = Sprint {
                 id: format!("sprint-{}", Utc::now().format("%Y%m%d%H%M%S")),
+                key: None,
                 name: sprint_name,
                 description: None,
                 start_date,
@@ -101,14 +244,36 @@ pub async fn execute(
                 capacity: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                version: 1,
                 retrospective: None,
+                carry_over: None,
             };
             
             let created = service.create_sprint(new_sprint).await?;
-            println!("{} Sprint {} created", "✓".green(), created.id.cyan());
+            println!("{} Sprint {} created ({})", "✓".green(), created.key.as_deref().unwrap_or(&created.id).cyan(), created.id.dimmed());
             Ok(())
         }
         
+        SprintCommands::Plan { cadence, count, start, name_pattern, first_number } => {
+            let plan = SprintPlan {
+                start: start.as_deref().map(parse_date).transpose()?,
+                cadence_days: parse_cadence(&cadence)?,
+                count,
+                name_pattern,
+                first_number,
+            };
+            let planned = service.plan_sprints(plan).await?;
+            println!("{} Planned {} sprints", "✓".green(), planned.len());
+            print_sprint_table(&planned);
+            Ok(())
+        }
+
+        SprintCommands::Calendar { past } => {
+            let sprints = service.list_sprints(None).await?;
+            print_sprint_calendar(&sprints, Local::now().date_naive(), past);
+            Ok(())
+        }
+
         SprintCommands::Current => {
             match service.get_current_sprint().await? {
                 Some(sprint) => {
@@ -128,25 +293,144 @@ pub async fn execute(
         }
         
         SprintCommands::Start { sprint_id } => {
+            let sprint_id = keys.resolve("sprint", &sprint_id).await?;
             let started_sprint = service.start_sprint(&sprint_id).await?;
             println!("{} Sprint {} started", "✓".green(), started_sprint.id.cyan());
             Ok(())
         }
         
-        SprintCommands::Complete { sprint_id, retrospective } => {
+        SprintCommands::Complete { sprint_id, retrospective, carry_to } => {
+            let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+            let sprint = service.get_sprint_by_id(&sprint_id).await?
+                .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", sprint_id)))?;
+            let open_tasks: Vec<_> = sprint.tasks.iter().filter(|t| !is_finished(&t.status)).collect();
+            let carry_to = match carry_to {
+                Some(target) => parse_carry_over_target(&target, &keys).await?,
+                // Only an active sprint can be completed; let the service say so before asking anything
+                None if open_tasks.is_empty() || sprint.status != SprintStatus::Active => CarryOverTarget::Keep,
+                None => prompt_carry_over_target(open_tasks.len())?,
+            };
+            let assignees: Vec<String> = open_tasks.iter().filter_map(|t| t.assignee.clone()).collect();
             let retro_data = if retrospective {
                 Some(create_retrospective_interactive()?)
             } else {
                 None
             };
-            let completed_sprint = service.complete_sprint(&sprint_id, retro_data).await?;
+            let completed_sprint = service.complete_sprint(&sprint_id, retro_data, carry_to).await?;
             println!("{} Sprint {} completed", "✓".green(), completed_sprint.id.cyan());
+
+            if let Some(carry_over) = &completed_sprint.carry_over {
+                match &carry_over.to_sprint_id {
+                    Some(to) => {
+                        println!("  Carried {} tasks ({} points) over to sprint {}", carry_over.task_ids.len(), carry_over.points, to.cyan());
+                        print_capacity_warnings(&capacity.get_capacity(to).await?, &assignees);
+                    }
+                    None => println!("  Moved {} tasks ({} points) to the backlog", carry_over.task_ids.len(), carry_over.points),
+                }
+                let report = service.get_sprint_report(&completed_sprint.id).await?;
+                if !report.repeatedly_carried.is_empty() {
+                    println!(
+                        "  {} Carried over repeatedly: {}",
+                        "!".yellow(),
+                        report.repeatedly_carried.join(", ").yellow()
+                    );
+                }
+            }
             Ok(())
         }
         
-        SprintCommands::Report { sprint_id } => {
+        SprintCommands::Report { sprint_id, chart, format } => {
+            let sprint_id = keys.resolve("sprint", &sprint_id).await?;
             let report = service.get_sprint_report(&sprint_id).await?;
-            print_sprint_report(&report);
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => {
+                    print_sprint_report(&report);
+                    if chart {
+                        print_burn_charts(&report.burndown);
+                    }
+                }
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Csv => print_burndown_csv(&report.burndown)?,
+            }
+            Ok(())
+        }
+        
+        SprintCommands::Velocity { last, window, format } => {
+            let report = service.get_velocity(last, window).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_velocity_report(&report),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Csv => print_velocity_csv(&report)?,
+            }
+            Ok(())
+        }
+
+        SprintCommands::Forecast { epic, label, last, runs, seed, format } => {
+            let target = match (epic, label) {
+                (Some(epic), _) => ForecastTarget::Epic(keys.resolve("task", &epic).await?),
+                (None, Some(label)) => ForecastTarget::Label(label),
+                (None, None) => return Err(Error::CliArgumentError("Give --epic or --label".to_string())),
+            };
+            let forecast = service.forecast(target, last, runs, seed).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_forecast(&forecast),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&forecast)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for forecasts".to_string()));
+                }
+            }
+            Ok(())
+        }
+
+        SprintCommands::Capacity { action } => match action {
+            CapacityAction::Show { sprint_id, format } => {
+                let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+                let report = capacity.get_capacity(&sprint_id).await?;
+                match format.unwrap_or(OutputFormat::Table) {
+                    OutputFormat::Table => print_capacity_report(&report),
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                    OutputFormat::Csv => {
+                        return Err(Error::CliArgumentError("CSV output is not supported for capacity".to_string()));
+                    }
+                }
+                Ok(())
+            }
+            CapacityAction::Set { sprint_id, member, days, focus, leave } => {
+                let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+                let availability = capacity.set_availability(&sprint_id, &member, days, focus, leave).await?;
+                let report = capacity.get_capacity(&sprint_id).await?;
+                println!(
+                    "{} {} available {} day(s) less {} leave at focus {}",
+                    "✓".green(),
+                    availability.member.cyan(),
+                    availability.days_available,
+                    availability.leave_days,
+                    availability.focus_factor
+                );
+                print_capacity_warnings(&report, std::slice::from_ref(&availability.member));
+                Ok(())
+            }
+            CapacityAction::Remove { sprint_id, member } => {
+                let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+                capacity.remove_availability(&sprint_id, &member).await?;
+                println!("{} Availability of {} removed", "✓".green(), member.cyan());
+                Ok(())
+            }
+        },
+
+        SprintCommands::Delete { sprint_id, force } => {
+            let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+            if force || Confirm::new()
+                .with_prompt(format!("Are you sure you want to delete sprint {}?", sprint_id))
+                .default(false)
+                .interact()?
+            {
+                let references = service.delete_sprint(&sprint_id).await?;
+                println!("{} Sprint {} moved to trash", "✓".green(), sprint_id.cyan());
+                print_dangling_references(&sprint_id, &references);
+            } else {
+                println!("Deletion cancelled.");
+            }
             Ok(())
         }
     }
@@ -154,6 +438,44 @@ pub async fn execute(
 
 // Removed parse_sprint_status as it's now in output.rs
 
+/// Sprint length in days from `10d`, `2w` or a plain number of days.
+fn parse_cadence(cadence: &str) -> Result<u32> {
+    let cadence = cadence.trim().to_lowercase();
+    let (number, days_per_unit) = match cadence.strip_suffix('w') {
+        Some(weeks) => (weeks, 7),
+        None => (cadence.strip_suffix('d').unwrap_or(&cadence), 1),
+    };
+    match number.trim().parse::<u32>() {
+        Ok(n) if n > 0 => n
+            .checked_mul(days_per_unit)
+            .ok_or_else(|| Error::ValidationError(format!("Cadence is too long: {}", cadence))),
+        _ => Err(Error::InvalidInput(format!("Invalid cadence (use e.g. 2w or 10d): {}", cadence))),
+    }
+}
+
+async fn parse_carry_over_target(target: &str, keys: &KeyService) -> Result<CarryOverTarget> {
+    match target.to_lowercase().as_str() {
+        "next" => Ok(CarryOverTarget::Next),
+        "backlog" => Ok(CarryOverTarget::Backlog),
+        "none" => Ok(CarryOverTarget::Keep),
+        _ => Ok(CarryOverTarget::Sprint(keys.resolve("sprint", target).await?)),
+    }
+}
+
+fn prompt_carry_over_target(open_tasks: usize) -> Result<CarryOverTarget> {
+    let choices = ["Next planned sprint", "Backlog", "Leave them in this sprint"];
+    let choice = Select::new()
+        .with_prompt(format!("{} tasks are unfinished. Carry them over to", open_tasks))
+        .items(&choices)
+        .default(0)
+        .interact()?;
+    Ok(match choice {
+        0 => CarryOverTarget::Next,
+        1 => CarryOverTarget::Backlog,
+        _ => CarryOverTarget::Keep,
+    })
+}
+
 fn create_retrospective_interactive() -> Result<Retrospective> {
     println!("\n--- Sprint Retrospective ---");
     let what_went_well: Vec<String> = Input::<String>::new()
@@ -1,7 +1,7 @@
 // llmdoc/src/cli/commands/task_cmds.rs
 
-use clap::Subcommand;
-use std::sync::Arc;
+use chrono::{Days, Local, NaiveDate};
+use clap::{Args, Subcommand};
 use colored::*;
 use dialoguer::{Confirm, Input, Select};
 use serde_json;
@@ -11,17 +11,40 @@ use crate::app_config::Config;
 use crate::core::errors::{self, Result}; // Import the errors module and its Result type
 use crate::core::models::{
     task::{Task, TaskType},
+    task_filter::{DateRange, TaskFilter},
+    work_log::{format_duration, parse_duration, WorkLogEntry},
     validation, // Import the validation module
 };
-use crate::services::task_service::TaskService;
-use crate::cli::output::{print_task_table, print_task, print_task_csv, parse_task_status, OutputFormat};
+use crate::services::dependency_service::DependencyServiceTrait;
+use crate::services::hierarchy_service::HierarchyServiceTrait;
+use crate::services::time_tracking_service::TimeTrackingServiceTrait;
+use crate::services::key_service::KeyServiceTrait;
+use crate::services::template_service::TemplateServiceTrait;
+use crate::services::capacity_service::CapacityServiceTrait;
+use crate::cli::commands::Services;
+use crate::cli::output::{
+    print_task_table, print_task, print_task_csv, print_history, print_dangling_references, print_page_info,
+    print_dependency_tree, print_task_dependencies, print_dependency_plan, print_task_hierarchy,
+    print_work_log, print_time_report, print_template_table, print_capacity_warnings,
+    parse_task_status, parse_task_type, parse_priority, parse_date_bound, parse_date, parse_days, OutputFormat,
+};
 
 #[derive(Subcommand, Debug)]
 pub enum TaskCommands {
     /// Create a new task
     Add {
         /// Task JSON or interactive mode if not provided
+        #[arg(conflicts_with = "template")]
         json: Option<String>,
+
+        /// Start from a named template (see `task template list`)
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Fill a template placeholder or set a field (id, assignee, priority, type, points,
+        /// estimate, labels, sprint, parent, start, due), e.g. --set title="Crash on save"
+        #[arg(long = "set", value_name = "NAME=VALUE", requires = "template")]
+        set: Vec<String>,
     },
     
     /// Get task details
@@ -34,15 +57,9 @@ pub enum TaskCommands {
     
     /// List tasks
     List {
-        #[arg(short, long)]
-        status: Option<String>,
-        
-        #[arg(short = 'p', long)]
-        sprint: Option<String>,
-        
-        #[arg(short, long)]
-        assignee: Option<String>,
-        
+        #[command(flatten)]
+        filter: Box<TaskListArgs>,
+
         #[arg(long)]
         format: Option<OutputFormat>,
     },
@@ -62,6 +79,40 @@ pub enum TaskCommands {
         
         #[arg(long)]
         points: Option<u8>,
+
+        /// Original time estimate, e.g. 1d 4h
+        #[arg(long)]
+        estimate: Option<String>,
+
+        /// Time still needed, e.g. 3h
+        #[arg(long)]
+        remaining: Option<String>,
+
+        /// Epic or story this task belongs to
+        #[arg(long)]
+        parent: Option<String>,
+
+        /// Move the task to the top level
+        #[arg(long, conflicts_with = "parent")]
+        no_parent: bool,
+
+        /// When work should start (YYYY-MM-DD); must fall within the task's sprint
+        #[arg(long)]
+        start: Option<String>,
+
+        #[arg(long, conflicts_with = "start")]
+        no_start: bool,
+
+        /// When the task is due (YYYY-MM-DD); must fall within the task's sprint
+        #[arg(long)]
+        due: Option<String>,
+
+        #[arg(long, conflicts_with = "due")]
+        no_due: bool,
+
+        /// Only apply the update if the task is still at this version (as shown by `task get`)
+        #[arg(long)]
+        if_version: Option<u32>,
     },
     
     /// Delete task (soft delete)
@@ -72,6 +123,85 @@ pub enum TaskCommands {
         force: bool,
     },
     
+    /// Show what a task depends on and what it blocks
+    Deps {
+        task_id: String,
+
+        /// Show everything it depends on, recursively
+        #[arg(long, conflicts_with = "reverse")]
+        tree: bool,
+
+        /// Show everything that depends on it, recursively
+        #[arg(long)]
+        reverse: bool,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// List tasks that can be started now (all dependencies finished)
+    Ready {
+        /// Show every open task in dependency order, grouped into waves
+        #[arg(long)]
+        plan: bool,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Show the epic/story/task hierarchy with rolled-up story points and progress
+    Tree {
+        /// Only show this task and what is under it
+        task_id: Option<String>,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Log time spent on a task, or list the time logged against it
+    Log {
+        task_id: String,
+
+        /// Time spent, e.g. 2h30m, 1d 4h or 45m; omit to list the logged time
+        duration: Option<String>,
+
+        note: Option<String>,
+
+        #[arg(long)]
+        author: Option<String>,
+
+        /// When the work was done (YYYY-MM-DD or RFC 3339); defaults to now
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Set the remaining estimate instead of counting it down by the time logged
+        #[arg(long)]
+        remaining: Option<String>,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Report estimated against logged time
+    Time {
+        /// Group by task, sprint or assignee
+        #[arg(long, default_value = "task")]
+        by: String,
+
+        /// Only tasks in this sprint
+        #[arg(short = 'p', long)]
+        sprint: Option<String>,
+
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Task templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
     /// Bulk operations
     Bulk {
         #[command(subcommand)]
@@ -79,16 +209,150 @@ pub enum TaskCommands {
     },
 }
 
+/// Filters, sorting and pagination for `task list`
+#[derive(Args, Debug)]
+pub struct TaskListArgs {
+    /// Comma-separated statuses, e.g. todo,in_progress
+    #[arg(short, long, value_delimiter = ',')]
+    status: Vec<String>,
+
+    /// Comma-separated task types, e.g. bug,feature
+    #[arg(short = 't', long = "type", value_delimiter = ',')]
+    task_type: Vec<String>,
+
+    /// Comma-separated priorities, e.g. high,critical
+    #[arg(long, value_delimiter = ',')]
+    priority: Vec<String>,
+
+    #[arg(short = 'p', long)]
+    sprint: Option<String>,
+
+    /// Direct children of this epic or story
+    #[arg(long)]
+    parent: Option<String>,
+
+    #[arg(short, long)]
+    assignee: Option<String>,
+
+    /// Tasks with any of these comma-separated labels
+    #[arg(short, long, value_delimiter = ',')]
+    label: Vec<String>,
+
+    /// Tasks with all of these comma-separated labels
+    #[arg(long, value_delimiter = ',')]
+    all_labels: Vec<String>,
+
+    /// Created on or after this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    created_after: Option<String>,
+
+    #[arg(long)]
+    created_before: Option<String>,
+
+    #[arg(long)]
+    updated_after: Option<String>,
+
+    #[arg(long)]
+    updated_before: Option<String>,
+
+    #[arg(long)]
+    completed_after: Option<String>,
+
+    #[arg(long)]
+    completed_before: Option<String>,
+
+    /// Open tasks past their due date
+    #[arg(long, conflicts_with = "due_within")]
+    overdue: bool,
+
+    /// Open tasks due within this many days, e.g. 3d or 2w, including overdue ones
+    #[arg(long)]
+    due_within: Option<String>,
+
+    /// Search title and description
+    #[arg(short = 'q', long)]
+    text: Option<String>,
+
+    /// created, updated, completed, priority, status, title, points, due or id
+    #[arg(long, default_value = "created")]
+    sort: String,
+
+    /// Sort in descending order
+    #[arg(long)]
+    desc: bool,
+
+    #[arg(long)]
+    limit: Option<u32>,
+
+    #[arg(long, default_value_t = 0)]
+    offset: u32,
+}
+
+impl TaskListArgs {
+    /// Last due date matched by `--overdue` or `--due-within`, counting from today.
+    fn due_by(&self) -> Result<Option<NaiveDate>> {
+        let today = Local::now().date_naive();
+        if self.overdue {
+            return Ok(today.pred_opt());
+        }
+        match &self.due_within {
+            Some(days) => Ok(today.checked_add_days(Days::new(parse_days(days)?.into()))),
+            None => Ok(None),
+        }
+    }
+
+    fn to_filter(&self) -> Result<TaskFilter> {
+        let range = |after: &Option<String>, before: &Option<String>| -> Result<DateRange> {
+            Ok(DateRange {
+                from: after.as_deref().map(|s| parse_date_bound(s, false)).transpose()?,
+                to: before.as_deref().map(|s| parse_date_bound(s, true)).transpose()?,
+            })
+        };
+        Ok(TaskFilter {
+            statuses: self.status.iter().map(|s| parse_task_status(s)).collect::<anyhow::Result<_>>()?,
+            task_types: self.task_type.iter().map(|t| parse_task_type(t)).collect::<anyhow::Result<_>>()?,
+            priorities: self.priority.iter().map(|p| parse_priority(p)).collect::<anyhow::Result<_>>()?,
+            sprint_id: self.sprint.clone(),
+            parent_id: self.parent.clone(),
+            assignee: self.assignee.clone(),
+            labels_any: self.label.clone(),
+            labels_all: self.all_labels.clone(),
+            created: range(&self.created_after, &self.created_before)?,
+            updated: range(&self.updated_after, &self.updated_before)?,
+            completed: range(&self.completed_after, &self.completed_before)?,
+            due_by: self.due_by()?,
+            text: self.text.clone(),
+            sort_by: self.sort.parse()?,
+            descending: self.desc,
+            limit: self.limit,
+            offset: self.offset,
+        })
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateAction {
+    /// List built-in templates and those in .llmdocs/templates
+    List {
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum BulkAction {
     /// Update status for multiple tasks
     Status {
+        /// Comma-separated task IDs
+        #[arg(required = true, num_args = 1, value_delimiter = ',')]
         task_ids: Vec<String>,
         status: String,
     },
     
     /// Assign tasks to sprint
     Sprint {
+        /// Comma-separated task IDs
+        #[arg(required = true, num_args = 1, value_delimiter = ',')]
         task_ids: Vec<String>,
         sprint_id: String,
     },
@@ -96,32 +360,53 @@ pub enum BulkAction {
 
 pub async fn execute(
     cmd: TaskCommands,
-    service: Arc<TaskService>,
+    services: &Services,
     config: &Config, // Keep config for print_task
 ) -> Result<()> {
+    let service = &services.task_service;
+    let dependency_service = &services.dependency_service;
+    let hierarchy_service = &services.hierarchy_service;
+    let time_tracking_service = &services.time_tracking_service;
+    let keys = &services.key_service;
     match cmd {
-        TaskCommands::Add { json } => {
-            let task = if let Some(json) = json {
+        TaskCommands::Add { json, template, set } => {
+            let mut task: Task = if let Some(json) = json {
                 serde_json::from_str(&json)?
+            } else if let Some(name) = template {
+                let values = set
+                    .iter()
+                    .map(|pair| match pair.split_once('=') {
+                        Some((name, value)) => Ok((name.trim().to_string(), value.to_string())),
+                        None => Err(errors::Error::CliArgumentError(format!("Expected NAME=VALUE, got '{}'", pair))),
+                    })
+                    .collect::<Result<_>>()?;
+                services.template_service.get_template(&name).await?.instantiate(&values)?
             } else {
                 // Interactive mode
                 create_task_interactive()?
             };
+            if let Some(sprint_id) = task.sprint_id.take() {
+                task.sprint_id = Some(keys.resolve("sprint", &sprint_id).await?);
+            }
+            if let Some(parent_id) = task.parent_id.take() {
+                task.parent_id = Some(keys.resolve("task", &parent_id).await?);
+            }
+            task.dependencies = keys.resolve_all("task", std::mem::take(&mut task.dependencies)).await?;
             
             let created = service.create_task(task).await?;
-            println!("{} Task {} created", "✓".green(), created.id.cyan());
+            println!("{} Task {} created ({})", "✓".green(), created.key.as_deref().unwrap_or(&created.id).cyan(), created.id.dimmed());
             Ok(())
         }
         
-        TaskCommands::Get { task_id, history: _history } => {
+        TaskCommands::Get { task_id, history } => {
+            let task_id = keys.resolve("task", &task_id).await?;
             match service.get_task_by_id(task_id.clone()).await? {
                 Some(task) => {
                     print_task(&task, config);
-                    // History is not yet implemented in TaskService
-                    // if history {
-                    //     let history = service.get_task_history(&task_id).await?;
-                    //     print_task_history(&history);
-                    // }
+                    if history {
+                        let history = service.get_task_history(task_id).await?;
+                        print_history(&history);
+                    }
                 }
                 None => {
                     eprintln!("{} Task {} not found", "✗".red(), task_id);
@@ -130,12 +415,25 @@ pub async fn execute(
             Ok(())
         }
         
-        TaskCommands::List { status: _status, sprint: _sprint, assignee: _assignee, format } => {
-            // TaskQuery is not used directly here, list_tasks is simplified
-            let tasks = service.list_tasks().await?;
+        TaskCommands::List { filter, format } => {
+            let mut filter = filter.to_filter()?;
+            if let Some(sprint_id) = filter.sprint_id.take() {
+                filter.sprint_id = Some(keys.resolve("sprint", &sprint_id).await?);
+            }
+            if let Some(parent_id) = filter.parent_id.take() {
+                filter.parent_id = Some(keys.resolve("task", &parent_id).await?);
+            }
+            let paginated = filter.limit.is_some() || filter.offset > 0;
+            let total = if paginated { Some(service.count_tasks(filter.clone()).await?) } else { None };
+            let tasks = service.find_tasks(filter.clone()).await?;
             
             match format.unwrap_or(OutputFormat::Table) {
-                OutputFormat::Table => print_task_table(&tasks),
+                OutputFormat::Table => {
+                    print_task_table(&tasks);
+                    if let Some(total) = total {
+                        print_page_info(filter.offset, tasks.len(), total);
+                    }
+                }
                 OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&tasks)?),
                 OutputFormat::Csv => print_task_csv(&tasks)?,
             }
@@ -143,9 +441,15 @@ pub async fn execute(
             Ok(())
         }
         
-        TaskCommands::Update { task_id, status, assignee, sprint, points } => {
+        TaskCommands::Update {
+            task_id, status, assignee, sprint, points, estimate, remaining, parent, no_parent, start, no_start, due, no_due, if_version,
+        } => {
+            let task_id = keys.resolve("task", &task_id).await?;
             let mut task = service.get_task_by_id(task_id.clone()).await?
                 .ok_or_else(|| errors::Error::NotFound(format!("Task {} not found", task_id)))?;
+            if let Some(version) = if_version {
+                task.version = version;
+            }
             
             if let Some(s) = status {
                 task.status = parse_task_status(&s)?;
@@ -153,47 +457,181 @@ pub async fn execute(
             if let Some(a) = assignee {
                 task.assignee = Some(a);
             }
+            let moved = sprint.is_some();
             if let Some(s) = sprint {
-                task.sprint_id = Some(s);
+                task.sprint_id = Some(keys.resolve("sprint", &s).await?);
             }
             if let Some(p) = points {
                 task.story_points = Some(p);
             }
+            if let Some(e) = estimate {
+                task.original_estimate_minutes = Some(parse_duration(&e)?);
+            }
+            if let Some(r) = remaining {
+                task.remaining_estimate_minutes = Some(parse_duration(&r)?);
+            }
+            if let Some(p) = parent {
+                task.parent_id = Some(keys.resolve("task", &p).await?);
+            } else if no_parent {
+                task.parent_id = None;
+            }
+            if let Some(s) = start {
+                task.start_date = Some(parse_date(&s)?);
+            } else if no_start {
+                task.start_date = None;
+            }
+            if let Some(d) = due {
+                task.due_date = Some(parse_date(&d)?);
+            } else if no_due {
+                task.due_date = None;
+            }
             
             let updated = service.update_task(task).await?;
-            println!("{} Task {} updated", "✓".green(), updated.id.cyan());
+            println!("{} Task {} updated (version {})", "✓".green(), updated.id.cyan(), updated.version);
+            if let (true, Some(sprint_id), Some(assignee)) = (moved, &updated.sprint_id, &updated.assignee) {
+                let report = services.capacity_service.get_capacity(sprint_id).await?;
+                print_capacity_warnings(&report, std::slice::from_ref(assignee));
+            }
             Ok(())
         }
         
         TaskCommands::Delete { task_id, force } => {
+            let task_id = keys.resolve("task", &task_id).await?;
             if force || Confirm::new()
                 .with_prompt(format!("Are you sure you want to delete task {}?", task_id))
                 .default(false)
                 .interact()?
             {
-                service.delete_task(task_id.clone()).await?;
-                println!("{} Task {} deleted", "✓".green(), task_id.cyan());
+                let references = service.delete_task(task_id.clone()).await?;
+                println!("{} Task {} moved to trash", "✓".green(), task_id.cyan());
+                print_dangling_references(&task_id, &references);
             } else {
                 println!("Deletion cancelled.");
             }
             Ok(())
         }
         
+        TaskCommands::Deps { task_id, tree, reverse, format } => {
+            let task_id = keys.resolve("task", &task_id).await?;
+            let json = matches!(format, Some(OutputFormat::Json));
+            if tree || reverse {
+                let tree = dependency_service.get_tree(&task_id, reverse).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&tree)?);
+                } else {
+                    print_dependency_tree(&tree);
+                }
+            } else {
+                let dependencies = dependency_service.get_dependencies(&task_id).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&dependencies)?);
+                } else {
+                    print_task_dependencies(&dependencies);
+                }
+            }
+            Ok(())
+        }
+
+        TaskCommands::Ready { plan, format } => {
+            let format = format.unwrap_or(OutputFormat::Table);
+            if plan {
+                let plan = dependency_service.get_plan().await?;
+                match format {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+                    _ => print_dependency_plan(&plan),
+                }
+            } else {
+                let tasks = dependency_service.get_ready_tasks().await?;
+                match format {
+                    OutputFormat::Table => print_task_table(&tasks),
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&tasks)?),
+                    OutputFormat::Csv => print_task_csv(&tasks)?,
+                }
+            }
+            Ok(())
+        }
+
+        TaskCommands::Tree { task_id, format } => {
+            let trees = match task_id {
+                Some(id) => vec![hierarchy_service.get_tree(&keys.resolve("task", &id).await?).await?],
+                None => hierarchy_service.get_forest().await?,
+            };
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&trees)?),
+                _ => print_task_hierarchy(&trees),
+            }
+            Ok(())
+        }
+
+        TaskCommands::Log { task_id, duration, note, author, date, remaining, format } => {
+            let task_id = keys.resolve("task", &task_id).await?;
+            let Some(duration) = duration else {
+                let entries = time_tracking_service.get_work_log(&task_id).await?;
+                match format.unwrap_or(OutputFormat::Table) {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+                    _ => print_work_log(&entries),
+                }
+                return Ok(());
+            };
+            let mut entry = WorkLogEntry::new(task_id, parse_duration(&duration)?, author, note);
+            if let Some(date) = date {
+                entry.started_at = parse_date_bound(&date, false)?;
+            }
+            let remaining = remaining.map(|r| parse_duration(&r)).transpose()?;
+            let (entry, task) = time_tracking_service.log_work(entry, remaining).await?;
+            print!("{} Logged {} on {}", "✓".green(), format_duration(entry.duration_minutes), task.id.cyan());
+            match task.remaining_estimate_minutes {
+                Some(remaining) => println!(" ({} remaining)", format_duration(remaining)),
+                None => println!(),
+            }
+            Ok(())
+        }
+
+        TaskCommands::Time { by, sprint, format } => {
+            let sprint = match sprint {
+                Some(sprint) => Some(keys.resolve("sprint", &sprint).await?),
+                None => None,
+            };
+            let report = time_tracking_service.get_report(by.parse()?, sprint).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                _ => print_time_report(&report),
+            }
+            Ok(())
+        }
+
+        TaskCommands::Template { action } => {
+            match action {
+                TemplateAction::List { format } => {
+                    let templates = services.template_service.list_templates().await?;
+                    match format.unwrap_or(OutputFormat::Table) {
+                        OutputFormat::Table => print_template_table(&templates)?,
+                        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&templates)?),
+                        OutputFormat::Csv => {
+                            return Err(errors::Error::CliArgumentError("CSV output is not supported for templates".to_string()));
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+
         TaskCommands::Bulk { action } => {
             match action {
-                BulkAction::Status { task_ids: _task_ids, status: _status } => {
-                    // Bulk update status not yet implemented in service
-                    // let parsed_status = parse_task_status(&status)?;
-                    // let updated_count = service.bulk_update_status(&task_ids, parsed_status, None).await?.len();
-                    // println!("{} Updated status for {} tasks", "✓".green(), updated_count);
-                    println!("{} Bulk status update not yet implemented.", "⚠".yellow());
+                BulkAction::Status { task_ids, status } => {
+                    let task_ids = keys.resolve_all("task", task_ids).await?;
+                    let parsed_status = parse_task_status(&status)?;
+                    let updated = service.bulk_update_status(task_ids, parsed_status, None).await?;
+                    println!("{} Updated status for {} tasks", "✓".green(), updated.len());
                     Ok(())
                 }
-                BulkAction::Sprint { task_ids: _task_ids, sprint_id: _sprint_id } => {
-                    // Bulk add to sprint not yet implemented in service
-                    // let updated_count = service.bulk_add_to_sprint(&task_ids, &sprint_id, None).await?.len();
-                    // println!("{} Added {} tasks to sprint {}", "✓".green(), updated_count, sprint_id.cyan());
-                    println!("{} Bulk sprint assignment not yet implemented.", "⚠".yellow());
+                BulkAction::Sprint { task_ids, sprint_id } => {
+                    let task_ids = keys.resolve_all("task", task_ids).await?;
+                    let sprint_id = keys.resolve("sprint", &sprint_id).await?;
+                    let updated = service.bulk_assign_sprint(task_ids, sprint_id.clone(), None).await?;
+                    println!("{} Added {} tasks to sprint {}", "✓".green(), updated.len(), sprint_id.cyan());
+                    let assignees: Vec<String> = updated.iter().filter_map(|t| t.assignee.clone()).collect();
+                    print_capacity_warnings(&services.capacity_service.get_capacity(&sprint_id).await?, &assignees);
                     Ok(())
                 }
             }
@@ -203,9 +641,10 @@ pub async fn execute(
 
 fn create_task_interactive() -> Result<Task> {
     let id: String = Input::new()
-        .with_prompt("Task ID (e.g., TASK-001)")
+        .with_prompt("Task ID (e.g., TASK-001; leave empty to generate one)")
+        .allow_empty(true)
         .validate_with(|input: &String| -> std::result::Result<(), &str> { // Use std::result::Result
-            if validation::is_valid_task_id(input) {
+            if input.is_empty() || validation::is_valid_task_id(input) {
                 Ok(())
             } else {
                 Err("Invalid task ID format (use TASK-XXX)")
@@ -0,0 +1,93 @@
+// llmdoc/src/cli/commands/trash_cmds.rs
+
+use clap::Subcommand;
+use std::sync::Arc;
+use chrono::Utc;
+use colored::*;
+use dialoguer::Confirm;
+
+use crate::core::errors::{Error, Result};
+use crate::services::trash_service::{TrashService, TrashServiceTrait};
+use crate::services::key_service::{KeyService, KeyServiceTrait};
+use crate::cli::output::{parse_age, print_trash_table, OutputFormat};
+
+#[derive(Subcommand, Debug)]
+pub enum TrashCommands {
+    /// List deleted items
+    List {
+        /// Only show one entity type (task, sprint, adr, component, user_story)
+        #[arg(short = 't', long = "type")]
+        entity_type: Option<String>,
+
+        #[arg(short, long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Restore a deleted item
+    Restore {
+        id: String,
+    },
+
+    /// Permanently remove deleted items
+    Purge {
+        /// Purge a single item
+        id: Option<String>,
+
+        /// Purge everything deleted longer ago than this (e.g. 30d, 2w, 12h)
+        #[arg(long)]
+        older_than: Option<String>,
+
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+pub async fn execute(cmd: TrashCommands, service: Arc<TrashService>, keys: Arc<KeyService>) -> Result<()> {
+    match cmd {
+        TrashCommands::List { entity_type, format } => {
+            let tombstones = service.list_trash(entity_type).await?;
+            match format.unwrap_or(OutputFormat::Table) {
+                OutputFormat::Table => print_trash_table(&tombstones),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&tombstones)?),
+                OutputFormat::Csv => {
+                    return Err(Error::CliArgumentError("CSV output is not supported for trash".to_string()));
+                }
+            }
+            Ok(())
+        }
+
+        TrashCommands::Restore { id } => {
+            let id = keys.resolve_any(&id).await?;
+            let entity_type = service.restore(&id).await?;
+            println!("{} Restored {} {}", "✓".green(), entity_type, id.cyan());
+            Ok(())
+        }
+
+        TrashCommands::Purge { id, older_than, force } => {
+            let prompt = match (&id, &older_than) {
+                (Some(id), None) => format!("Permanently delete {}? This cannot be undone", id),
+                (None, Some(age)) => format!("Permanently delete everything in the trash older than {}? This cannot be undone", age),
+                _ => {
+                    return Err(Error::CliArgumentError("Specify either an ID or --older-than".to_string()));
+                }
+            };
+            if !force && !Confirm::new().with_prompt(prompt).default(false).interact()? {
+                println!("Purge cancelled.");
+                return Ok(());
+            }
+
+            if let Some(id) = id {
+                let purged = service.purge(&keys.resolve_any(&id).await?).await?;
+                println!("{} Purged {} {}", "✓".green(), purged.entity_type, purged.id.cyan());
+            } else if let Some(age) = older_than {
+                let cutoff = Utc::now() - parse_age(&age)?;
+                let purged = service.purge_deleted_before(cutoff).await?;
+                for tombstone in &purged {
+                    println!("  - {} {}", tombstone.entity_type, tombstone.id);
+                }
+                println!("{} Purged {} items", "✓".green(), purged.len());
+            }
+            Ok(())
+        }
+    }
+}
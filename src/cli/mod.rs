@@ -9,5 +9,9 @@ pub mod output;
 pub use commands::{Cli, Commands};
 
 pub async fn process_cli_command(cli: Cli, config: &Config) -> Result<(), Error> { // Change return type to include Error
-    commands::execute(cli, config).await
+    let result = commands::execute(cli, config).await;
+    if let Err(err @ Error::Conflict { .. }) = &result {
+        output::print_conflict(err);
+    }
+    result
 }
\ No newline at end of file
@@ -1,10 +1,15 @@
 // llmdoc/src/cli/output.rs
 use anyhow::Result;
 use crate::core::models::{
-    task::{Task, TaskStatus, TaskType},
+    task::{Priority, Task, TaskStatus, TaskType},
     sprint::{Sprint, SprintStatus},
+    history::HistoryEntry,
+    comment::Comment,
+    task_template::TaskTemplate,
+    label::Label,
+    work_log::{format_duration, WorkLogEntry},
 };
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use colored::*;
 use comfy_table::{presets::*, Cell, Color, ContentArrangement, Table};
 
@@ -29,6 +34,7 @@ pub fn print_task_table(tasks: &[Task]) {
         .load_preset(UTF8_FULL)
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
+            Cell::new("Key").fg(Color::Blue),
             Cell::new("ID").fg(Color::Blue),
             Cell::new("Title"),
             Cell::new("Status"),
@@ -36,35 +42,29 @@ pub fn print_task_table(tasks: &[Task]) {
             Cell::new("Sprint"),
             Cell::new("Assignee"),
             Cell::new("Points"),
+            Cell::new("Due"),
             Cell::new("Updated"),
         ]);
     
+    let today = Local::now().date_naive();
     for task in tasks {
-        let status_cell = match task.status {
-            TaskStatus::Todo => Cell::new("TODO").fg(Color::White),
-            TaskStatus::InProgress => Cell::new("IN PROGRESS").fg(Color::Yellow),
-            TaskStatus::Done => Cell::new("DONE").fg(Color::Green),
-            TaskStatus::Blocked => Cell::new("BLOCKED").fg(Color::Red),
-            TaskStatus::Cancelled => Cell::new("CANCELLED").fg(Color::DarkGrey),
+        // Overdue rows stand out by key, title and due date
+        let overdue = task.is_overdue(today);
+        let highlight = |cell: Cell, normal: Option<Color>| match (overdue, normal) {
+            (true, _) => cell.fg(Color::Red),
+            (false, Some(color)) => cell.fg(color),
+            (false, None) => cell,
         };
-        
-        let type_cell = match task.task_type {
-            TaskType::Bug => Cell::new("🐛 Bug").fg(Color::Red),
-            TaskType::Feature => Cell::new("✨ Feature").fg(Color::Green),
-            TaskType::Task => Cell::new("📋 Task"),
-            TaskType::Epic => Cell::new("🏔️ Epic").fg(Color::Magenta),
-            TaskType::Story => Cell::new("📖 Story").fg(Color::Blue),
-            TaskType::Spike => Cell::new("🔬 Spike").fg(Color::Yellow),
-        };
-        
         table.add_row(vec![
-            Cell::new(&task.id).fg(Color::Cyan),
-            Cell::new(&task.title),
-            status_cell,
-            type_cell,
+            highlight(Cell::new(task.key.as_deref().unwrap_or("-")), Some(Color::Cyan)),
+            Cell::new(&task.id),
+            highlight(Cell::new(&task.title), None),
+            status_cell(&task.status),
+            type_cell(&task.task_type),
             Cell::new(task.sprint_id.as_deref().unwrap_or("-")),
             Cell::new(task.assignee.as_deref().unwrap_or("-")),
             Cell::new(task.story_points.map_or("-".to_string(), |p| p.to_string())),
+            highlight(Cell::new(task.due_date.map_or("-".to_string(), |d| d.to_string())), None),
             Cell::new(format_datetime(&task.updated_at)),
         ]);
     }
@@ -73,9 +73,33 @@ pub fn print_task_table(tasks: &[Task]) {
     println!("\n{} {} tasks", "Total:".dimmed(), tasks.len());
 }
 
+fn status_cell(status: &TaskStatus) -> Cell {
+    match status {
+        TaskStatus::Todo => Cell::new("TODO").fg(Color::White),
+        TaskStatus::InProgress => Cell::new("IN PROGRESS").fg(Color::Yellow),
+        TaskStatus::Done => Cell::new("DONE").fg(Color::Green),
+        TaskStatus::Blocked => Cell::new("BLOCKED").fg(Color::Red),
+        TaskStatus::Cancelled => Cell::new("CANCELLED").fg(Color::DarkGrey),
+    }
+}
+
+fn type_cell(task_type: &TaskType) -> Cell {
+    match task_type {
+        TaskType::Bug => Cell::new("🐛 Bug").fg(Color::Red),
+        TaskType::Feature => Cell::new("✨ Feature").fg(Color::Green),
+        TaskType::Task => Cell::new("📋 Task"),
+        TaskType::Epic => Cell::new("🏔️ Epic").fg(Color::Magenta),
+        TaskType::Story => Cell::new("📖 Story").fg(Color::Blue),
+        TaskType::Spike => Cell::new("🔬 Spike").fg(Color::Yellow),
+    }
+}
+
 /// Print a single task with details
 pub fn print_task(task: &Task, _config: &crate::app_config::Config) {
-    println!("{}", format!("Task {}", task.id).cyan().bold());
+    match &task.key {
+        Some(key) => println!("{} {}", format!("Task {}", key).cyan().bold(), task.id.dimmed()),
+        None => println!("{}", format!("Task {}", task.id).cyan().bold()),
+    }
     println!("{}", "═".repeat(50));
     
     println!("{}: {}", "Title".bold(), task.title);
@@ -93,13 +117,17 @@ pub fn print_task(task: &Task, _config: &crate::app_config::Config) {
     };
     println!("{}: {}", "Status".bold(), status_str);
     
-    println!("{}: {}", "Type".bold(), format!("{:?}", task.task_type));
-    println!("{}: {}", "Priority".bold(), format!("{:?}", task.priority));
+    println!("{}: {:?}", "Type".bold(), task.task_type);
+    println!("{}: {:?}", "Priority".bold(), task.priority);
     
     if let Some(sprint) = &task.sprint_id {
         println!("{}: {}", "Sprint".bold(), sprint);
     }
     
+    if let Some(parent) = &task.parent_id {
+        println!("{}: {}", "Parent".bold(), parent);
+    }
+    
     if let Some(assignee) = &task.assignee {
         println!("{}: {}", "Assignee".bold(), assignee);
     }
@@ -107,6 +135,29 @@ pub fn print_task(task: &Task, _config: &crate::app_config::Config) {
     if let Some(points) = task.story_points {
         println!("{}: {}", "Story Points".bold(), points);
     }
+    if task.carry_over_count > 0 {
+        println!("{}: {} time(s)", "Carried Over".bold(), task.carry_over_count);
+    }
+    
+    if let Some(original) = task.original_estimate_minutes {
+        println!("{}: {}", "Estimate".bold(), format_duration(original));
+    }
+    
+    if let Some(remaining) = task.remaining_estimate_minutes {
+        println!("{}: {}", "Remaining".bold(), format_duration(remaining));
+    }
+    
+    if let Some(start) = task.start_date {
+        println!("{}: {}", "Start Date".bold(), start);
+    }
+    
+    if let Some(due) = task.due_date {
+        if task.is_overdue(Local::now().date_naive()) {
+            println!("{}: {} {}", "Due Date".bold(), due.to_string().red(), "(overdue)".red());
+        } else {
+            println!("{}: {}", "Due Date".bold(), due);
+        }
+    }
     
     if !task.labels.is_empty() {
         println!("{}: {}", "Labels".bold(), task.labels.join(", "));
@@ -125,38 +176,339 @@ pub fn print_task(task: &Task, _config: &crate::app_config::Config) {
         format_datetime(&task.updated_at),
         task.updated_by.as_deref().unwrap_or("").dimmed()
     );
+    println!("  Version: {}", task.version);
     
     if let Some(completed) = &task.completed_at {
         println!("  Completed: {}", format_datetime(completed).green());
     }
 }
 
-/// Print task history
-pub fn print_task_history(history: &[serde_json::Value]) {
+/// Print the open dependencies of a task and the tasks it holds up
+pub fn print_task_dependencies(dependencies: &crate::services::dependency_service::TaskDependencies) {
+    println!("{}", format!("Dependencies of {}", dependencies.task_id).cyan().bold());
+    let list = |ids: &[String]| if ids.is_empty() { "-".dimmed().to_string() } else { ids.join(", ") };
+    println!("  {}: {}", "Blocked by".bold(), list(&dependencies.blocked_by));
+    println!("  {}: {}", "Blocks".bold(), list(&dependencies.blocks));
+}
+
+/// Print a dependency tree with box-drawing branches
+pub fn print_dependency_tree(tree: &crate::core::dependency_graph::DependencyTree) {
+    println!("{}", dependency_label(tree));
+    print_dependency_children(&tree.children, "");
+}
+
+fn print_dependency_children(children: &[crate::core::dependency_graph::DependencyTree], prefix: &str) {
+    for (idx, child) in children.iter().enumerate() {
+        let last = idx == children.len() - 1;
+        println!("{}{}{}", prefix, if last { "└── " } else { "├── " }, dependency_label(child));
+        let next = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        print_dependency_children(&child.children, &next);
+    }
+}
+
+fn dependency_label(node: &crate::core::dependency_graph::DependencyTree) -> String {
+    let status = match &node.status {
+        Some(status) if crate::core::dependency_graph::is_finished(status) => format!("{:?}", status).green(),
+        Some(TaskStatus::Blocked) => "Blocked".red(),
+        Some(status) => format!("{:?}", status).yellow(),
+        None => "missing".dimmed(),
+    };
+    let mut label = format!("{} {} [{}]", node.id.cyan(), node.title.as_deref().unwrap_or(""), status);
+    if node.repeated {
+        label.push_str(&format!(" {}", "(cycle)".red()));
+    }
+    label
+}
+
+/// Print open tasks grouped into waves that can be worked on in order
+pub fn print_dependency_plan(plan: &crate::core::dependency_graph::DependencyPlan) {
+    if plan.waves.is_empty() && plan.cyclic.is_empty() {
+        println!("No open tasks.");
+        return;
+    }
+    for (idx, wave) in plan.waves.iter().enumerate() {
+        let heading = if idx == 0 { "Wave 1 (ready now)".to_string() } else { format!("Wave {}", idx + 1) };
+        println!("{} {}", heading.bold(), wave.join(", "));
+    }
+    if !plan.cyclic.is_empty() {
+        println!("{} {}", "In a dependency cycle:".red().bold(), plan.cyclic.join(", "));
+    }
+}
+
+/// Print task hierarchies as an indented table with rolled-up points and progress
+pub fn print_task_hierarchy(trees: &[crate::core::task_hierarchy::TaskTree]) {
+    if trees.is_empty() {
+        println!("No tasks.");
+        return;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("ID").fg(Color::Blue),
+            Cell::new("Title"),
+            Cell::new("Type"),
+            Cell::new("Status"),
+            Cell::new("Points"),
+            Cell::new("Done"),
+            Cell::new("Progress"),
+        ]);
+    for tree in trees {
+        add_hierarchy_rows(&mut table, tree, "", "");
+    }
+    println!("{table}");
+}
+
+fn add_hierarchy_rows(table: &mut Table, node: &crate::core::task_hierarchy::TaskTree, branch: &str, prefix: &str) {
+    let progress = if node.percent_complete >= 100.0 {
+        Cell::new("100%").fg(Color::Green)
+    } else {
+        Cell::new(format!("{:.0}%", node.percent_complete))
+    };
+    table.add_row(vec![
+        Cell::new(format!("{}{}", branch, node.id)).fg(Color::Cyan),
+        Cell::new(&node.title),
+        type_cell(&node.task_type),
+        status_cell(&node.status),
+        Cell::new(node.total_points),
+        Cell::new(format!("{}/{}", node.done_items, node.total_items)),
+        progress,
+    ]);
+    for (idx, child) in node.children.iter().enumerate() {
+        let last = idx == node.children.len() - 1;
+        let branch = format!("{}{}", prefix, if last { "└─ " } else { "├─ " });
+        let next = format!("{}{}", prefix, if last { "   " } else { "│  " });
+        add_hierarchy_rows(table, child, &branch, &next);
+    }
+}
+
+/// Print the time entries logged against a task
+pub fn print_work_log(entries: &[WorkLogEntry]) {
+    if entries.is_empty() {
+        println!("No time logged.");
+        return;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["#", "Date", "Author", "Time", "Note"]);
+    for entry in entries {
+        table.add_row(vec![
+            Cell::new(entry.id),
+            Cell::new(format_datetime(&entry.started_at)),
+            Cell::new(entry.author.as_deref().unwrap_or("-")),
+            Cell::new(format_duration(entry.duration_minutes)),
+            Cell::new(entry.note.as_deref().unwrap_or("")),
+        ]);
+    }
+    println!("{table}");
+    let total: u32 = entries.iter().map(|e| e.duration_minutes).sum();
+    println!("\n{} {}", "Total:".dimmed(), format_duration(total));
+}
+
+/// Print estimated against logged time per task, sprint or assignee
+pub fn print_time_report(report: &crate::services::time_tracking_service::TimeReport) {
+    use crate::services::time_tracking_service::{TimeGrouping, TimeSummary};
+
+    let heading = match report.group_by {
+        TimeGrouping::Task => "Task",
+        TimeGrouping::Sprint => "Sprint",
+        TimeGrouping::Assignee => "Assignee",
+    };
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![heading, "Tasks", "Estimate", "Logged", "Remaining", "Variance"]);
+    let row = |summary: &TimeSummary| {
+        let variance = summary.variance_minutes();
+        let variance_cell = match variance {
+            0 => Cell::new("-"),
+            v if v > 0 => Cell::new(format!("+{}", format_duration(v as u32))).fg(Color::Red),
+            v => Cell::new(format!("-{}", format_duration(v.unsigned_abs() as u32))).fg(Color::Green),
+        };
+        vec![
+            Cell::new(&summary.key),
+            Cell::new(summary.tasks),
+            Cell::new(format_duration(summary.original_estimate_minutes)),
+            Cell::new(format_duration(summary.logged_minutes)),
+            Cell::new(format_duration(summary.remaining_estimate_minutes)),
+            variance_cell,
+        ]
+    };
+    for summary in &report.rows {
+        table.add_row(row(summary));
+    }
+    table.add_row(row(&report.total));
+    println!("{table}");
+}
+
+/// Print a comment thread, replies indented under the comment they answer
+pub fn print_comment_thread(comments: &[Comment]) {
+    if comments.is_empty() {
+        println!("No comments.");
+        return;
+    }
+    // Replies to comments that are not in the thread any more are shown at the top level
+    let is_root = |c: &Comment| c.reply_to.as_ref().is_none_or(|r| !comments.iter().any(|p| &p.id == r));
+    for comment in comments.iter().filter(|c| is_root(c)) {
+        print_comment(comments, comment, 0);
+    }
+}
+
+fn print_comment(comments: &[Comment], comment: &Comment, depth: usize) {
+    let indent = "    ".repeat(depth);
+    println!(
+        "{}{} {} {}",
+        indent,
+        comment.author.as_deref().unwrap_or("unknown").cyan().bold(),
+        format_datetime(&comment.created_at).dimmed(),
+        comment.id.dimmed()
+    );
+    for line in comment.body.lines() {
+        println!("{}  {}", indent, line);
+    }
+    println!();
+    for reply in comments.iter().filter(|c| c.reply_to.as_ref() == Some(&comment.id)) {
+        print_comment(comments, reply, depth + 1);
+    }
+}
+
+/// Print comments found by a search, with the entity each belongs to
+pub fn print_comment_table(comments: &[Comment]) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["On", "Author", "Date", "Comment"]);
+    for comment in comments {
+        table.add_row(vec![
+            Cell::new(format!("{} {}", comment.entity_type, comment.entity_id)).fg(Color::Cyan),
+            Cell::new(comment.author.as_deref().unwrap_or("-")),
+            Cell::new(format_datetime(&comment.created_at)),
+            Cell::new(comment.body.lines().next().unwrap_or("")),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// Print available task templates and the placeholders each needs
+pub fn print_template_table(templates: &[TaskTemplate]) -> Result<()> {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Name", "Type", "Priority", "Labels", "Needs", "About", "Source"]);
+    for template in templates {
+        table.add_row(vec![
+            Cell::new(&template.name).fg(Color::Cyan),
+            Cell::new(template.task_type.as_deref().unwrap_or("task")),
+            Cell::new(template.priority.as_deref().unwrap_or("medium")),
+            Cell::new(template.labels.join(", ")),
+            Cell::new(template.required_placeholders()?.join(", ")),
+            Cell::new(template.about.as_deref().unwrap_or("")),
+            Cell::new(&template.source),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Print the label registry
+pub fn print_label_table(labels: &[Label]) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Name", "Color", "Description", "Updated"]);
+    for label in labels {
+        table.add_row(vec![
+            label_cell(&label.name, label.color.as_deref()),
+            Cell::new(label.color.as_deref().unwrap_or("-")),
+            Cell::new(label.description.as_deref().unwrap_or("")),
+            Cell::new(format_datetime(&label.updated_at)),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// Print label usage, flagging labels that are not registered
+pub fn print_label_stats(stats: &[crate::services::label_service::LabelStats]) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Label", "Registered", "Tasks", "Open", "Closed"]);
+    for row in stats {
+        table.add_row(vec![
+            label_cell(&row.name, row.color.as_deref()),
+            if row.registered { Cell::new("yes") } else { Cell::new("no").fg(Color::Yellow) },
+            Cell::new(row.tasks),
+            Cell::new(row.open),
+            Cell::new(row.closed),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// A label name in its registered color, if any
+fn label_cell(name: &str, color: Option<&str>) -> Cell {
+    let cell = Cell::new(name);
+    let color = match color.map(str::to_lowercase).as_deref() {
+        Some("black") => Color::Black,
+        Some("red") => Color::Red,
+        Some("green") => Color::Green,
+        Some("yellow") => Color::Yellow,
+        Some("blue") => Color::Blue,
+        Some("magenta") => Color::Magenta,
+        Some("cyan") => Color::Cyan,
+        Some("white") => Color::White,
+        Some("grey") | Some("gray") => Color::Grey,
+        Some(hex) if hex.len() == 7 && hex.starts_with('#') => match u32::from_str_radix(&hex[1..], 16) {
+            Ok(rgb) => Color::Rgb { r: (rgb >> 16) as u8, g: (rgb >> 8) as u8, b: rgb as u8 },
+            Err(_) => return cell,
+        },
+        _ => return cell,
+    };
+    cell.fg(color)
+}
+
+/// Print which slice of a paginated listing is shown
+pub fn print_page_info(offset: u32, shown: usize, total: u64) {
+    if shown == 0 {
+        println!("{} no results at offset {} of {}", "Page:".dimmed(), offset, total);
+    } else {
+        println!("{} {}-{} of {}", "Page:".dimmed(), offset as u64 + 1, offset as u64 + shown as u64, total);
+    }
+}
+
+/// Print change history, one block per version
+pub fn print_history(history: &[HistoryEntry]) {
     println!("\n{}", "History".cyan().bold());
     println!("{}", "─".repeat(50));
-    
-    for (idx, entry) in history.iter().enumerate() {
-        if let Some(meta) = entry.get("_meta") {
-            let version = meta["version"].as_i64().unwrap_or(0);
-            let changed_at = meta["changed_at"].as_str().unwrap_or("");
-            let change_type = meta["change_type"].as_str().unwrap_or("");
-            let changed_by = meta["changed_by"].as_str().unwrap_or("unknown");
-            
+
+    if history.is_empty() {
+        println!("{}", "No recorded changes".dimmed());
+        return;
+    }
+
+    let mut previous: Option<(&str, u32)> = None;
+    for entry in history {
+        let key = (entry.entity_id.as_str(), entry.version);
+        if previous != Some(key) {
             println!(
                 "{} {} - {} by {}",
-                format!("v{}", version).dimmed(),
-                changed_at.dimmed(),
-                change_type.yellow(),
-                changed_by.cyan()
+                format!("v{}", entry.version).dimmed(),
+                format_datetime(&entry.changed_at).dimmed(),
+                entry.change_type.to_string().to_lowercase().yellow(),
+                entry.changed_by.as_deref().unwrap_or("unknown").cyan()
             );
-            
-            // Show what changed (simplified)
-            if idx < history.len() - 1 {
-                // Compare with previous version
-                // This is simplified - in production, use a proper diff library
-                println!("  Changes made in this version");
-            }
+            previous = Some(key);
+        }
+        if entry.field.is_some() {
+            println!("  {}", entry.summary());
         }
     }
 }
@@ -171,15 +523,16 @@ pub fn print_task_csv(tasks: &[Task]) -> Result<()> {
     let mut wtr = csv::Writer::from_writer(std::io::stdout());
     
     // Write header
-    wtr.write_record(&[
-        "ID", "Title", "Description", "Status", "Type", "Priority",
-        "Sprint", "Assignee", "Points", "Created", "Updated"
+    wtr.write_record([
+        "ID", "Key", "Title", "Description", "Status", "Type", "Priority",
+        "Sprint", "Assignee", "Points", "Start", "Due", "Created", "Updated"
     ])?;
     
     // Write tasks
     for task in tasks {
-        wtr.write_record(&[
+        wtr.write_record([
             &task.id,
+            task.key.as_deref().unwrap_or(""),
             &task.title,
             task.description.as_deref().unwrap_or(""),
             &format!("{:?}", task.status),
@@ -188,6 +541,8 @@ pub fn print_task_csv(tasks: &[Task]) -> Result<()> {
             task.sprint_id.as_deref().unwrap_or(""),
             task.assignee.as_deref().unwrap_or(""),
             &task.story_points.map_or(String::new(), |p| p.to_string()),
+            &task.start_date.map_or(String::new(), |d| d.to_string()),
+            &task.due_date.map_or(String::new(), |d| d.to_string()),
             &task.created_at.to_rfc3339(),
             &task.updated_at.to_rfc3339(),
         ])?;
@@ -197,16 +552,79 @@ pub fn print_task_csv(tasks: &[Task]) -> Result<()> {
     Ok(())
 }
 
+/// Print change history as CSV
+pub fn print_history_csv(history: &[HistoryEntry]) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(std::io::stdout());
+
+    wtr.write_record([
+        "Entity Type", "Entity ID", "Version", "Change", "Field",
+        "Old Value", "New Value", "Changed By", "Changed At"
+    ])?;
+
+    for entry in history {
+        wtr.write_record([
+            &entry.entity_type,
+            &entry.entity_id,
+            &entry.version.to_string(),
+            &entry.change_type.to_string(),
+            entry.field.as_deref().unwrap_or(""),
+            &entry.old_value.as_ref().map_or(String::new(), |v| v.to_string()),
+            &entry.new_value.as_ref().map_or(String::new(), |v| v.to_string()),
+            entry.changed_by.as_deref().unwrap_or(""),
+            &entry.changed_at.to_rfc3339(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
 /// Parse task status from string
 pub fn parse_task_status(s: &str) -> Result<TaskStatus> {
-    match s.to_lowercase().as_str() {
-        "todo" | "to-do" | "to_do" => Ok(TaskStatus::Todo),
-        "in_progress" | "in-progress" | "inprogress" | "doing" => Ok(TaskStatus::InProgress),
-        "done" | "completed" | "complete" => Ok(TaskStatus::Done),
-        "blocked" => Ok(TaskStatus::Blocked),
-        "cancelled" | "canceled" => Ok(TaskStatus::Cancelled),
-        _ => Err(crate::core::errors::Error::InvalidInput(format!("Invalid status: {}", s)).into()),
+    Ok(s.parse()?)
+}
+
+/// Parse task type from string
+pub fn parse_task_type(s: &str) -> Result<TaskType> {
+    Ok(s.parse()?)
+}
+
+/// Parse priority from string
+pub fn parse_priority(s: &str) -> Result<Priority> {
+    Ok(s.parse()?)
+}
+
+/// Parse a date filter bound: `YYYY-MM-DD` (start of day, or end of day with `end_of_day`) or RFC 3339
+pub fn parse_date_bound(s: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let time = if end_of_day {
+            chrono::NaiveTime::from_hms_milli_opt(23, 59, 59, 999)
+        } else {
+            chrono::NaiveTime::from_hms_opt(0, 0, 0)
+        };
+        return Ok(date.and_time(time.unwrap_or_default()).and_utc());
     }
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| crate::core::errors::Error::InvalidInput(format!("Invalid date (use YYYY-MM-DD or RFC 3339): {}", s)).into())
+}
+
+/// Parse a calendar date such as a due date: `YYYY-MM-DD`
+pub fn parse_date(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+        .map_err(|_| crate::core::errors::Error::InvalidInput(format!("Invalid date (use YYYY-MM-DD): {}", s)).into())
+}
+
+/// Parse a number of days: `3`, `3d` or `2w`
+pub fn parse_days(s: &str) -> Result<u32> {
+    let s = s.trim().to_lowercase();
+    let invalid = || crate::core::errors::Error::InvalidInput(format!("Invalid number of days (use e.g. 3d or 2w): {}", s));
+    let (number, factor) = match s.strip_suffix('w') {
+        Some(weeks) => (weeks, 7),
+        None => (s.strip_suffix('d').unwrap_or(&s), 1),
+    };
+    let days: u32 = number.trim().parse().map_err(|_| invalid())?;
+    Ok(days.checked_mul(factor).ok_or_else(invalid)?)
 }
 
 /// Print a formatted sprint table
@@ -216,6 +634,7 @@ pub fn print_sprint_table(sprints: &[Sprint]) {
         .load_preset(UTF8_FULL)
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
+            Cell::new("Key").fg(Color::Blue),
             Cell::new("ID").fg(Color::Blue),
             Cell::new("Name"),
             Cell::new("Status"),
@@ -233,7 +652,8 @@ pub fn print_sprint_table(sprints: &[Sprint]) {
         };
 
         table.add_row(vec![
-            Cell::new(&sprint.id).fg(Color::Cyan),
+            Cell::new(sprint.key.as_deref().unwrap_or("-")).fg(Color::Cyan),
+            Cell::new(&sprint.id),
             Cell::new(&sprint.name),
             status_cell,
             Cell::new(format_datetime(&sprint.start_date)),
@@ -246,6 +666,64 @@ pub fn print_sprint_table(sprints: &[Sprint]) {
     println!("\n{} {} sprints", "Total:".dimmed(), sprints.len());
 }
 
+/// Print sprints in date order, marking where `today` falls and any gaps or overlaps
+/// between consecutive sprints. Cancelled sprints are left out.
+pub fn print_sprint_calendar(sprints: &[Sprint], today: NaiveDate, past: Option<usize>) {
+    let mut sprints: Vec<&Sprint> = sprints.iter().filter(|s| s.status != SprintStatus::Cancelled).collect();
+    sprints.sort_by_key(|s| s.start_date);
+    let past_count = sprints.iter().filter(|s| s.end_date.date_naive() < today).count();
+    let skip = past.map_or(0, |keep| past_count.saturating_sub(keep));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("When"),
+            Cell::new("Key").fg(Color::Blue),
+            Cell::new("Name"),
+            Cell::new("Status"),
+            Cell::new("Start"),
+            Cell::new("End"),
+            Cell::new("Days"),
+        ]);
+    let mut notes = Vec::new();
+    for (index, sprint) in sprints.iter().enumerate().skip(skip) {
+        let (start, end) = (sprint.start_date.date_naive(), sprint.end_date.date_naive());
+        let when = if end < today {
+            Cell::new("past").fg(Color::DarkGrey)
+        } else if start > today {
+            Cell::new("upcoming")
+        } else {
+            Cell::new("current").fg(Color::Green)
+        };
+        table.add_row(vec![
+            when,
+            Cell::new(sprint.key.as_deref().unwrap_or("-")).fg(Color::Cyan),
+            Cell::new(&sprint.name),
+            Cell::new(sprint.status.to_string().to_uppercase()),
+            Cell::new(start.format("%Y-%m-%d")),
+            Cell::new(end.format("%Y-%m-%d")),
+            Cell::new((end - start).num_days() + 1),
+        ]);
+
+        if let Some(next) = sprints.get(index + 1) {
+            let label = |s: &Sprint| s.key.clone().unwrap_or_else(|| s.name.clone());
+            let gap = (next.start_date.date_naive() - end).num_days() - 1;
+            if next.start_date < sprint.end_date {
+                notes.push(format!("! {} overlaps {}", label(sprint), label(next)).red().to_string());
+            } else if gap > 0 {
+                notes.push(format!("  {} day(s) without a sprint between {} and {}", gap, label(sprint), label(next)).yellow().to_string());
+            }
+        }
+    }
+
+    println!("{table}");
+    for note in notes {
+        println!("{}", note);
+    }
+}
+
 /// Print a sprint report
 pub fn print_sprint_report(report: &crate::services::sprint_service::SprintReport) {
     println!("{}", format!("Sprint Report: {}", report.sprint_name).cyan().bold());
@@ -255,9 +733,34 @@ pub fn print_sprint_report(report: &crate::services::sprint_service::SprintRepor
     println!("{}: {}", "Total Tasks".bold(), report.total_tasks);
     println!("{}: {}", "Completed Tasks".bold(), report.completed_tasks);
     println!("{}: {}", "Remaining Tasks".bold(), report.remaining_tasks);
+    if report.overdue_tasks > 0 {
+        println!("{}: {}", "Overdue Tasks".bold(), report.overdue_tasks.to_string().red());
+    } else {
+        println!("{}: {}", "Overdue Tasks".bold(), report.overdue_tasks);
+    }
     println!("{}: {}", "Total Story Points".bold(), report.total_story_points);
     println!("{}: {}", "Completed Story Points".bold(), report.completed_story_points);
     println!("{}: {}", "Remaining Story Points".bold(), report.remaining_story_points);
+    if let Some(velocity) = report.velocity {
+        println!("{}: {}", "Velocity".bold(), velocity);
+    }
+    if let Some(to) = &report.carried_over_to {
+        println!(
+            "{}: {} tasks, {} points to {}",
+            "Carried Over".bold(),
+            report.carried_over_tasks,
+            report.carried_over_points,
+            to
+        );
+    }
+    if !report.repeatedly_carried.is_empty() {
+        println!("{}: {}", "Repeatedly Carried".bold(), report.repeatedly_carried.join(", ").yellow());
+    }
+    if report.original_estimate_minutes > 0 || report.logged_minutes > 0 {
+        println!("{}: {}", "Original Estimate".bold(), format_duration(report.original_estimate_minutes));
+        println!("{}: {}", "Time Logged".bold(), format_duration(report.logged_minutes));
+        println!("{}: {}", "Time Remaining".bold(), format_duration(report.remaining_estimate_minutes));
+    }
 
     if let Some(retro) = &report.retrospective {
         println!("\n{}", "Retrospective".dimmed());
@@ -276,6 +779,268 @@ pub fn print_sprint_report(report: &crate::services::sprint_service::SprintRepor
     }
 }
 
+/// Height of the ASCII charts, in rows above the zero line
+const CHART_HEIGHT: usize = 10;
+
+/// Print a sprint's burndown and burnup as ASCII charts
+pub fn print_burn_charts(burndown: &crate::core::burndown::Burndown) {
+    let days = &burndown.days;
+    if days.is_empty() {
+        return;
+    }
+    let series = |value: fn(&crate::core::burndown::BurndownDay) -> Option<f64>| days.iter().map(value).collect::<Vec<_>>();
+
+    println!("\n{}", format!("Burndown ({})", burndown.unit).bold());
+    print_line_chart(days, &[
+        ("remaining", '*', colored::Color::Red, series(|d| d.remaining.map(f64::from))),
+        ("ideal", '.', colored::Color::Green, series(|d| Some(d.ideal))),
+    ]);
+    println!("\n{}", format!("Burnup ({})", burndown.unit).bold());
+    print_line_chart(days, &[
+        ("completed", '*', colored::Color::Green, series(|d| d.completed.map(f64::from))),
+        ("scope", '-', colored::Color::Blue, series(|d| d.scope.map(f64::from))),
+        ("added", '+', colored::Color::Yellow, series(|d| d.added.map(f64::from))),
+    ]);
+}
+
+/// Plot each series as one mark per day; where marks meet, the earlier series is drawn
+fn print_line_chart(days: &[crate::core::burndown::BurndownDay], series: &[(&str, char, colored::Color, Vec<Option<f64>>)]) {
+    let max = series.iter().flat_map(|(_, _, _, values)| values.iter().flatten()).fold(1.0_f64, |max, v| max.max(*v));
+    let step = if days.len() <= 30 { 2 } else { 1 };
+    let width = days.len() * step;
+    let label_width = format!("{:.0}", max).len();
+
+    for row in (0..=CHART_HEIGHT).rev() {
+        let label = match row {
+            CHART_HEIGHT => format!("{:.0}", max),
+            0 => "0".to_string(),
+            _ => String::new(),
+        };
+        let mut line = format!("{:>w$} │", label, w = label_width);
+        for index in 0..days.len() {
+            let mark = series.iter().find(|(_, _, _, values)| {
+                values[index].is_some_and(|v| (v / max * CHART_HEIGHT as f64).round() as usize == row)
+            });
+            match mark {
+                Some((_, symbol, color, _)) => line.push_str(&symbol.to_string().color(*color).to_string()),
+                None => line.push(' '),
+            }
+            line.push_str(&" ".repeat(step - 1));
+        }
+        println!("{}", line.trim_end());
+    }
+    println!("{:>w$} └{}", "", "─".repeat(width), w = label_width);
+
+    let first = days[0].date.format("%m-%d").to_string();
+    let last = days[days.len() - 1].date.format("%m-%d").to_string();
+    let mut axis = first.clone();
+    if days.len() > 1 && width > first.len() + last.len() {
+        axis.push_str(&format!("{:>w$}", last, w = width - first.len()));
+    }
+    println!("{:>w$}  {}", "", axis, w = label_width);
+    let legend: Vec<String> = series
+        .iter()
+        .map(|(name, symbol, color, _)| format!("{} {}", symbol.to_string().color(*color), name))
+        .collect();
+    println!("{:>w$}  {}", "", legend.join("   "), w = label_width);
+}
+
+/// Print a sprint's burndown series as CSV
+pub fn print_burndown_csv(burndown: &crate::core::burndown::Burndown) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(std::io::stdout());
+    wtr.write_record(["Date", "Unit", "Scope", "Added", "Completed", "Remaining", "Ideal"])?;
+    let count = |value: Option<u32>| value.map_or(String::new(), |v| v.to_string());
+    for day in &burndown.days {
+        wtr.write_record([
+            day.date.to_string(),
+            burndown.unit.to_string(),
+            count(day.scope),
+            count(day.added),
+            count(day.completed),
+            count(day.remaining),
+            day.ideal.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Print the velocity of completed sprints with rolling averages
+pub fn print_velocity_report(report: &crate::core::forecast::VelocityReport) {
+    if report.sprints.is_empty() {
+        println!("No completed sprints.");
+        return;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Key").fg(Color::Blue),
+            Cell::new("Name"),
+            Cell::new("Ended"),
+            Cell::new("Velocity"),
+            Cell::new(format!("Rolling Avg ({})", report.window)),
+        ]);
+    for sprint in &report.sprints {
+        table.add_row(vec![
+            Cell::new(sprint.key.as_deref().unwrap_or(&sprint.sprint_id)).fg(Color::Cyan),
+            Cell::new(&sprint.name),
+            Cell::new(sprint.end_date.format("%Y-%m-%d")),
+            Cell::new(sprint.velocity),
+            Cell::new(sprint.rolling_average),
+        ]);
+    }
+    println!("{table}");
+    println!("{}: {}", "Average".bold(), report.average);
+    println!("{}: {}", "Standard Deviation".bold(), report.std_dev);
+    println!("{}: {}", format!("Rolling Average (last {})", report.window).bold(), report.rolling_average);
+}
+
+/// Print sprint velocities as CSV
+pub fn print_velocity_csv(report: &crate::core::forecast::VelocityReport) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(std::io::stdout());
+    wtr.write_record(["Sprint", "Key", "Name", "Ended", "Velocity", "Rolling Average"])?;
+    for sprint in &report.sprints {
+        wtr.write_record([
+            sprint.sprint_id.clone(),
+            sprint.key.clone().unwrap_or_default(),
+            sprint.name.clone(),
+            sprint.end_date.format("%Y-%m-%d").to_string(),
+            sprint.velocity.to_string(),
+            sprint.rolling_average.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Print a Monte Carlo forecast with a histogram of the simulated outcomes
+pub fn print_forecast(forecast: &crate::core::forecast::Forecast) {
+    println!("{}", format!("Forecast: {}", forecast.target).cyan().bold());
+    println!("{}", "═".repeat(50));
+    println!("{}: {} points in {} task(s)", "Open Work".bold(), forecast.remaining_points, forecast.open_tasks);
+    if forecast.unestimated_tasks > 0 {
+        println!("  {} {} open task(s) have no story points and are not counted", "!".yellow(), forecast.unestimated_tasks);
+    }
+    let velocities: Vec<String> = forecast.velocities.iter().map(f64::to_string).collect();
+    println!("{}: {} sprint(s) ({})", "Velocity Sample".bold(), velocities.len(), velocities.join(", "));
+    println!("{}: {} (seed {})", "Runs".bold(), forecast.runs, forecast.seed);
+
+    println!("\n{}", "Sprints Needed".dimmed());
+    for percentile in &forecast.percentiles {
+        println!("  {:>3}% chance within {} sprint(s)", percentile.percent, percentile.sprints.to_string().green());
+    }
+
+    println!("\n{}", "Distribution".dimmed());
+    let highest = forecast.distribution.iter().map(|b| b.probability).fold(0.0_f64, f64::max);
+    for bucket in &forecast.distribution {
+        let width = if highest > 0.0 { (bucket.probability / highest * 40.0).round() as usize } else { 0 };
+        println!(
+            "  {:>4} │{:<40} {:>5.1}%  {}",
+            bucket.sprints,
+            "█".repeat(width).blue(),
+            bucket.probability * 100.0,
+            format!("(≤ {:.1}%)", bucket.cumulative * 100.0).dimmed()
+        );
+    }
+}
+
+/// Print each member's capacity and planned load in a sprint
+pub fn print_capacity_report(report: &crate::services::capacity_service::CapacityReport) {
+    println!("{}", format!("Capacity: {}", report.sprint_name).cyan().bold());
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Member").fg(Color::Blue),
+            Cell::new("Days"),
+            Cell::new("Leave"),
+            Cell::new("Focus"),
+            Cell::new("Capacity"),
+            Cell::new("Load"),
+            Cell::new("Tasks"),
+            Cell::new("Status"),
+        ]);
+    for load in &report.members {
+        let availability = |value: fn(&crate::core::models::MemberAvailability) -> f64| {
+            load.availability.as_ref().map_or("-".to_string(), |a| value(a).to_string())
+        };
+        let status = match (load.capacity_points, load.over_by()) {
+            (None, _) => Cell::new("no availability").fg(Color::DarkGrey),
+            (Some(_), Some(over)) => Cell::new(format!("OVER by {:.1}", over)).fg(Color::Red),
+            (Some(_), None) => Cell::new("ok").fg(Color::Green),
+        };
+        let tasks = if load.unestimated_tasks > 0 {
+            format!("{} ({} unestimated)", load.tasks, load.unestimated_tasks)
+        } else {
+            load.tasks.to_string()
+        };
+        table.add_row(vec![
+            Cell::new(&load.member).fg(Color::Cyan),
+            Cell::new(availability(|a| a.days_available)),
+            Cell::new(availability(|a| a.leave_days)),
+            Cell::new(availability(|a| a.focus_factor)),
+            Cell::new(load.capacity_points.map_or("-".to_string(), |c| c.to_string())),
+            Cell::new(load.load_points),
+            Cell::new(tasks),
+            status,
+        ]);
+    }
+    println!("{table}");
+    println!("{}: {} points ({} per focused day)", "Total Capacity".bold(), report.total_capacity, report.points_per_day);
+    println!("{}: {} points, {} unassigned", "Total Load".bold(), report.total_load, report.unassigned_points);
+}
+
+/// Warn about any of `members` planned beyond their capacity in the sprint
+pub fn print_capacity_warnings(report: &crate::services::capacity_service::CapacityReport, members: &[String]) {
+    let named = |load: &&crate::services::capacity_service::MemberLoad| {
+        members.iter().any(|m| crate::core::models::capacity::same_member(m, &load.member))
+    };
+    for load in report.overcommitted().filter(named) {
+        println!(
+            "  {} {} is over capacity in {}: {} of {} points",
+            "!".yellow(),
+            load.member.cyan(),
+            report.sprint_name,
+            load.load_points,
+            load.capacity_points.unwrap_or(0.0)
+        );
+    }
+}
+
+/// Print applied and pending schema migrations
+pub fn print_migration_status(statuses: &[crate::core::schema::MigrationStatus], current: u32, latest: u32) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Version").fg(Color::Blue),
+            Cell::new("Name"),
+            Cell::new("Status"),
+            Cell::new("Applied"),
+        ]);
+
+    for status in statuses {
+        let status_cell = if status.is_applied() {
+            Cell::new("APPLIED").fg(Color::Green)
+        } else {
+            Cell::new("PENDING").fg(Color::Yellow)
+        };
+        table.add_row(vec![
+            Cell::new(status.version).fg(Color::Cyan),
+            Cell::new(&status.name),
+            status_cell,
+            Cell::new(status.applied_at.as_ref().map_or("-".to_string(), format_datetime)),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n{} {} (latest: {})", "Schema version:".dimmed(), current, latest);
+}
+
 /// Parse sprint status from string
 pub fn parse_sprint_status(s: &str) -> Result<SprintStatus> {
     match s.to_lowercase().as_str() {
@@ -285,4 +1050,194 @@ pub fn parse_sprint_status(s: &str) -> Result<SprintStatus> {
         "cancelled" => Ok(SprintStatus::Cancelled),
         _ => Err(crate::core::errors::Error::InvalidInput(format!("Invalid sprint status: {}", s)).into()),
     }
-}
\ No newline at end of file
+}
+/// Print backups recorded in the manifest
+pub fn print_backup_table(backups: &[(crate::core::backup::BackupRecord, std::path::PathBuf)]) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("File").fg(Color::Blue),
+            Cell::new("Kind"),
+            Cell::new("Created"),
+            Cell::new("Schema"),
+            Cell::new("Size"),
+            Cell::new("SHA-256"),
+        ]);
+
+    for (record, _) in backups {
+        table.add_row(vec![
+            Cell::new(&record.file).fg(Color::Cyan),
+            Cell::new(format!("{:?}", record.kind)),
+            Cell::new(format_datetime(&record.created_at)),
+            Cell::new(record.schema_version),
+            Cell::new(format!("{} KB", record.size_bytes.div_ceil(1024))),
+            Cell::new(&record.sha256[..record.sha256.len().min(12)]),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n{} {} backups", "Total:".dimmed(), backups.len());
+}
+
+/// Print database statistics
+pub fn print_database_stats(stats: &crate::services::stats_service::DatabaseStats) {
+    println!("{}", "📊 Database Statistics".cyan().bold());
+    println!("{}", "═".repeat(50));
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Entity").fg(Color::Blue),
+            Cell::new("Total"),
+            Cell::new("In Trash"),
+            Cell::new("Breakdown"),
+        ]);
+
+    for entity in &stats.entities {
+        let breakdown = entity
+            .breakdown
+            .iter()
+            .map(|(value, count)| format!("{}: {}", value, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        table.add_row(vec![
+            Cell::new(&entity.entity_type).fg(Color::Cyan),
+            Cell::new(entity.total),
+            Cell::new(entity.deleted),
+            Cell::new(if breakdown.is_empty() { "-".to_string() } else { format!("by {}: {}", entity.breakdown_by, breakdown) }),
+        ]);
+    }
+    println!("{table}");
+
+    let storage = &stats.storage;
+    println!("\n{}", "Storage".dimmed());
+    println!("  {}: {}", "Schema Version".bold(), stats.schema_version);
+    println!("  {}: {} KB ({} pages of {} bytes)", "Database Size".bold(),
+        storage.database_bytes.div_ceil(1024), storage.page_count, storage.page_size);
+    println!("  {}: {} KB ({} free pages)", "Free Space".bold(), storage.free_bytes.div_ceil(1024), storage.freelist_count);
+    println!("  {}: {}", "Journal Mode".bold(), storage.journal_mode);
+    println!("  {}: {}", "WAL Size".bold(), storage.wal_bytes.map_or("-".to_string(), |b| format!("{} KB", b.div_ceil(1024))));
+    println!("  {}: {}", "History Entries".bold(), stats.history_entries);
+    println!("  {}: {}", "Embedding Coverage".bold(),
+        stats.embedding_coverage.map_or("n/a (no embeddings stored)".to_string(), |c| format!("{:.1}%", c)));
+    println!("  {}: {}", "Last Backup".bold(), stats.last_backup.as_ref().map_or("Never".to_string(), format_datetime));
+}
+
+/// Print the findings of `db verify`
+pub fn print_integrity_report(report: &crate::core::integrity::IntegrityReport) {
+    if report.is_clean() {
+        println!("{} Database integrity check passed!", "✓".green());
+        return;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Issue").fg(Color::Blue),
+            Cell::new("Type"),
+            Cell::new("ID"),
+            Cell::new("Field"),
+            Cell::new("Details"),
+        ]);
+
+    for issue in &report.issues {
+        let kind_cell = if issue.is_fixable() {
+            Cell::new(issue.kind).fg(Color::Yellow)
+        } else {
+            Cell::new(issue.kind).fg(Color::Red)
+        };
+        table.add_row(vec![
+            kind_cell,
+            Cell::new(&issue.entity_type),
+            Cell::new(&issue.entity_id).fg(Color::Cyan),
+            Cell::new(issue.field.as_deref().unwrap_or("-")),
+            Cell::new(&issue.message),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n{} {} issues", "Total:".dimmed(), report.issues.len());
+    if report.fixed > 0 {
+        println!("{} Repaired {} broken link(s)", "✓".green(), report.fixed);
+    } else if report.issues.iter().any(|i| i.is_fixable()) {
+        println!("{} Run with --fix to remove or null links to missing items", "ℹ".blue());
+    }
+}
+
+/// Print soft-deleted items
+pub fn print_trash_table(tombstones: &[crate::core::repositories::Tombstone]) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("ID").fg(Color::Blue),
+            Cell::new("Type"),
+            Cell::new("Name"),
+            Cell::new("Deleted"),
+        ]);
+
+    for tombstone in tombstones {
+        table.add_row(vec![
+            Cell::new(&tombstone.id).fg(Color::Cyan),
+            Cell::new(&tombstone.entity_type),
+            Cell::new(&tombstone.name),
+            Cell::new(format_datetime(&tombstone.deleted_at)),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n{} {} deleted items", "Total:".dimmed(), tombstones.len());
+}
+
+/// Warn about live entities that still point at something that was just deleted
+pub fn print_dangling_references(id: &str, references: &[crate::core::repositories::Reference]) {
+    if references.is_empty() {
+        return;
+    }
+    println!("{} {} is still referenced by:", "⚠".yellow(), id.cyan());
+    for reference in references {
+        println!("  - {}", reference);
+    }
+}
+
+/// Explain an optimistic-concurrency conflict and show the record as it is now stored
+pub fn print_conflict(error: &crate::core::errors::Error) {
+    let crate::core::errors::Error::Conflict { entity_type, id, expected_version, current_version, current } = error else {
+        return;
+    };
+    eprintln!(
+        "{} {} {} was changed by someone else: you had version {}, it is now at version {}.",
+        "✗".red(),
+        entity_type,
+        id.cyan(),
+        expected_version,
+        current_version
+    );
+    eprintln!("  Re-read it, re-apply your change to the current version and retry.");
+    eprintln!("{}", "Current record:".dimmed());
+    eprintln!("{}", serde_json::to_string_pretty(current).unwrap_or_default());
+}
+
+/// Parse an age such as `30d`, `2w` or `12h` (a bare number means days)
+pub fn parse_age(s: &str) -> Result<chrono::Duration> {
+    let s = s.trim();
+    let (amount, unit) = match s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((idx, _)) => s.split_at(idx),
+        None => (s, "d"),
+    };
+    let invalid = || crate::core::errors::Error::InvalidInput(format!("Invalid age: {} (expected e.g. 30d, 2w, 12h)", s));
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    match unit {
+        "h" => Ok(chrono::Duration::hours(amount)),
+        "d" => Ok(chrono::Duration::days(amount)),
+        "w" => Ok(chrono::Duration::weeks(amount)),
+        _ => Err(invalid().into()),
+    }
+}
@@ -0,0 +1,308 @@
+// llmdoc/src/core/backup.rs
+
+//! Online backups of the SQLite database.
+//!
+//! Backups are taken with SQLite's online backup API, so they are consistent
+//! even while other connections are writing. Every backup written to the
+//! backup directory is recorded in `manifest.json` next to it, with its
+//! SHA-256 checksum and schema version, which restore checks before touching
+//! the live database.
+
+use chrono::{DateTime, Datelike, Utc};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::BackupConfig;
+use crate::core::errors::{Error, Result};
+use crate::core::schema;
+
+/// Name of the checksum manifest inside the backup directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum BackupKind {
+    /// Taken by `db backup`; subject to the retention policy.
+    Manual,
+    /// Taken automatically before a schema migration; never pruned.
+    PreMigration,
+    /// Taken automatically before a restore; never pruned.
+    PreRestore,
+}
+
+impl BackupKind {
+    fn file_prefix(&self) -> &'static str {
+        match self {
+            BackupKind::Manual => "llmdocs_backup",
+            BackupKind::PreMigration => "llmdocs_pre_migration",
+            BackupKind::PreRestore => "llmdocs_pre_restore",
+        }
+    }
+}
+
+/// One backup file recorded in the manifest.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupRecord {
+    pub file: String, // File name, relative to the backup directory
+    pub kind: BackupKind,
+    pub created_at: DateTime<Utc>,
+    pub schema_version: u32,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct BackupManifest {
+    pub backups: Vec<BackupRecord>,
+}
+
+impl BackupManifest {
+    /// Loads the manifest from `dir`, or an empty one if there is none yet.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let tmp = dir.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(tmp, dir.join(MANIFEST_FILE))?;
+        Ok(())
+    }
+
+    pub fn find(&self, file_name: &str) -> Option<&BackupRecord> {
+        self.backups.iter().find(|record| record.file == file_name)
+    }
+}
+
+/// Copies the live database into `dir` under a timestamped name and records it in the manifest.
+pub fn create_backup(conn: &Connection, dir: &Path, kind: BackupKind) -> Result<BackupRecord> {
+    fs::create_dir_all(dir)?;
+    let now = Utc::now();
+    let schema_version = schema::current_version(conn)?;
+    let mut file = format!("{}_v{}_{}.db", kind.file_prefix(), schema_version, now.format("%Y%m%d_%H%M%S"));
+    // Two backups within the same second must not overwrite each other
+    let mut suffix = 1;
+    while dir.join(&file).exists() {
+        suffix += 1;
+        file = format!("{}_v{}_{}_{}.db", kind.file_prefix(), schema_version, now.format("%Y%m%d_%H%M%S"), suffix);
+    }
+
+    let path = dir.join(&file);
+    backup_to(conn, &path)?;
+    let record = BackupRecord {
+        file,
+        kind,
+        created_at: now,
+        schema_version,
+        size_bytes: fs::metadata(&path)?.len(),
+        sha256: sha256_file(&path)?,
+    };
+
+    let mut manifest = BackupManifest::load(dir)?;
+    manifest.backups.push(record.clone());
+    manifest.save(dir)?;
+    Ok(record)
+}
+
+/// Copies the live database to an arbitrary path without recording it.
+pub fn backup_to(conn: &Connection, path: &Path) -> Result<()> {
+    conn.backup(DatabaseName::Main, path, None)?;
+    Ok(())
+}
+
+/// Checks a backup file before it is restored: the checksum must match the
+/// manifest (when the file is listed there), SQLite's integrity check must
+/// pass, and its schema must not be newer than this build. Returns its schema version.
+pub fn verify_backup(path: &Path) -> Result<u32> {
+    if !path.exists() {
+        return Err(Error::NotFound(format!("Backup file not found: {}", path.display())));
+    }
+
+    if let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) {
+        let manifest = BackupManifest::load(dir)?;
+        if let Some(record) = manifest.find(&file_name.to_string_lossy()) {
+            let actual = sha256_file(path)?;
+            if actual != record.sha256 {
+                return Err(Error::ValidationError(format!(
+                    "Checksum mismatch for {}: manifest has {}, file has {}",
+                    path.display(),
+                    record.sha256,
+                    actual
+                )));
+            }
+        }
+    }
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if result != "ok" {
+        return Err(Error::ValidationError(format!("Backup {} failed integrity check: {}", path.display(), result)));
+    }
+
+    let version = schema::current_version(&conn)?;
+    if version > schema::latest_version() {
+        return Err(Error::ValidationError(format!(
+            "Backup {} has schema version {}, newer than the latest supported by this build ({})",
+            path.display(),
+            version,
+            schema::latest_version()
+        )));
+    }
+    Ok(version)
+}
+
+/// Replaces the contents of the live database with a backup. Callers should
+/// [`verify_backup`] first. Returns the restored schema version.
+pub fn restore_from(conn: &mut Connection, path: &Path) -> Result<u32> {
+    conn.restore(DatabaseName::Main, path, None::<fn(rusqlite::backup::Progress)>)?;
+    schema::current_version(conn)
+}
+
+/// Deletes manual backups that fall outside the retention policy and returns them.
+///
+/// The newest manual backup of each of the last `keep_daily` days and of each
+/// of the last `keep_weekly` ISO weeks is kept, as is the newest backup overall.
+/// Automatic backups are never pruned.
+pub fn apply_retention(dir: &Path, policy: &BackupConfig) -> Result<Vec<BackupRecord>> {
+    let mut manifest = BackupManifest::load(dir)?;
+    let mut manual: Vec<&BackupRecord> = manifest.backups.iter().filter(|r| r.kind == BackupKind::Manual).collect();
+    manual.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+
+    let mut keep: HashSet<String> = HashSet::new();
+    let mut days = Vec::new();
+    let mut weeks = Vec::new();
+    for (idx, record) in manual.iter().enumerate() {
+        let day = record.created_at.date_naive();
+        let week = record.created_at.iso_week();
+        let week = (week.year(), week.week());
+        let mut kept = idx == 0;
+        if !days.contains(&day) && days.len() < policy.keep_daily as usize {
+            days.push(day);
+            kept = true;
+        }
+        if !weeks.contains(&week) && weeks.len() < policy.keep_weekly as usize {
+            weeks.push(week);
+            kept = true;
+        }
+        if kept {
+            keep.insert(record.file.clone());
+        }
+    }
+
+    let (kept, pruned): (Vec<BackupRecord>, Vec<BackupRecord>) = manifest
+        .backups
+        .drain(..)
+        .partition(|r| r.kind != BackupKind::Manual || keep.contains(&r.file));
+    for record in &pruned {
+        let path = dir.join(&record.file);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+    manifest.backups = kept;
+    manifest.save(dir)?;
+    Ok(pruned)
+}
+
+/// Manifest entries whose files still exist, newest first.
+pub fn list_backups(dir: &Path) -> Result<Vec<(BackupRecord, PathBuf)>> {
+    let manifest = BackupManifest::load(dir)?;
+    let mut backups: Vec<(BackupRecord, PathBuf)> = manifest
+        .backups
+        .into_iter()
+        .map(|record| {
+            let path = dir.join(&record.file);
+            (record, path)
+        })
+        .filter(|(_, path)| path.exists())
+        .collect();
+    backups.sort_by_key(|(record, _)| std::cmp::Reverse(record.created_at));
+    Ok(backups)
+}
+
+/// Hex-encoded SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(file: &str, kind: BackupKind, created_at: DateTime<Utc>) -> BackupRecord {
+        BackupRecord { file: file.to_string(), kind, created_at, schema_version: 1, size_bytes: 0, sha256: String::new() }
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = Connection::open(dir.path().join("live.db"))?;
+        schema::migrate_up(&mut conn, None)?;
+        conn.execute("CREATE TABLE marker (value TEXT)", [])?;
+        conn.execute("INSERT INTO marker VALUES ('before')", [])?;
+
+        let backups = dir.path().join("backups");
+        let record = create_backup(&conn, &backups, BackupKind::Manual)?;
+        assert_eq!(record.schema_version, schema::latest_version());
+        assert_eq!(verify_backup(&backups.join(&record.file))?, schema::latest_version());
+
+        conn.execute("UPDATE marker SET value = 'after'", [])?;
+        restore_from(&mut conn, &backups.join(&record.file))?;
+        let value: String = conn.query_row("SELECT value FROM marker", [], |row| row.get(0))?;
+        assert_eq!(value, "before");
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_backup() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut conn = Connection::open(dir.path().join("live.db"))?;
+        schema::migrate_up(&mut conn, None)?;
+        let record = create_backup(&conn, dir.path(), BackupKind::Manual)?;
+
+        let path = dir.path().join(&record.file);
+        let mut bytes = fs::read(&path)?;
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&path, bytes)?;
+        assert!(matches!(verify_backup(&path), Err(Error::ValidationError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_retention_keeps_newest_per_day_and_week() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let now = Utc::now();
+        let records = vec![
+            record("today_late.db", BackupKind::Manual, now),
+            record("today_early.db", BackupKind::Manual, now - Duration::minutes(5)),
+            record("yesterday.db", BackupKind::Manual, now - Duration::days(1)),
+            record("last_month.db", BackupKind::Manual, now - Duration::days(30)),
+            record("migration.db", BackupKind::PreMigration, now - Duration::days(60)),
+        ];
+        for r in &records {
+            fs::write(dir.path().join(&r.file), b"")?;
+        }
+        BackupManifest { backups: records }.save(dir.path())?;
+
+        let pruned = apply_retention(dir.path(), &BackupConfig { keep_daily: 2, keep_weekly: 0 })?;
+        let mut pruned: Vec<String> = pruned.into_iter().map(|r| r.file).collect();
+        pruned.sort();
+        assert_eq!(pruned, vec!["last_month.db".to_string(), "today_early.db".to_string()]);
+        assert!(!dir.path().join("today_early.db").exists());
+        assert!(dir.path().join("migration.db").exists());
+        assert_eq!(list_backups(dir.path())?.len(), 3);
+        Ok(())
+    }
+}
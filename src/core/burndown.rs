@@ -0,0 +1,258 @@
+// llmdoc/src/core/burndown.rs
+
+//! Day-by-day burndown and burnup series for a sprint, rebuilt from the task change log.
+//!
+//! Each task's stored document is replayed from its history, and at the end of every
+//! sprint day the tasks then in the sprint are counted. Done tasks count as completed;
+//! cancelled tasks leave the scope. Days after today have no actual values, only the
+//! ideal line, which falls evenly from the scope committed on the first day to zero on
+//! the last. Work added later is counted separately, as it is in the burnup.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+
+use crate::core::models::history::{ChangeType, HistoryEntry};
+use crate::core::models::sprint::Sprint;
+use crate::core::models::task::TaskStatus;
+
+/// What the series count: story points, or tasks when nothing in the sprint is estimated.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BurndownUnit {
+    Points,
+    Tasks,
+}
+
+impl std::fmt::Display for BurndownUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BurndownUnit::Points => write!(f, "points"),
+            BurndownUnit::Tasks => write!(f, "tasks"),
+        }
+    }
+}
+
+/// Sprint totals at the end of one day.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BurndownDay {
+    pub date: NaiveDate,
+    pub scope: Option<u32>,     // None for days still to come
+    pub added: Option<u32>,     // Part of the scope not in the sprint on its first day
+    pub completed: Option<u32>,
+    pub remaining: Option<u32>,
+    pub ideal: f64,             // Ideal remaining work
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Burndown {
+    pub unit: BurndownUnit,
+    pub days: Vec<BurndownDay>,
+}
+
+/// What counts about a task on a given day.
+#[derive(Clone, Copy)]
+struct Snapshot {
+    in_sprint: bool,
+    done: bool,
+    cancelled: bool,
+    points: u32,
+}
+
+impl Snapshot {
+    fn of(document: &Json, sprint_id: &str) -> Self {
+        let status = document.get("status").and_then(Json::as_str).unwrap_or_default();
+        Self {
+            in_sprint: document.get("sprint_id").and_then(Json::as_str) == Some(sprint_id),
+            done: status == "Done",
+            cancelled: status == "Cancelled",
+            points: document.get("story_points").and_then(Json::as_u64).unwrap_or(0) as u32,
+        }
+    }
+}
+
+/// The burndown of `sprint` from `history` (task entries, oldest first per task), up to `today`.
+/// Tasks in the sprint without any history are taken as they are now, from the day they were created.
+pub fn compute(sprint: &Sprint, history: &[HistoryEntry], today: NaiveDate) -> Burndown {
+    let start = sprint.start_date.date_naive();
+    let end = sprint.end_date.date_naive().max(start);
+    let timelines = timelines(sprint, history);
+
+    let unit = if timelines.values().flatten().any(|(_, s)| s.is_some_and(|s| s.in_sprint && s.points > 0)) {
+        BurndownUnit::Points
+    } else {
+        BurndownUnit::Tasks
+    };
+    let measure = |s: &Snapshot| if unit == BurndownUnit::Points { s.points } else { 1 };
+
+    let in_scope_on = |timeline: &[(NaiveDate, Option<Snapshot>)], date: NaiveDate| {
+        match timeline.iter().rev().find(|(day, _)| *day <= date).map(|(_, s)| s) {
+            Some(Some(snapshot)) if snapshot.in_sprint && !snapshot.cancelled => Some(*snapshot),
+            _ => None,
+        }
+    };
+    let committed: Vec<&String> = timelines.iter().filter(|(_, t)| in_scope_on(t, start).is_some()).map(|(id, _)| id).collect();
+
+    let mut days = Vec::new();
+    for date in start.iter_days().take_while(|d| *d <= end) {
+        let (mut scope, mut added, mut completed) = (0, 0, 0);
+        for (task_id, timeline) in &timelines {
+            let Some(snapshot) = in_scope_on(timeline, date) else {
+                continue;
+            };
+            scope += measure(&snapshot);
+            if !committed.contains(&task_id) {
+                added += measure(&snapshot);
+            }
+            if snapshot.done {
+                completed += measure(&snapshot);
+            }
+        }
+        let actual = date <= today;
+        days.push(BurndownDay {
+            date,
+            scope: actual.then_some(scope),
+            added: actual.then_some(added),
+            completed: actual.then_some(completed),
+            remaining: actual.then_some(scope - completed),
+            ideal: 0.0,
+        });
+    }
+
+    // The ideal line starts from the first day's scope, or today's for a sprint not yet begun.
+    let baseline = days.first().and_then(|d| d.scope).unwrap_or_else(|| {
+        let current = sprint.tasks.iter().filter(|t| t.status != TaskStatus::Cancelled);
+        current.map(|t| if unit == BurndownUnit::Points { t.story_points.unwrap_or(0) as u32 } else { 1 }).sum()
+    });
+    let span = (days.len().max(2) - 1) as f64;
+    for (index, day) in days.iter_mut().enumerate() {
+        let ideal = f64::from(baseline) * (1.0 - index as f64 / span);
+        day.ideal = (ideal.max(0.0) * 10.0).round() / 10.0;
+    }
+    Burndown { unit, days }
+}
+
+/// Each relevant task's snapshots by the day they took effect; `None` while the task is deleted.
+fn timelines(sprint: &Sprint, history: &[HistoryEntry]) -> BTreeMap<String, Vec<(NaiveDate, Option<Snapshot>)>> {
+    let mut by_task: BTreeMap<&str, Vec<&HistoryEntry>> = BTreeMap::new();
+    for entry in history.iter().filter(|e| e.entity_type == "task") {
+        by_task.entry(&entry.entity_id).or_default().push(entry);
+    }
+
+    let mut timelines = BTreeMap::new();
+    for (task_id, entries) in by_task {
+        let mut document: Option<Json> = None;
+        let mut deleted: Option<Json> = None;
+        let mut timeline = Vec::new();
+        let mut touches_sprint = false;
+        for entry in entries {
+            match entry.change_type {
+                ChangeType::Created => document = entry.new_value.clone(),
+                ChangeType::Updated => {
+                    if let (Some(Json::Object(fields)), Some(field)) = (document.as_mut(), &entry.field) {
+                        fields.insert(field.clone(), entry.new_value.clone().unwrap_or(Json::Null));
+                    }
+                }
+                ChangeType::Deleted => deleted = document.take(),
+                ChangeType::Restored => document = entry.new_value.clone().or_else(|| deleted.take()),
+                ChangeType::Purged => document = None,
+            }
+            let snapshot = document.as_ref().map(|d| Snapshot::of(d, &sprint.id));
+            touches_sprint |= snapshot.is_some_and(|s| s.in_sprint);
+            timeline.push((entry.changed_at.date_naive(), snapshot));
+        }
+        if touches_sprint {
+            timelines.insert(task_id.to_string(), timeline);
+        }
+    }
+
+    for task in &sprint.tasks {
+        if !timelines.contains_key(&task.id) {
+            if let Ok(document) = serde_json::to_value(task) {
+                timelines.insert(task.id.clone(), vec![(task.created_at.date_naive(), Some(Snapshot::of(&document, &sprint.id)))]);
+            }
+        }
+    }
+    timelines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn entry(task: &str, day: u32, change_type: ChangeType, field: Option<&str>, new_value: Json) -> HistoryEntry {
+        HistoryEntry {
+            id: 0,
+            entity_type: "task".to_string(),
+            entity_id: task.to_string(),
+            version: 0,
+            change_type,
+            field: field.map(String::from),
+            old_value: None,
+            new_value: Some(new_value),
+            changed_by: None,
+            changed_at: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_series_follow_status_and_scope_changes() {
+        let sprint = Sprint::new(
+            "March".to_string(),
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 6, 23, 59, 59).unwrap(),
+        );
+        let created = |points: u32| json!({"status": "Todo", "sprint_id": sprint.id, "story_points": points});
+        let history = vec![
+            entry("A", 1, ChangeType::Created, None, created(5)),
+            entry("A", 3, ChangeType::Updated, Some("status"), json!("Done")),
+            entry("B", 1, ChangeType::Created, None, created(3)),
+            entry("B", 4, ChangeType::Updated, Some("status"), json!("Cancelled")),
+            entry("C", 3, ChangeType::Created, None, json!({"status": "Todo", "sprint_id": null, "story_points": 2})),
+            entry("C", 3, ChangeType::Updated, Some("sprint_id"), json!(sprint.id)),
+            entry("D", 1, ChangeType::Created, None, json!({"status": "Todo", "sprint_id": "other", "story_points": 8})),
+        ];
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let burndown = compute(&sprint, &history, today);
+
+        assert_eq!(burndown.unit, BurndownUnit::Points);
+        let series: Vec<_> = burndown.days.iter().map(|d| (d.scope, d.added, d.completed, d.remaining, d.ideal)).collect();
+        assert_eq!(series, vec![
+            (Some(8), Some(0), Some(0), Some(8), 8.0),
+            (Some(10), Some(2), Some(5), Some(5), 6.0),
+            (Some(7), Some(2), Some(5), Some(2), 4.0),
+            (None, None, None, None, 2.0),
+            (None, None, None, None, 0.0),
+        ]);
+    }
+
+    #[test]
+    fn test_work_added_after_the_start_is_kept_off_the_ideal_line() {
+        let sprint = Sprint::new(
+            "Late start".to_string(),
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 6, 23, 59, 59).unwrap(),
+        );
+        // Only A is planned on the first day; B joins the next day
+        let history = vec![
+            entry("A", 2, ChangeType::Created, None, json!({"status": "Todo", "sprint_id": sprint.id, "story_points": 4})),
+            entry("B", 3, ChangeType::Created, None, json!({"status": "Todo", "sprint_id": sprint.id, "story_points": 4})),
+            entry("B", 5, ChangeType::Updated, Some("status"), json!("Done")),
+        ];
+        let burndown = compute(&sprint, &history, NaiveDate::from_ymd_opt(2026, 3, 6).unwrap());
+
+        let series: Vec<_> = burndown.days.iter().map(|d| (d.scope, d.added, d.completed)).collect();
+        assert_eq!(series, vec![
+            (Some(4), Some(0), Some(0)),
+            (Some(8), Some(4), Some(0)),
+            (Some(8), Some(4), Some(0)),
+            (Some(8), Some(4), Some(4)),
+            (Some(8), Some(4), Some(4)),
+        ]);
+        let ideal: Vec<_> = burndown.days.iter().map(|d| d.ideal).collect();
+        assert_eq!(ideal, vec![4.0, 3.0, 2.0, 1.0, 0.0]);
+    }
+}
@@ -1,90 +1,305 @@
 // llmdoc/src/core/database.rs
 
-// Placeholder for database connection and operation logic
+// Database handle shared by every service
+use crate::app_config::{BackupConfig, Config};
+use crate::core::backup::{self, BackupKind, BackupRecord};
 use crate::core::errors::{Error, Result};
-use deadpool_sqlite::{Pool, Runtime};
-use std::path::Path;
+use crate::core::integrity::{self, IntegrityReport};
+use crate::core::repositories::key_repository::LOOKUPS;
+use crate::core::repositories::{Entity, EntityKind, Record, Reference, Tombstone, TrashRepository};
+use crate::core::schema::{self, MigrationStatus};
+use crate::core::store::{self, MemoryStore, Store, STORED_REFERENCES};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-/// Represents the database connection pool.
+/// Handle to the configured [`Store`], shared by every service.
 #[derive(Clone)]
 pub struct DbConnection {
-    pub pool: Pool,
+    store: Arc<dyn Store>,
+    pub database_url: String,
 }
 
 impl DbConnection {
-    /// Creates a new database connection pool.
+    /// Opens the database at `database_url`; `:memory:` selects the in-memory backend.
     pub async fn new(database_url: &str) -> Result<Self> {
-        let cfg = deadpool_sqlite::Config::new(database_url.to_string());
-        let pool = cfg
-            .builder(Runtime::Tokio1)
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to build SQLite pool builder: {}", e)))?
-            .build()
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to build deadpool_sqlite pool: {}", e)))?;
+        Ok(Self::with_store(store::open_url(database_url).await?, database_url))
+    }
 
-        // Test the connection
-        let _conn = pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get connection from pool during initialization: {}", e)))?;
-        tracing::info!("Database connection pool initialized successfully.");
+    /// Opens the backend selected by `config.backend` and `config.database_url`.
+    pub async fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self::with_store(store::open(config).await?, &config.database_url))
+    }
 
-        Ok(DbConnection { pool })
+    /// A fresh, empty in-memory database. Nothing is written to disk.
+    pub fn in_memory() -> Result<Self> {
+        Ok(Self::with_store(Arc::new(MemoryStore::new()?), ":memory:"))
     }
 
-    /// Runs database migrations.
-    pub async fn run_migrations(&self) -> Result<()> {
-        let conn = self.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get connection for migrations: {}", e)))?;
-        conn.interact(|conn| {
-            // Example migration: Create a simple table if it doesn't exist
-            conn.execute_batch(
-                "CREATE TABLE IF NOT EXISTS adr (
-                    id TEXT PRIMARY KEY,
-                    title TEXT NOT NULL,
-                    status TEXT NOT NULL,
-                    date TEXT NOT NULL,
-                    deciders TEXT,
-                    consulted TEXT,
-                    informed TEXT,
-                    decision TEXT NOT NULL,
-                    alternatives TEXT,
-                    consequences TEXT
-                );
-                CREATE TABLE IF NOT EXISTS sprints (
-                    id TEXT PRIMARY KEY,
-                    name TEXT NOT NULL,
-                    start_date TEXT,
-                    end_date TEXT,
-                    goal TEXT,
-                    created_at TEXT NOT NULL,
-                    updated_at TEXT NOT NULL
-                );
-                CREATE TABLE IF NOT EXISTS tasks (
-                    id TEXT PRIMARY KEY,
-                    title TEXT NOT NULL,
-                    description TEXT,
-                    status TEXT NOT NULL,
-                    task_type TEXT NOT NULL,
-                    priority TEXT NOT NULL,
-                    sprint_id TEXT,
-                    assignee TEXT,
-                    story_points INTEGER,
-                    labels TEXT,
-                    dependencies TEXT,
-                    created_at TEXT NOT NULL,
-                    updated_at TEXT NOT NULL,
-                    created_by TEXT,
-                    updated_by TEXT,
-                    completed_at TEXT
-                );"
-            )?;
-            Ok::<(), crate::core::errors::Error>(())
+    pub fn with_store(store: Arc<dyn Store>, database_url: &str) -> Self {
+        Self { store, database_url: database_url.to_string() }
+    }
+
+    /// The backend, for entity operations that do not know the entity's type.
+    pub fn store(&self) -> &dyn Store {
+        self.store.as_ref()
+    }
+
+    /// Runs `f` on a connection from the store, off the async executor.
+    pub async fn with_connection<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut rusqlite::Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        store::run_with(self.store.as_ref(), f).await
+    }
+
+    /// The live `E` with this ID, through the store's entity operations.
+    pub async fn get_entity<E: Entity>(&self, id: &str) -> Result<Option<E>> {
+        self.store
+            .get(EntityKind::of::<E>(), id)
+            .await?
+            .map(|document| serde_json::from_value(document).map_err(Into::into))
+            .transpose()
+    }
+
+    /// Every live `E`, oldest first.
+    pub async fn list_entities<E: Entity>(&self) -> Result<Vec<E>> {
+        self.store
+            .list(EntityKind::of::<E>())
+            .await?
+            .into_iter()
+            .map(|document| serde_json::from_value(document).map_err(Into::into))
+            .collect()
+    }
+
+    /// Stores a new `entity`, giving it the next `key_prefix` key if it has none, and returns it as stored.
+    pub async fn insert_entity<E: Entity>(&self, entity: &E, key_prefix: &str) -> Result<E> {
+        self.store.insert(EntityKind::of::<E>(), Record::of(entity)?, key_prefix).await?.into_entity()
+    }
+
+    /// Saves `entity` if the stored copy is still at its version, and returns it at the next version.
+    /// `None` if there is no live `E` with its ID; [`Error::Conflict`] if it was changed in the meantime.
+    pub async fn update_entity<E: Entity>(&self, entity: &E) -> Result<Option<E>> {
+        self.store
+            .update(EntityKind::of::<E>(), Record::of(entity)?)
+            .await?
+            .map(Record::into_entity)
+            .transpose()
+    }
+
+    /// Moves the live `E` with this ID to the trash. Returns `false` if there is none.
+    pub async fn delete_entity<E: Entity>(&self, id: &str) -> Result<bool> {
+        self.store.delete(EntityKind::of::<E>(), id).await
+    }
+
+    /// The deleted `E`s, oldest deletion first.
+    pub async fn list_deleted_entities<E: Entity>(&self) -> Result<Vec<Tombstone>> {
+        self.store
+            .list_deleted(EntityKind::of::<E>())
+            .await?
+            .into_iter()
+            .map(|(document, deleted_at)| Tombstone::of::<E>(document, deleted_at))
+            .collect()
+    }
+
+    /// The ID of the `kind` entity kept by the store that `input` names by ID, key or unique
+    /// UUID prefix, like [`Repository::resolve_id`](crate::core::repositories::Repository::resolve_id).
+    pub async fn resolve_stored(&self, kind: EntityKind, input: &str, include_deleted: bool) -> Result<Option<String>> {
+        for lookup in LOOKUPS {
+            let mut ids = self.store.find_ids(kind, lookup, input, include_deleted).await?;
+            if ids.len() > 1 {
+                return Err(crate::core::repositories::key_repository::ambiguous(input, ids.into_iter()));
+            }
+            if let Some(id) = ids.pop() {
+                return Ok(Some(id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Live entities that still point at `id`: tasks by dependency, sprint or parent, and
+    /// stored entities through their [`STORED_REFERENCES`] field.
+    pub async fn references_to(&self, id: &str) -> Result<Vec<Reference>> {
+        let target = id.to_string();
+        let mut references = self.with_connection(move |conn| TrashRepository::references_to(conn, &target)).await?;
+        for (kind, field) in STORED_REFERENCES {
+            for document in self.store.list(kind).await? {
+                let points_at = document[field].as_array().is_some_and(|ids| ids.iter().any(|v| v.as_str() == Some(id)));
+                let Some(referrer) = document["id"].as_str().filter(|referrer| points_at && *referrer != id) else {
+                    continue;
+                };
+                let reference = Reference { entity_type: kind.entity_type.to_string(), id: referrer.to_string() };
+                if !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+        Ok(references)
+    }
+
+    /// Runs `f` as a single unit of work: everything it writes through the transaction
+    /// is committed together if it returns `Ok`, and rolled back if it returns an error.
+    ///
+    /// Repositories take a `&Connection`, which a `Transaction` derefs to, so several
+    /// services' `*_in` helpers can be composed inside one call.
+    pub async fn transaction<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&rusqlite::Transaction) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.with_connection(move |conn| {
+            let tx = conn.transaction()?;
+            // Dropping the transaction without committing rolls it back
+            let out = f(&tx)?;
+            tx.commit()?;
+            Ok(out)
         })
         .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error during migration: {:?}", e)))?
-        .map_err(|e| Error::DatabaseOperation(format!("Migration execution failed within interact block: {}", e)))?;
+    }
 
-        tracing::info!("Database migrations executed successfully.");
+    /// Applies all pending schema migrations, taking a backup first if the database already holds data.
+    pub async fn run_migrations(&self) -> Result<()> {
+        let applied = self.migrate_up(None).await?;
+        if applied.is_empty() {
+            tracing::debug!("Database schema is up to date (version {}).", schema::latest_version());
+        } else {
+            tracing::info!("Database migrations executed successfully: {:?}", applied);
+        }
         Ok(())
     }
+
+    /// Returns the schema version currently recorded in the database.
+    pub async fn schema_version(&self) -> Result<u32> {
+        self.with_connection(|conn| schema::current_version(conn)).await
+    }
+
+    /// Lists applied and pending migrations.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        self.with_connection(|conn| schema::status(conn)).await
+    }
+
+    /// Applies pending migrations up to `target` (latest when `None`). Returns the applied versions.
+    pub async fn migrate_up(&self, target: Option<u32>) -> Result<Vec<u32>> {
+        let pending = self.with_connection(move |conn| schema::pending(conn, target)).await?;
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.backup_before_migration().await?;
+        self.with_connection(move |conn| schema::migrate_up(conn, target)).await
+    }
+
+    /// Reverts migrations until the schema is at version `target`. Returns the reverted versions.
+    pub async fn migrate_down(&self, target: u32) -> Result<Vec<u32>> {
+        if self.schema_version().await? > target {
+            self.backup_before_migration().await?;
+        }
+        self.with_connection(move |conn| schema::migrate_down(conn, target)).await
+    }
+
+    /// Returns the on-disk path of the database, or `None` for in-memory databases.
+    pub fn database_path(&self) -> Option<PathBuf> {
+        self.store.database_path()
+    }
+
+    /// Directory holding automatic and manual backups (`<db dir>/backups`).
+    pub fn backup_dir(&self) -> Option<PathBuf> {
+        self.database_path()
+            .map(|p| p.parent().map(Path::to_path_buf).unwrap_or_default().join("backups"))
+    }
+
+    /// Directory holding task templates (`<db dir>/templates`).
+    pub fn templates_dir(&self) -> Option<PathBuf> {
+        self.database_path()
+            .map(|p| p.parent().map(Path::to_path_buf).unwrap_or_default().join("templates"))
+    }
+
+    /// Takes a manual backup into the backup directory, then prunes old ones per `retention`.
+    /// Returns the new backup and the pruned ones.
+    pub async fn backup(&self, retention: &BackupConfig) -> Result<(BackupRecord, Vec<BackupRecord>)> {
+        let backup_dir = self.require_backup_dir()?;
+        let dir = backup_dir.clone();
+        let record = self.with_connection(move |conn| backup::create_backup(conn, &dir, BackupKind::Manual)).await?;
+        let pruned = backup::apply_retention(&backup_dir, retention)?;
+        tracing::info!("Created backup {} ({} pruned)", record.file, pruned.len());
+        Ok((record, pruned))
+    }
+
+    /// Copies the database to an arbitrary path, outside the managed backup directory.
+    pub async fn backup_to(&self, path: PathBuf) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        self.with_connection(move |conn| backup::backup_to(conn, &path)).await
+    }
+
+    /// Backups recorded in the manifest, newest first.
+    pub async fn list_backups(&self) -> Result<Vec<(BackupRecord, PathBuf)>> {
+        match self.backup_dir() {
+            Some(dir) => backup::list_backups(&dir),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Verifies `path` and replaces the live database with it, after saving the current
+    /// contents as a pre-restore backup. Returns the restored schema version.
+    pub async fn restore(&self, path: PathBuf) -> Result<u32> {
+        backup::verify_backup(&path)?;
+        let backup_dir = self.require_backup_dir()?;
+        self.with_connection(move |conn| {
+            let safety = backup::create_backup(conn, &backup_dir, BackupKind::PreRestore)?;
+            tracing::info!("Saved current database as {} before restoring", safety.file);
+            backup::restore_from(conn, &path)
+        })
+        .await
+    }
+
+    /// Runs the integrity checks. With `fix`, missing references are repaired in a single
+    /// transaction; the report still lists everything that was found.
+    pub async fn verify(&self, schema_dir: Option<PathBuf>, fix: bool) -> Result<IntegrityReport> {
+        let validators = integrity::load_validators(schema_dir.as_deref())?;
+        self.transaction(move |tx| {
+            let mut report = integrity::check(tx, &validators)?;
+            if fix && report.issues.iter().any(|i| i.is_fixable()) {
+                report.fixed = integrity::fix(tx, &report.issues)?;
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    fn require_backup_dir(&self) -> Result<PathBuf> {
+        self.backup_dir()
+            .ok_or_else(|| Error::OperationFailed("Backups are not supported for in-memory databases".to_string()))
+    }
+
+    /// Copies the current database into the backup directory before the schema is changed.
+    /// Skipped for in-memory databases and databases without any tables yet.
+    async fn backup_before_migration(&self) -> Result<Option<PathBuf>> {
+        let (Some(db_path), Some(backup_dir)) = (self.database_path(), self.backup_dir()) else {
+            return Ok(None);
+        };
+        if !db_path.exists() {
+            return Ok(None);
+        }
+        let record = self.with_connection(move |conn| {
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name NOT IN ('schema_version', 'sqlite_sequence')",
+                [],
+                |row| row.get(0),
+            )?;
+            if count == 0 {
+                return Ok(None);
+            }
+            backup::create_backup(conn, &backup_dir, BackupKind::PreMigration).map(Some)
+        })
+        .await?;
+        let Some(record) = record else {
+            return Ok(None);
+        };
+        let backup_path = self.require_backup_dir()?.join(&record.file);
+        tracing::info!("Created pre-migration backup at {:?}", backup_path);
+        Ok(Some(backup_path))
+    }
 }
 
 
@@ -94,9 +309,9 @@ pub fn ensure_db_parent_dir_exists(database_url: &str) -> Result<()> {
     if let Some(parent) = db_path.parent() {
         if !parent.exists() {
             std::fs::create_dir_all(parent)
-                .map_err(|e| Error::IoError(e))?;
+                .map_err(Error::IoError)?;
             tracing::info!("Created database directory: {:?}", parent);
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,242 @@
+// llmdoc/src/core/dependency_graph.rs
+
+//! Task dependency graph built from `Task::dependencies`.
+//!
+//! An edge `A -> B` means "A depends on B": A cannot start until B is
+//! finished (done or cancelled). Dependencies on tasks that do not exist, or
+//! are in the trash, never block anything.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::core::models::task::{Task, TaskStatus};
+
+/// Whether a task in this status no longer holds up its dependents.
+pub fn is_finished(status: &TaskStatus) -> bool {
+    matches!(status, TaskStatus::Done | TaskStatus::Cancelled)
+}
+
+struct Node {
+    title: String,
+    status: TaskStatus,
+    status_before_block: Option<TaskStatus>,
+    dependencies: Vec<String>,
+}
+
+/// A task and, recursively, the tasks it depends on (or that depend on it, when reversed).
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyTree {
+    pub id: String,
+    pub title: Option<String>,    // None if the task does not exist
+    pub status: Option<TaskStatus>,
+    pub repeated: bool,           // Already shown higher up this branch; children omitted
+    pub children: Vec<DependencyTree>,
+}
+
+/// Open tasks in the order they can be worked on.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyPlan {
+    /// Each wave only depends on finished tasks and earlier waves; the first wave can start now.
+    pub waves: Vec<Vec<String>>,
+    /// Open tasks that depend on each other in a cycle and can never start.
+    pub cyclic: Vec<String>,
+}
+
+pub struct DependencyGraph {
+    nodes: BTreeMap<String, Node>,
+}
+
+impl DependencyGraph {
+    pub fn new(tasks: &[Task]) -> Self {
+        let nodes = tasks
+            .iter()
+            .map(|task| {
+                let node = Node {
+                    title: task.title.clone(),
+                    status: task.status.clone(),
+                    status_before_block: task.status_before_block.clone(),
+                    dependencies: task.dependencies.clone(),
+                };
+                (task.id.clone(), node)
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Whether `id` exists and is not finished.
+    pub fn is_open(&self, id: &str) -> bool {
+        self.nodes.get(id).is_some_and(|node| !is_finished(&node.status))
+    }
+
+    /// Direct dependencies of `id` that are still open.
+    pub fn blocked_by(&self, id: &str) -> Vec<String> {
+        self.nodes.get(id).map_or_else(Vec::new, |node| {
+            node.dependencies.iter().filter(|dep| self.is_open(dep)).cloned().collect()
+        })
+    }
+
+    /// Tasks that directly depend on `id`.
+    pub fn blocks(&self, id: &str) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.dependencies.iter().any(|dep| dep == id))
+            .map(|(task_id, _)| task_id.clone())
+            .collect()
+    }
+
+    /// If giving `id` these dependencies would close a cycle, returns it as a path
+    /// starting and ending at `id`.
+    pub fn find_cycle(&self, id: &str, dependencies: &[String]) -> Option<Vec<String>> {
+        let mut visited = BTreeSet::new();
+        for dep in dependencies {
+            let mut path = vec![id.to_string()];
+            if self.reaches(dep, id, &mut path, &mut visited) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Depth-first search from `from` for `target`, following current edges. On success
+    /// `path` holds the route walked, ending at `target`.
+    fn reaches(&self, from: &str, target: &str, path: &mut Vec<String>, visited: &mut BTreeSet<String>) -> bool {
+        path.push(from.to_string());
+        if from == target {
+            return true;
+        }
+        if visited.insert(from.to_string()) {
+            if let Some(node) = self.nodes.get(from) {
+                for dep in &node.dependencies {
+                    if self.reaches(dep, target, path, visited) {
+                        return true;
+                    }
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+
+    /// The status `id` should move to because of its dependencies, if any: open tasks with
+    /// open dependencies are blocked, and blocked tasks whose dependencies have all
+    /// finished go back to the status they were blocked from, or `Todo` if that is not
+    /// known. Tasks without dependencies are never touched.
+    pub fn desired_status(&self, id: &str) -> Option<TaskStatus> {
+        let node = self.nodes.get(id)?;
+        if is_finished(&node.status) || node.dependencies.is_empty() {
+            return None;
+        }
+        let blocked = !self.blocked_by(id).is_empty();
+        match (&node.status, blocked) {
+            (TaskStatus::Blocked, false) => Some(node.status_before_block.clone().unwrap_or(TaskStatus::Todo)),
+            (TaskStatus::Blocked, true) => None,
+            (_, true) => Some(TaskStatus::Blocked),
+            (_, false) => None,
+        }
+    }
+
+    /// What `id` depends on, recursively; with `reverse`, what depends on it.
+    pub fn tree(&self, id: &str, reverse: bool) -> DependencyTree {
+        self.subtree(id, reverse, &mut Vec::new())
+    }
+
+    fn subtree(&self, id: &str, reverse: bool, ancestors: &mut Vec<String>) -> DependencyTree {
+        let node = self.nodes.get(id);
+        let repeated = ancestors.iter().any(|a| a == id);
+        let mut tree = DependencyTree {
+            id: id.to_string(),
+            title: node.map(|n| n.title.clone()),
+            status: node.map(|n| n.status.clone()),
+            repeated,
+            children: Vec::new(),
+        };
+        if repeated {
+            return tree;
+        }
+        let next = if reverse { self.blocks(id) } else { node.map(|n| n.dependencies.clone()).unwrap_or_default() };
+        ancestors.push(id.to_string());
+        tree.children = next.iter().map(|child| self.subtree(child, reverse, ancestors)).collect();
+        ancestors.pop();
+        tree
+    }
+
+    /// Topological layering of the open tasks (Kahn's algorithm).
+    pub fn plan(&self) -> DependencyPlan {
+        let mut remaining: BTreeMap<&str, BTreeSet<&str>> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| !is_finished(&node.status))
+            .map(|(id, node)| {
+                let open: BTreeSet<&str> = node.dependencies.iter().filter(|d| self.is_open(d)).map(String::as_str).collect();
+                (id.as_str(), open)
+            })
+            .collect();
+
+        let mut waves = Vec::new();
+        loop {
+            let wave: Vec<&str> = remaining.iter().filter(|(_, deps)| deps.is_empty()).map(|(id, _)| *id).collect();
+            if wave.is_empty() {
+                break;
+            }
+            for id in &wave {
+                remaining.remove(id);
+            }
+            for deps in remaining.values_mut() {
+                for id in &wave {
+                    deps.remove(id);
+                }
+            }
+            waves.push(wave.into_iter().map(String::from).collect());
+        }
+
+        DependencyPlan { waves, cyclic: remaining.into_keys().map(String::from).collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::TaskType;
+
+    fn task(id: &str, status: TaskStatus, deps: &[&str]) -> Task {
+        let mut task = Task::new(id.to_string(), id.to_string(), TaskType::Task);
+        task.status = status;
+        task.dependencies = deps.iter().map(|d| d.to_string()).collect();
+        task
+    }
+
+    #[test]
+    fn test_cycles_blocking_and_plan() {
+        let graph = DependencyGraph::new(&[
+            task("A", TaskStatus::Todo, &["B", "C"]),
+            task("B", TaskStatus::Todo, &["C"]),
+            task("C", TaskStatus::Done, &[]),
+            task("D", TaskStatus::Blocked, &["C", "GONE"]),
+        ]);
+
+        assert_eq!(graph.find_cycle("C", &["A".to_string()]), Some(vec!["C".into(), "A".into(), "B".into(), "C".into()]));
+        assert_eq!(graph.find_cycle("A", &["A".to_string()]), Some(vec!["A".into(), "A".into()]));
+        assert_eq!(graph.find_cycle("D", &["A".to_string()]), None);
+
+        assert_eq!(graph.blocked_by("A"), vec!["B".to_string()]);
+        assert_eq!(graph.blocks("C"), vec!["A".to_string(), "B".to_string(), "D".to_string()]);
+        assert_eq!(graph.desired_status("A"), Some(TaskStatus::Blocked));
+        assert_eq!(graph.desired_status("B"), None);
+        assert_eq!(graph.desired_status("D"), Some(TaskStatus::Todo));
+        let mut resumed = task("E", TaskStatus::Blocked, &["C"]);
+        resumed.status_before_block = Some(TaskStatus::InProgress);
+        assert_eq!(DependencyGraph::new(&[resumed]).desired_status("E"), Some(TaskStatus::InProgress));
+
+        let plan = graph.plan();
+        assert_eq!(plan.waves, vec![vec!["B".to_string(), "D".to_string()], vec!["A".to_string()]]);
+        assert!(plan.cyclic.is_empty());
+
+        let tree = graph.tree("A", false);
+        assert_eq!(tree.children.len(), 2);
+        assert_eq!(tree.children[0].children[0].id, "C");
+    }
+}
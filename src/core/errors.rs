@@ -13,6 +13,15 @@ pub enum Error {
     InvalidInput(String),
     ValidationError(String),
     NotFound(String),
+    /// An update was based on a stale copy: the stored record is at `current_version`,
+    /// not `expected_version`. `current` is the stored record, for the caller to re-apply its change to.
+    Conflict {
+        entity_type: String,
+        id: String,
+        expected_version: u32,
+        current_version: u32,
+        current: Box<serde_json::Value>,
+    },
     OperationFailed(String),
     EmbeddingError(String),
     ExportError(String),
@@ -35,6 +44,11 @@ impl fmt::Display for Error {
             Error::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             Error::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             Error::NotFound(msg) => write!(f, "Not found: {}", msg),
+            Error::Conflict { entity_type, id, expected_version, current_version, .. } => write!(
+                f,
+                "Conflict: {} {} was modified concurrently (expected version {}, found {}); re-read it and retry",
+                entity_type, id, expected_version, current_version
+            ),
             Error::OperationFailed(msg) => write!(f, "Operation failed: {}", msg),
             Error::EmbeddingError(e) => write!(f, "Embedding service error: {}", e),
             Error::ExportError(e) => write!(f, "Export service error: {}", e),
@@ -0,0 +1,234 @@
+// llmdoc/src/core/forecast.rs
+
+//! Velocity statistics over completed sprints, and Monte Carlo forecasts built on them.
+//!
+//! A forecast replays the remaining backlog many times, each simulated sprint finishing
+//! as many points as a randomly chosen past sprint did, and reports how many sprints
+//! the runs needed. The generator is seeded, so a forecast can be reproduced.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::core::errors::{Error, Result};
+
+/// Percentiles of the simulated sprint counts that a forecast reports.
+pub const FORECAST_PERCENTILES: [u8; 3] = [50, 85, 95];
+
+/// Simulated runs give up after this many sprints.
+const MAX_SIMULATED_SPRINTS: u32 = 1000;
+
+/// What a forecast is for.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastTarget {
+    /// The open work under an epic (or any parent task).
+    Epic(String),
+    /// The open tasks carrying a label.
+    Label(String),
+}
+
+impl std::fmt::Display for ForecastTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ForecastTarget::Epic(id) => write!(f, "epic {}", id),
+            ForecastTarget::Label(label) => write!(f, "label {}", label),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintVelocity {
+    pub sprint_id: String,
+    pub key: Option<String>,
+    pub name: String,
+    pub end_date: DateTime<Utc>,
+    pub velocity: f64,         // Story points completed
+    pub rolling_average: f64,  // Over this sprint and the ones before it, up to the window
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VelocityReport {
+    pub window: usize,
+    pub sprints: Vec<SprintVelocity>, // Oldest first
+    pub average: f64,
+    pub std_dev: f64,                 // Sample standard deviation
+    pub rolling_average: f64,         // Of the latest `window` sprints
+}
+
+impl VelocityReport {
+    /// Statistics over `sprints`, oldest first, with rolling averages over `window` sprints.
+    pub fn new(mut sprints: Vec<SprintVelocity>, window: usize) -> Self {
+        let window = window.max(1);
+        for index in 0..sprints.len() {
+            let from = (index + 1).saturating_sub(window);
+            sprints[index].rolling_average = round(mean(sprints[from..=index].iter().map(|s| s.velocity)));
+        }
+        let velocities: Vec<f64> = sprints.iter().map(|s| s.velocity).collect();
+        let average = mean(velocities.iter().copied());
+        let std_dev = if velocities.len() > 1 {
+            (velocities.iter().map(|v| (v - average).powi(2)).sum::<f64>() / (velocities.len() - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Self {
+            window,
+            rolling_average: sprints.last().map_or(0.0, |s| s.rolling_average),
+            sprints,
+            average: round(average),
+            std_dev: round(std_dev),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastPercentile {
+    pub percent: u8,
+    pub sprints: u32,
+}
+
+/// Share of runs that finished in exactly `sprints` sprints, and in at most that many.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastBucket {
+    pub sprints: u32,
+    pub probability: f64,
+    pub cumulative: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Forecast {
+    pub target: ForecastTarget,
+    pub remaining_points: u32,
+    pub open_tasks: u32,
+    pub unestimated_tasks: u32,    // Open tasks without story points, not counted in remaining_points
+    pub velocities: Vec<f64>,      // The sprint velocities sampled from
+    pub runs: u32,
+    pub seed: u64,
+    pub percentiles: Vec<ForecastPercentile>,
+    pub distribution: Vec<ForecastBucket>,
+}
+
+impl Forecast {
+    /// Simulates `runs` attempts at finishing `remaining_points`, drawing each sprint's
+    /// velocity from `velocities`. Fails if none of them completed anything.
+    pub fn simulate(target: ForecastTarget, remaining_points: u32, velocities: Vec<f64>, runs: u32, seed: u64) -> Result<Self> {
+        if !velocities.iter().any(|v| *v > 0.0) {
+            return Err(Error::ValidationError(
+                "No completed sprint has any velocity to forecast from; complete a sprint with done, estimated tasks first".to_string(),
+            ));
+        }
+        let runs = runs.max(1);
+        let mut rng = SplitMix64(seed);
+        let mut outcomes: Vec<u32> = (0..runs)
+            .map(|_| {
+                let (mut done, mut sprints) = (0.0, 0);
+                while done < f64::from(remaining_points) && sprints < MAX_SIMULATED_SPRINTS {
+                    done += velocities[rng.below(velocities.len())];
+                    sprints += 1;
+                }
+                sprints
+            })
+            .collect();
+        outcomes.sort_unstable();
+
+        let percentiles = FORECAST_PERCENTILES
+            .iter()
+            .map(|&percent| {
+                let rank = (f64::from(percent) / 100.0 * f64::from(runs)).ceil() as usize;
+                ForecastPercentile { percent, sprints: outcomes[rank.clamp(1, outcomes.len()) - 1] }
+            })
+            .collect();
+        let mut distribution: Vec<ForecastBucket> = Vec::new();
+        for sprints in &outcomes {
+            match distribution.last_mut() {
+                Some(bucket) if bucket.sprints == *sprints => bucket.probability += 1.0,
+                _ => distribution.push(ForecastBucket { sprints: *sprints, probability: 1.0, cumulative: 0.0 }),
+            }
+        }
+        let mut cumulative = 0.0;
+        for bucket in &mut distribution {
+            cumulative += bucket.probability;
+            bucket.probability = round(bucket.probability / f64::from(runs));
+            bucket.cumulative = round(cumulative / f64::from(runs));
+        }
+
+        Ok(Self {
+            target,
+            remaining_points,
+            open_tasks: 0,
+            unestimated_tasks: 0,
+            velocities,
+            runs,
+            seed,
+            percentiles,
+            distribution,
+        })
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Small seeded generator; forecasts need repeatable draws, not cryptographic ones.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprint(name: &str, velocity: f64) -> SprintVelocity {
+        SprintVelocity {
+            sprint_id: name.to_string(),
+            key: None,
+            name: name.to_string(),
+            end_date: Utc::now(),
+            velocity,
+            rolling_average: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_velocity_statistics_and_forecast() -> Result<()> {
+        let report = VelocityReport::new(vec![sprint("S1", 10.0), sprint("S2", 20.0), sprint("S3", 30.0), sprint("S4", 20.0)], 3);
+        let rolling: Vec<f64> = report.sprints.iter().map(|s| s.rolling_average).collect();
+        assert_eq!(rolling, vec![10.0, 15.0, 20.0, 23.33]);
+        assert_eq!((report.average, report.std_dev, report.rolling_average), (20.0, 8.16, 23.33));
+
+        let target = ForecastTarget::Label("backend".to_string());
+        let steady = Forecast::simulate(target.clone(), 50, vec![10.0], 100, 7)?;
+        assert!(steady.percentiles.iter().all(|p| p.sprints == 5));
+        assert_eq!(steady.distribution.len(), 1);
+        assert_eq!(steady.distribution[0].cumulative, 1.0);
+
+        let varied = Forecast::simulate(target.clone(), 60, vec![5.0, 10.0, 30.0], 2000, 42)?;
+        let sprints: Vec<u32> = varied.percentiles.iter().map(|p| p.sprints).collect();
+        assert!(sprints.windows(2).all(|w| w[0] <= w[1]) && sprints[0] >= 2 && sprints[2] <= 12);
+        assert_eq!(varied.distribution.last().unwrap().cumulative, 1.0);
+        let again = Forecast::simulate(target.clone(), 60, vec![5.0, 10.0, 30.0], 2000, 42)?;
+        assert_eq!(sprints, again.percentiles.iter().map(|p| p.sprints).collect::<Vec<_>>());
+
+        assert_eq!(Forecast::simulate(target.clone(), 0, vec![10.0], 10, 1)?.percentiles[2].sprints, 0);
+        assert!(matches!(Forecast::simulate(target, 10, vec![0.0], 10, 1), Err(Error::ValidationError(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+// llmdoc/src/core/integrity.rs
+
+//! Structural and semantic checks behind `db verify`.
+//!
+//! Besides SQLite's own `PRAGMA integrity_check`, every stored document is
+//! validated against its JSON schema and deserialized into its model, the
+//! typed enum columns are checked against the model's variants, and every
+//! cross-entity link is resolved. Broken links to rows that do not exist at
+//! all can be repaired with [`fix`]; links to rows in the trash are reported
+//! but left alone so that a restore brings them back.
+//!
+//! The checks read the tables directly. A [`MemoryStore`](crate::core::store::MemoryStore)
+//! keeps ADRs, components and user stories outside them, so only its tables are checked.
+
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use crate::core::errors::Result;
+use crate::core::models::validation::Validator;
+use crate::core::models::{Adr, Comment, Component, Sprint, Task, UserStory};
+use crate::core::repositories::{
+    AdrRepository, CommentRepository, ComponentRepository, Entity, TaskRepository, UserStoryRepository,
+};
+
+/// Built-in schemas, used when the configured schema directory has no override.
+const BUILTIN_SCHEMAS: &[(&str, &str)] = &[
+    ("task", include_str!("../../schemas/task.json")),
+    ("sprint", include_str!("../../schemas/sprint.json")),
+    ("adr", include_str!("../../schemas/adr.json")),
+    ("component", include_str!("../../schemas/component.json")),
+    ("user_story", include_str!("../../schemas/user_story.json")),
+    ("comment", include_str!("../../schemas/comment.json")),
+];
+
+/// Typed enum columns and the values the models accept.
+const ENUM_COLUMNS: &[(&str, &str, &str, &[&str])] = &[
+    ("task", "tasks", "status", &["Todo", "InProgress", "Done", "Blocked", "Cancelled"]),
+    ("task", "tasks", "task_type", &["Bug", "Feature", "Task", "Epic", "Story", "Spike"]),
+    ("task", "tasks", "priority", &["Low", "Medium", "High", "Critical"]),
+    ("sprint", "sprints", "status", &["Planning", "Active", "Completed", "Cancelled"]),
+    ("adr", "adrs", "status", &["Proposed", "Accepted", "Rejected", "Deprecated", "Superseded"]),
+    ("component", "components", "component_type", &["Module", "Service", "Library", "Database", "Api", "Other"]),
+    ("user_story", "user_stories", "priority", &["Low", "Medium", "High", "Critical"]),
+];
+
+/// Links between documents: (entity type, table, JSON path, target table, is an array).
+const LINKS: &[(&str, &str, &str, &str, bool)] = &[
+    ("task", "tasks", "sprint_id", "sprints", false),
+    ("task", "tasks", "parent_id", "tasks", false),
+    ("task", "tasks", "dependencies", "tasks", true),
+    ("adr", "adrs", "related_adrs", "adrs", true),
+    ("component", "components", "dependencies", "components", true),
+    ("user_story", "user_stories", "tasks", "tasks", true),
+    ("user_story", "user_stories", "epic_id", "tasks", false),
+    ("comment", "comments", "reply_to", "comments", false),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum IssueKind {
+    /// SQLite reported storage-level corruption.
+    Corruption,
+    /// The `data` document does not deserialize into its model.
+    InvalidDocument,
+    /// The `data` document does not match its JSON schema.
+    SchemaViolation,
+    /// A typed column holds a value that is not a known enum variant.
+    InvalidEnum,
+    /// A link points at an ID that does not exist.
+    MissingReference,
+    /// A link points at an item that is in the trash.
+    DeletedReference,
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityIssue {
+    pub kind: IssueKind,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub field: Option<String>,
+    pub target: Option<String>, // The missing ID, for reference issues
+    pub message: String,
+}
+
+impl IntegrityIssue {
+    /// Whether [`fix`] can repair this issue.
+    pub fn is_fixable(&self) -> bool {
+        self.kind == IssueKind::MissingReference
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    /// Number of issues repaired by `--fix`.
+    pub fixed: usize,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Loads the JSON schema validators, preferring `<schema_dir>/<entity>.json` over the built-in copy.
+pub fn load_validators(schema_dir: Option<&Path>) -> Result<HashMap<String, Validator>> {
+    let mut validators = HashMap::new();
+    for (entity_type, builtin) in BUILTIN_SCHEMAS {
+        let override_path = schema_dir.map(|dir| dir.join(format!("{}.json", entity_type)));
+        let schema: serde_json::Value = match override_path.filter(|p| p.exists()) {
+            Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+            None => serde_json::from_str(builtin)?,
+        };
+        validators.insert(entity_type.to_string(), Validator::new(&schema)?);
+    }
+    Ok(validators)
+}
+
+/// Runs every check and returns the problems found.
+pub fn check(conn: &Connection, validators: &HashMap<String, Validator>) -> Result<IntegrityReport> {
+    let mut issues = Vec::new();
+
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    for row in stmt.query_map([], |row| row.get::<_, String>(0))? {
+        let result = row?;
+        if result != "ok" {
+            issues.push(issue(IssueKind::Corruption, "database", "-", None, None, result));
+        }
+    }
+
+    check_documents::<Task>(conn, validators, &mut issues)?;
+    check_documents::<Sprint>(conn, validators, &mut issues)?;
+    check_documents::<Adr>(conn, validators, &mut issues)?;
+    check_documents::<Component>(conn, validators, &mut issues)?;
+    check_documents::<UserStory>(conn, validators, &mut issues)?;
+    check_documents::<Comment>(conn, validators, &mut issues)?;
+
+    for (entity_type, table, column, allowed) in ENUM_COLUMNS {
+        let sql = format!("SELECT id, {} FROM {}", column, table);
+        let mut stmt = conn.prepare(&sql)?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))? {
+            let (id, value) = row?;
+            let value = value.unwrap_or_default();
+            if !allowed.contains(&value.as_str()) {
+                issues.push(issue(
+                    IssueKind::InvalidEnum,
+                    entity_type,
+                    &id,
+                    Some(column),
+                    None,
+                    format!("'{}' is not one of {}", value, allowed.join(", ")),
+                ));
+            }
+        }
+    }
+
+    for (entity_type, table, field, target_table, is_array) in LINKS {
+        let sql = if *is_array {
+            format!(
+                "SELECT s.id, j.value, t.deleted_at IS NOT NULL, t.id IS NOT NULL
+                 FROM {table} s, json_each(s.data, '$.{field}') j
+                 LEFT JOIN {target_table} t ON t.id = j.value
+                 WHERE s.deleted_at IS NULL AND (t.id IS NULL OR t.deleted_at IS NOT NULL)"
+            )
+        } else {
+            format!(
+                "SELECT s.id, json_extract(s.data, '$.{field}') AS target, t.deleted_at IS NOT NULL, t.id IS NOT NULL
+                 FROM {table} s
+                 LEFT JOIN {target_table} t ON t.id = target
+                 WHERE s.deleted_at IS NULL AND target IS NOT NULL AND (t.id IS NULL OR t.deleted_at IS NOT NULL)"
+            )
+        };
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, bool>(2)?, row.get::<_, bool>(3)?))
+        })?;
+        for row in rows {
+            let (id, target, deleted, exists) = row?;
+            let (kind, message) = if exists && deleted {
+                (IssueKind::DeletedReference, format!("{} points at {}, which is in the trash", field, target))
+            } else {
+                (IssueKind::MissingReference, format!("{} points at {}, which does not exist", field, target))
+            };
+            issues.push(issue(kind, entity_type, &id, Some(field), Some(target), message));
+        }
+    }
+
+    Ok(IntegrityReport { issues, fixed: 0 })
+}
+
+/// Repairs missing references found by [`check`]: array entries are removed and
+/// scalar links are set to null. Each repair goes through the repository, so it
+/// shows up in the change history. Returns the number of issues repaired.
+pub fn fix(conn: &Connection, issues: &[IntegrityIssue]) -> Result<usize> {
+    // entity -> field -> missing targets
+    let mut broken: BTreeMap<(&str, &str), HashMap<&str, HashSet<&str>>> = BTreeMap::new();
+    for issue in issues.iter().filter(|i| i.is_fixable()) {
+        if let (Some(field), Some(target)) = (&issue.field, &issue.target) {
+            broken
+                .entry((issue.entity_type.as_str(), issue.entity_id.as_str()))
+                .or_default()
+                .entry(field.as_str())
+                .or_default()
+                .insert(target.as_str());
+        }
+    }
+
+    let mut fixed = 0;
+    for ((entity_type, id), fields) in broken {
+        let unlink = |values: &mut Vec<String>, field: &str| {
+            if let Some(targets) = fields.get(field) {
+                values.retain(|v| !targets.contains(v.as_str()));
+            }
+        };
+        let repaired = match entity_type {
+            "task" => match TaskRepository::get(conn, id)? {
+                Some(mut task) => {
+                    unlink(&mut task.dependencies, "dependencies");
+                    if fields.contains_key("sprint_id") {
+                        task.sprint_id = None;
+                    }
+                    if fields.contains_key("parent_id") {
+                        task.parent_id = None;
+                    }
+                    task.updated_at = Utc::now();
+                    task.updated_by = Some("db verify --fix".to_string());
+                    TaskRepository::update(conn, &mut task)?
+                }
+                None => false,
+            },
+            "adr" => match AdrRepository::get(conn, id)? {
+                Some(mut adr) => {
+                    unlink(&mut adr.related_adrs, "related_adrs");
+                    adr.updated_at = Utc::now();
+                    AdrRepository::update(conn, &mut adr)?
+                }
+                None => false,
+            },
+            "component" => match ComponentRepository::get(conn, id)? {
+                Some(mut component) => {
+                    unlink(&mut component.dependencies, "dependencies");
+                    component.updated_at = Utc::now();
+                    ComponentRepository::update(conn, &mut component)?
+                }
+                None => false,
+            },
+            "user_story" => match UserStoryRepository::get(conn, id)? {
+                Some(mut story) => {
+                    unlink(&mut story.tasks, "tasks");
+                    if fields.contains_key("epic_id") {
+                        story.epic_id = None;
+                    }
+                    story.updated_at = Utc::now();
+                    UserStoryRepository::update(conn, &mut story)?
+                }
+                None => false,
+            },
+            "comment" => match CommentRepository::get(conn, id)? {
+                Some(mut comment) => {
+                    comment.reply_to = None;
+                    comment.updated_at = Utc::now();
+                    CommentRepository::update(conn, &mut comment)?
+                }
+                None => false,
+            },
+            _ => false,
+        };
+        if repaired {
+            fixed += fields.values().map(HashSet::len).sum::<usize>();
+        }
+    }
+    Ok(fixed)
+}
+
+/// Validates and deserializes every live document of one entity type.
+fn check_documents<E: Entity>(
+    conn: &Connection,
+    validators: &HashMap<String, Validator>,
+    issues: &mut Vec<IntegrityIssue>,
+) -> Result<()> {
+    let sql = format!("SELECT id, data FROM {} WHERE deleted_at IS NULL", E::TABLE);
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    for row in rows {
+        let (id, data) = row?;
+        let document: serde_json::Value = match serde_json::from_str(&data) {
+            Ok(document) => document,
+            Err(e) => {
+                issues.push(issue(IssueKind::InvalidDocument, E::ENTITY_TYPE, &id, None, None, e.to_string()));
+                continue;
+            }
+        };
+        if let Some(validator) = validators.get(E::ENTITY_TYPE) {
+            if let Err(e) = validator.validate(&document) {
+                issues.push(issue(IssueKind::SchemaViolation, E::ENTITY_TYPE, &id, None, None, e.to_string()));
+                continue;
+            }
+        }
+        if let Err(e) = serde_json::from_value::<E>(document) {
+            issues.push(issue(IssueKind::InvalidDocument, E::ENTITY_TYPE, &id, None, None, e.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn issue(
+    kind: IssueKind,
+    entity_type: &str,
+    entity_id: &str,
+    field: Option<&str>,
+    target: Option<String>,
+    message: String,
+) -> IntegrityIssue {
+    IntegrityIssue {
+        kind,
+        entity_type: entity_type.to_string(),
+        entity_id: entity_id.to_string(),
+        field: field.map(str::to_string),
+        target,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::TaskType;
+    use crate::core::schema;
+
+    #[test]
+    fn test_detects_and_fixes_broken_links() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        schema::migrate_up(&mut conn, None)?;
+        let validators = load_validators(None)?;
+
+        let deleted = Task::new("TASK-2".to_string(), "Deleted".to_string(), TaskType::Task);
+        let mut task = Task::new("TASK-1".to_string(), "Broken".to_string(), TaskType::Task);
+        task.sprint_id = Some("missing-sprint".to_string());
+        task.dependencies = vec!["TASK-404".to_string(), "TASK-2".to_string()];
+        TaskRepository::insert(&conn, &deleted)?;
+        TaskRepository::insert(&conn, &task)?;
+        TaskRepository::delete(&conn, "TASK-2")?;
+
+        let report = check(&conn, &validators)?;
+        let kinds: Vec<(IssueKind, Option<&str>)> =
+            report.issues.iter().map(|i| (i.kind, i.target.as_deref())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (IssueKind::MissingReference, Some("missing-sprint")),
+                (IssueKind::MissingReference, Some("TASK-404")),
+                (IssueKind::DeletedReference, Some("TASK-2")),
+            ]
+        );
+
+        assert_eq!(fix(&conn, &report.issues)?, 2);
+        let repaired = TaskRepository::get(&conn, "TASK-1")?.unwrap();
+        assert_eq!(repaired.sprint_id, None);
+        assert_eq!(repaired.dependencies, vec!["TASK-2".to_string()]);
+        assert_eq!(check(&conn, &validators)?.issues.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_detects_invalid_enums_and_schema_violations() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        schema::migrate_up(&mut conn, None)?;
+        let validators = load_validators(None)?;
+        TaskRepository::insert(&conn, &Task::new("TASK-1".to_string(), "Ok".to_string(), TaskType::Task))?;
+        assert!(check(&conn, &validators)?.is_clean());
+
+        conn.execute(
+            "UPDATE tasks SET status = 'Someday', data = json_set(data, '$.status', 'Someday') WHERE id = 'TASK-1'",
+            [],
+        )?;
+        let kinds: Vec<IssueKind> = check(&conn, &validators)?.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, vec![IssueKind::SchemaViolation, IssueKind::InvalidEnum]);
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
 // llmdoc/src/core/mod.rs
 
+pub mod backup;
+pub mod burndown;
 pub mod database;
+pub mod dependency_graph;
 pub mod errors;
-pub mod models;
\ No newline at end of file
+pub mod forecast;
+pub mod integrity;
+pub mod models;
+pub mod repositories;
+pub mod schema;
+pub mod store;
+pub mod task_hierarchy;
+pub mod workflow;
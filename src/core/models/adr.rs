@@ -36,6 +36,8 @@ impl std::str::FromStr for AdrStatus {
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
 pub struct Adr {
     pub id: String, // ADR-uuid
+    #[serde(default)]
+    pub key: Option<String>, // Sequential key such as ADR-7, assigned on creation
     pub title: String,
     pub status: AdrStatus,
     pub context: String,
@@ -45,6 +47,8 @@ pub struct Adr {
     pub related_adrs: Vec<String>, // IDs of related ADRs
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32, // Bumped on every write, for optimistic concurrency
     pub created_by: Option<String>,
     pub approved_by: Option<String>,
     pub approved_at: Option<DateTime<Utc>>,
@@ -53,7 +57,8 @@ pub struct Adr {
 impl Adr {
     pub fn new(title: String, context: String, decision: String, consequences: String) -> Self {
         Self {
-            id: format!("adr-{}", Uuid::new_v4().to_string()),
+            id: format!("adr-{}", Uuid::new_v4()),
+            key: None,
             title,
             status: AdrStatus::Proposed, // Default status
             context,
@@ -63,6 +68,7 @@ impl Adr {
             related_adrs: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
             created_by: None,
             approved_by: None,
             approved_at: None,
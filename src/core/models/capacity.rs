@@ -0,0 +1,37 @@
+// llmdoc/src/core/models/capacity.rs
+
+use chrono::{DateTime, Utc};
+
+/// How much of a sprint one team member can work. Member names are compared with
+/// task assignees by [`same_member`].
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq)]
+pub struct MemberAvailability {
+    pub sprint_id: String,
+    pub member: String,
+    pub days_available: f64, // Working days in the sprint, before leave
+    pub focus_factor: f64,   // Share of a working day spent on sprint work, 0 to 1
+    pub leave_days: f64,     // Planned days off within the sprint
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MemberAvailability {
+    pub fn new(sprint_id: String, member: String, days_available: f64, focus_factor: f64, leave_days: f64) -> Self {
+        Self { sprint_id, member, days_available, focus_factor, leave_days, updated_at: Utc::now() }
+    }
+
+    /// Story points this member can take on, at `points_per_day` for a fully focused day.
+    pub fn capacity_points(&self, points_per_day: f64) -> f64 {
+        (self.days_available - self.leave_days).max(0.0) * self.focus_factor * points_per_day
+    }
+}
+
+/// Whether two member or assignee names are the same person: equal once trimmed, ignoring
+/// ASCII case as the `sprint_capacity.member` column does.
+pub fn same_member(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// A member name in the form [`same_member`] compares, for use as a map key.
+pub fn member_key(name: &str) -> String {
+    name.trim().to_ascii_lowercase()
+}
@@ -0,0 +1,34 @@
+// llmdoc/src/core/models/comment.rs
+
+use chrono::{DateTime, Utc};
+
+/// A remark in the discussion thread of a task, story, ADR or any other entity.
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
+pub struct Comment {
+    pub id: String, // UUID
+    pub entity_id: String,
+    pub entity_type: String, // e.g. "task", "adr"; filled in when the comment is added
+    pub author: Option<String>,
+    pub body: String, // Markdown
+    pub reply_to: Option<String>, // Comment this one answers
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32,
+}
+
+impl Comment {
+    pub fn new(entity_id: String, body: String, author: Option<String>) -> Self {
+        Self {
+            id: crate::utils::new_uuid_v4_str(),
+            entity_id,
+            entity_type: String::new(),
+            author,
+            body,
+            reply_to: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+        }
+    }
+}
@@ -39,6 +39,8 @@ impl std::str::FromStr for ComponentType {
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
 pub struct Component {
     pub id: String, // comp-uuid
+    #[serde(default)]
+    pub key: Option<String>, // Sequential key such as COMP-3, assigned on creation
     pub name: String,
     pub component_type: ComponentType,
     pub description: String,
@@ -50,13 +52,16 @@ pub struct Component {
     pub repository_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32, // Bumped on every write, for optimistic concurrency
     pub metadata: HashMap<String, String>, // Flexible metadata
 }
 
 impl Component {
     pub fn new(name: String, component_type: ComponentType, description: String) -> Self {
         Self {
-            id: format!("comp-{}", Uuid::new_v4().to_string()),
+            id: format!("comp-{}", Uuid::new_v4()),
+            key: None,
             name,
             component_type,
             description,
@@ -68,6 +73,7 @@ impl Component {
             repository_url: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
             metadata: HashMap::new(),
         }
     }
@@ -0,0 +1,73 @@
+// llmdoc/src/core/models/history.rs
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq)]
+pub enum ChangeType {
+    Created,
+    Updated,
+    Deleted,  // Moved to the trash
+    Restored, // Taken back out of the trash
+    Purged,   // Permanently removed from the trash
+}
+
+impl std::fmt::Display for ChangeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::str::FromStr for ChangeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(ChangeType::Created),
+            "updated" => Ok(ChangeType::Updated),
+            "deleted" => Ok(ChangeType::Deleted),
+            "restored" => Ok(ChangeType::Restored),
+            "purged" => Ok(ChangeType::Purged),
+            _ => Err(anyhow::anyhow!("Invalid ChangeType: {}", s)),
+        }
+    }
+}
+
+/// One row of the append-only change log.
+///
+/// A single write produces one entry per changed field, all sharing the same `version`.
+/// Entries other than `Updated` have no `field`; `Created`, `Deleted` and `Restored` carry the whole document.
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub entity_type: String, // e.g. "task", "sprint"
+    pub entity_id: String,
+    pub version: u32, // Per-entity change counter, starting at 1
+    pub change_type: ChangeType,
+    pub field: Option<String>,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+    pub changed_by: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl HistoryEntry {
+    /// One-line description of the change, e.g. `status: "Todo" → "Done"`.
+    pub fn summary(&self) -> String {
+        match (&self.change_type, &self.field) {
+            (ChangeType::Updated, Some(field)) => format!(
+                "{}: {} → {}",
+                field,
+                display_value(self.old_value.as_ref()),
+                display_value(self.new_value.as_ref())
+            ),
+            (change_type, _) => format!("{} {}", change_type.to_string().to_lowercase(), self.entity_type),
+        }
+    }
+}
+
+fn display_value(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => "(none)".to_string(),
+        Some(value) => value.to_string(),
+    }
+}
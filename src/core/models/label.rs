@@ -0,0 +1,21 @@
+// llmdoc/src/core/models/label.rs
+
+use chrono::{DateTime, Utc};
+
+/// A registered task label. Names are unique ignoring case, so `Backend` and
+/// `backend` are the same label; tasks use the registered spelling.
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
+pub struct Label {
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>, // A color name such as "red", or "#rrggbb"
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Label {
+    pub fn new(name: String, description: Option<String>, color: Option<String>) -> Self {
+        let now = Utc::now();
+        Self { name, description, color, created_at: now, updated_at: now }
+    }
+}
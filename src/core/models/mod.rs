@@ -2,15 +2,34 @@
 // This is the module declaration file for the models.
 
 pub mod adr;
+pub mod capacity;
+pub mod comment;
 pub mod component;
+pub mod history;
+pub mod label;
 pub mod sprint;
 pub mod task;
+pub mod task_filter;
+pub mod task_template;
 pub mod user_story;
 pub mod validation;
+pub mod work_log;
 
 pub use adr::{Adr, AdrStatus};
+pub use capacity::MemberAvailability;
+pub use comment::Comment;
 pub use component::{Component, ComponentType};
-pub use sprint::{Sprint, SprintStatus, Retrospective};
+pub use history::{ChangeType, HistoryEntry};
+pub use label::Label;
+pub use sprint::{CarryOver, Sprint, SprintStatus, Retrospective};
 pub use task::{Task, TaskStatus, TaskType, Priority};
+pub use task_filter::{DateRange, TaskFilter, TaskSortField};
+pub use task_template::TaskTemplate;
 pub use user_story::UserStory;
-pub use validation::{Validator, is_valid_task_id};
\ No newline at end of file
+pub use validation::{Validator, is_valid_task_id};
+pub use work_log::WorkLogEntry;
+
+/// Version of a freshly created entity, and of stored documents that predate versioning.
+pub(crate) fn initial_version() -> u32 {
+    1
+}
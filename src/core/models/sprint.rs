@@ -40,9 +40,19 @@ pub struct Retrospective {
     pub notes: Option<String>,
 }
 
+/// Unfinished tasks moved on when a sprint was completed.
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, Default)]
+pub struct CarryOver {
+    pub to_sprint_id: Option<String>, // None for the backlog
+    pub task_ids: Vec<String>,
+    pub points: u32,
+}
+
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
 pub struct Sprint {
     pub id: String, // UUID
+    #[serde(default)]
+    pub key: Option<String>, // Sequential key such as SPRINT-7, assigned on creation
     pub name: String,
     pub description: Option<String>,
     pub start_date: DateTime<Utc>,
@@ -54,13 +64,18 @@ pub struct Sprint {
     pub capacity: Option<f32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32, // Bumped on every write, for optimistic concurrency
     pub retrospective: Option<Retrospective>,
+    #[serde(default)]
+    pub carry_over: Option<CarryOver>, // Set by completing the sprint, unless its open tasks stayed
 }
 
 impl Sprint {
     pub fn new(name: String, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Self {
         Self {
-            id: format!("sprint-{}", Uuid::new_v4().to_string()),
+            id: format!("sprint-{}", Uuid::new_v4()),
+            key: None,
             name,
             description: None,
             start_date,
@@ -72,7 +87,9 @@ impl Sprint {
             capacity: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
             retrospective: None,
+            carry_over: None,
         }
     }
 }
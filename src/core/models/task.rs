@@ -1,8 +1,8 @@
 // llmdoc/src/core/models/task.rs
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
-#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum TaskStatus {
     Todo,
     InProgress,
@@ -11,7 +11,7 @@ pub enum TaskStatus {
     Cancelled,
 }
 
-#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum TaskType {
     Bug,
     Feature,
@@ -21,6 +21,37 @@ pub enum TaskType {
     Spike,
 }
 
+impl std::str::FromStr for TaskStatus {
+    type Err = crate::core::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "todo" | "to-do" | "to_do" => Ok(TaskStatus::Todo),
+            "in_progress" | "in-progress" | "inprogress" | "doing" => Ok(TaskStatus::InProgress),
+            "done" | "completed" | "complete" => Ok(TaskStatus::Done),
+            "blocked" => Ok(TaskStatus::Blocked),
+            "cancelled" | "canceled" => Ok(TaskStatus::Cancelled),
+            _ => Err(crate::core::errors::Error::InvalidInput(format!("Invalid status: {}", s))),
+        }
+    }
+}
+
+impl std::str::FromStr for TaskType {
+    type Err = crate::core::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bug" => Ok(TaskType::Bug),
+            "feature" => Ok(TaskType::Feature),
+            "task" => Ok(TaskType::Task),
+            "epic" => Ok(TaskType::Epic),
+            "story" => Ok(TaskType::Story),
+            "spike" => Ok(TaskType::Spike),
+            _ => Err(crate::core::errors::Error::InvalidInput(format!("Invalid task type: {}", s))),
+        }
+    }
+}
+
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone, PartialEq)]
 pub enum Priority {
     Low,
@@ -29,21 +60,53 @@ pub enum Priority {
     Critical,
 }
 
+impl std::str::FromStr for Priority {
+    type Err = crate::core::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            "critical" => Ok(Priority::Critical),
+            _ => Err(crate::core::errors::Error::InvalidInput(format!("Invalid priority: {}", s))),
+        }
+    }
+}
+
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
 pub struct Task {
     pub id: String, // UUID
+    #[serde(default)]
+    pub key: Option<String>, // Sequential key such as TASK-42, assigned on creation
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
     pub task_type: TaskType,
     pub priority: Priority,
     pub sprint_id: Option<String>, // Foreign key to Sprint
+    #[serde(default)]
+    pub parent_id: Option<String>, // Epic or story this task belongs to
     pub assignee: Option<String>, // User ID
     pub story_points: Option<u8>,
+    #[serde(default)]
+    pub original_estimate_minutes: Option<u32>,
+    #[serde(default)]
+    pub remaining_estimate_minutes: Option<u32>, // Counted down as work is logged
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>, // Within the sprint, if the task is in one
+    #[serde(default)]
+    pub carry_over_count: u32, // Times moved on unfinished from a completed sprint
+    #[serde(default)]
+    pub status_before_block: Option<TaskStatus>, // Restored when its dependencies finish
     pub labels: Vec<String>,
     pub dependencies: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32, // Bumped on every write, for optimistic concurrency
     pub created_by: Option<String>,
     pub updated_by: Option<String>,
     pub completed_at: Option<DateTime<Utc>>,
@@ -53,26 +116,43 @@ impl Task {
     pub fn new(id: String, title: String, task_type: TaskType) -> Self {
         Self {
             id,
+            key: None,
             title,
             description: None,
             status: TaskStatus::Todo,
             task_type,
             priority: Priority::Medium, // Default priority
             sprint_id: None,
+            parent_id: None,
             assignee: None,
             story_points: None,
+            original_estimate_minutes: None,
+            remaining_estimate_minutes: None,
+            start_date: None,
+            due_date: None,
+            carry_over_count: 0,
+            status_before_block: None,
             labels: Vec::new(),
             dependencies: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
             created_by: None,
             updated_by: None,
             completed_at: None,
         }
     }
 
+    /// Whether the task is still open after its due date.
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due_date.is_some_and(|due| due < today) && !crate::core::dependency_graph::is_finished(&self.status)
+    }
+
     pub fn set_status(&mut self, new_status: TaskStatus, updated_by: Option<String>) {
         self.status = new_status;
+        if self.status != TaskStatus::Blocked {
+            self.status_before_block = None;
+        }
         self.updated_at = Utc::now();
         self.updated_by = updated_by;
         if self.status == TaskStatus::Done {
@@ -0,0 +1,70 @@
+// llmdoc/src/core/models/task_filter.rs
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::task::{Priority, TaskStatus, TaskType};
+
+/// Criteria for listing tasks. Empty fields match everything; set fields are ANDed together.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskFilter {
+    pub statuses: Vec<TaskStatus>,     // Any of
+    pub task_types: Vec<TaskType>,     // Any of
+    pub priorities: Vec<Priority>,     // Any of
+    pub sprint_id: Option<String>,
+    pub parent_id: Option<String>,     // Direct children of this task
+    pub assignee: Option<String>,
+    pub labels_any: Vec<String>,       // At least one of these labels
+    pub labels_all: Vec<String>,       // Every one of these labels
+    pub created: DateRange,
+    pub updated: DateRange,
+    pub completed: DateRange,          // Only matches completed tasks when set
+    pub due_by: Option<NaiveDate>,     // Open tasks due on or before this date
+    pub text: Option<String>,          // Case-insensitive match on title or description
+    pub sort_by: TaskSortField,
+    pub descending: bool,
+    pub limit: Option<u32>,
+    pub offset: u32,
+}
+
+/// Inclusive time range; an open end is unbounded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DateRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortField {
+    #[default]
+    Created,
+    Updated,
+    Completed,
+    Priority,
+    Status,
+    Title,
+    Points,
+    Due,
+    Id,
+}
+
+impl std::str::FromStr for TaskSortField {
+    type Err = crate::core::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" | "created_at" => Ok(TaskSortField::Created),
+            "updated" | "updated_at" => Ok(TaskSortField::Updated),
+            "completed" | "completed_at" => Ok(TaskSortField::Completed),
+            "priority" => Ok(TaskSortField::Priority),
+            "status" => Ok(TaskSortField::Status),
+            "title" => Ok(TaskSortField::Title),
+            "points" | "story_points" => Ok(TaskSortField::Points),
+            "due" | "due_date" => Ok(TaskSortField::Due),
+            "id" => Ok(TaskSortField::Id),
+            _ => Err(crate::core::errors::Error::InvalidInput(format!("Unknown sort field: {}", s))),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// llmdoc/src/core/models/task_template.rs
+
+//! Named task templates for `task add --template`.
+//!
+//! A template is a TOML or JSON file giving defaults for a new task. Its title and
+//! description may contain `{{name}}` placeholders, filled from `--set name=value`;
+//! `{{name|text}}` falls back to `text` when the value is not given, and `{{date}}`
+//! is today's date.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Error, Result};
+use crate::core::models::task::{Priority, Task, TaskType};
+use crate::core::models::work_log::parse_duration;
+
+/// Templates available without any files, and written to the templates directory by `init`.
+pub const BUILTIN_TEMPLATES: [(&str, &str); 3] = [
+    ("bug", include_str!("../../../templates/tasks/bug.toml")),
+    ("spike", include_str!("../../../templates/tasks/spike.toml")),
+    ("chore", include_str!("../../../templates/tasks/chore.toml")),
+];
+
+/// `--set` keys that set a task field directly rather than (only) filling a placeholder.
+pub const FIELD_KEYS: [&str; 11] = [
+    "id", "assignee", "priority", "type", "points", "estimate", "labels", "sprint", "parent", "start", "due",
+];
+
+const PLACEHOLDER: &str = r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*(?:\|([^}]*))?\}\}";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskTemplate {
+    #[serde(skip_deserializing)]
+    pub name: String,                  // From the file name
+    #[serde(skip_deserializing)]
+    pub source: String,                // File it was read from, or "built-in"
+    pub about: Option<String>,         // One-line summary for `task template list`
+    pub title: Option<String>,         // Defaults to "{{title}}"
+    pub description: Option<String>,
+    pub task_type: Option<String>,
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+    pub story_points: Option<u8>,
+    pub estimate: Option<String>,      // Original estimate, e.g. "1d" or "4h"
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl TaskTemplate {
+    /// Reads a template from TOML, or from JSON when `extension` is `json`, and checks its fields.
+    pub fn parse(name: &str, content: &str, extension: &str) -> Result<Self> {
+        let invalid = |e: String| Error::ValidationError(format!("Template {}: {}", name, e));
+        let mut template: TaskTemplate = if extension.eq_ignore_ascii_case("json") {
+            serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?
+        } else {
+            toml::from_str(content).map_err(|e| invalid(e.to_string()))?
+        };
+        template.name = name.to_string();
+        if let Some(task_type) = &template.task_type {
+            task_type.parse::<TaskType>().map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(priority) = &template.priority {
+            priority.parse::<Priority>().map_err(|e| invalid(e.to_string()))?;
+        }
+        if let Some(estimate) = &template.estimate {
+            parse_duration(estimate).map_err(|e| invalid(e.to_string()))?;
+        }
+        Ok(template)
+    }
+
+    /// The templates shipped with llmdocs.
+    pub fn builtin() -> Vec<TaskTemplate> {
+        BUILTIN_TEMPLATES
+            .iter()
+            .map(|(name, content)| {
+                let mut template = Self::parse(name, content, "toml").expect("built-in templates are valid");
+                template.source = "built-in".to_string();
+                template
+            })
+            .collect()
+    }
+
+    /// Placeholders that have no fallback text and so must be given with `--set`.
+    pub fn required_placeholders(&self) -> Result<Vec<String>> {
+        let regex = Regex::new(PLACEHOLDER)?;
+        let mut names = BTreeSet::new();
+        for text in [self.title_pattern(), self.description.as_deref().unwrap_or_default()] {
+            for captures in regex.captures_iter(text) {
+                if captures.get(2).is_none() && &captures[1] != "date" {
+                    names.insert(captures[1].to_string());
+                }
+            }
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    /// A new task from this template, with placeholders and fields taken from `values`.
+    /// The task has an empty ID unless `id` is given, so the service assigns one.
+    pub fn instantiate(&self, values: &BTreeMap<String, String>) -> Result<Task> {
+        let regex = Regex::new(PLACEHOLDER)?;
+        let description = self.description.as_deref().unwrap_or_default();
+        let used: BTreeSet<&str> = regex
+            .captures_iter(self.title_pattern())
+            .chain(regex.captures_iter(description))
+            .map(|captures| captures.get(1).map_or("", |m| m.as_str()))
+            .collect();
+        if let Some(unknown) = values.keys().find(|key| !used.contains(key.as_str()) && !FIELD_KEYS.contains(&key.as_str())) {
+            return Err(Error::ValidationError(format!("Template {} has no placeholder or field '{}'", self.name, unknown)));
+        }
+
+        let mut placeholders = values.clone();
+        placeholders.entry("date".to_string()).or_insert_with(|| Utc::now().format("%Y-%m-%d").to_string());
+        let mut missing = BTreeSet::new();
+        let title = render(&regex, self.title_pattern(), &placeholders, &mut missing);
+        let description = self.description.as_deref().map(|d| render(&regex, d, &placeholders, &mut missing));
+        if !missing.is_empty() {
+            return Err(Error::ValidationError(format!(
+                "Template {} needs {}; pass them with --set name=value",
+                self.name,
+                missing.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+        if title.trim().is_empty() {
+            return Err(Error::ValidationError(format!("Template {} gives an empty title", self.name)));
+        }
+
+        let field = |key: &str| values.get(key).map(String::as_str);
+        let task_type = match field("type").or(self.task_type.as_deref()) {
+            Some(task_type) => task_type.parse()?,
+            None => TaskType::Task,
+        };
+        let mut task = Task::new(field("id").unwrap_or_default().to_string(), title.trim().to_string(), task_type);
+        task.description = description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        if let Some(priority) = field("priority").or(self.priority.as_deref()) {
+            task.priority = priority.parse()?;
+        }
+        task.assignee = field("assignee").or(self.assignee.as_deref()).map(String::from);
+        task.story_points = field("points").map(str::parse).transpose()?.or(self.story_points);
+        task.original_estimate_minutes = field("estimate").or(self.estimate.as_deref()).map(parse_duration).transpose()?;
+        task.sprint_id = field("sprint").map(String::from);
+        task.parent_id = field("parent").map(String::from);
+        let date = |key: &str| {
+            field(key)
+                .map(|s| NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d"))
+                .transpose()
+                .map_err(|_| Error::InvalidInput(format!("Invalid {} date (use YYYY-MM-DD): {}", key, values[key])))
+        };
+        task.start_date = date("start")?;
+        task.due_date = date("due")?;
+        let extra_labels = field("labels").unwrap_or_default().split(',').map(str::trim).filter(|l| !l.is_empty());
+        for label in self.labels.iter().map(String::as_str).chain(extra_labels) {
+            if !task.labels.iter().any(|l| l == label) {
+                task.labels.push(label.to_string());
+            }
+        }
+        Ok(task)
+    }
+
+    fn title_pattern(&self) -> &str {
+        self.title.as_deref().unwrap_or("{{title}}")
+    }
+}
+
+/// `text` with its placeholders replaced; names with neither a value nor fallback go into `missing`.
+fn render(regex: &Regex, text: &str, values: &BTreeMap<String, String>, missing: &mut BTreeSet<String>) -> String {
+    regex
+        .replace_all(text, |captures: &regex::Captures| {
+            match (values.get(&captures[1]), captures.get(2)) {
+                (Some(value), _) => value.clone(),
+                (None, Some(fallback)) => fallback.as_str().to_string(),
+                (None, None) => {
+                    missing.insert(captures[1].to_string());
+                    String::new()
+                }
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_fill_placeholders_and_fields() -> Result<()> {
+        let builtin = TaskTemplate::builtin();
+        let bug = builtin.iter().find(|t| t.name == "bug").unwrap();
+        assert_eq!(bug.required_placeholders()?, vec!["title".to_string()]);
+
+        let values = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let task = bug.instantiate(&values(&[("title", "Crash on empty input"), ("actual", "panics"), ("labels", "cli, bug")]))?;
+        assert_eq!(task.title, "Crash on empty input");
+        assert_eq!(task.task_type, TaskType::Bug);
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.labels, vec!["bug".to_string(), "cli".to_string()]);
+        let description = task.description.unwrap();
+        assert!(description.contains("## Actual behaviour\npanics"));
+        assert!(description.contains(&format!("Reported {}", Utc::now().format("%Y-%m-%d"))));
+
+        assert!(matches!(bug.instantiate(&values(&[])), Err(Error::ValidationError(_))));
+        assert!(matches!(bug.instantiate(&values(&[("title", "x"), ("titel", "y")])), Err(Error::ValidationError(_))));
+
+        let spike = TaskTemplate::parse(
+            "research",
+            r#"{"title": "Research {{topic}}", "task_type": "spike", "estimate": "4h"}"#,
+            "json",
+        )?;
+        let task = spike.instantiate(&values(&[("topic", "caching"), ("priority", "critical"), ("id", "TASK-7")]))?;
+        assert_eq!((task.id.as_str(), task.title.as_str()), ("TASK-7", "Research caching"));
+        assert_eq!((task.priority, task.original_estimate_minutes), (Priority::Critical, Some(240)));
+        assert!(TaskTemplate::parse("bad", "task_type = \"chore\"", "toml").is_err());
+        Ok(())
+    }
+}
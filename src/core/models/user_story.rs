@@ -5,6 +5,8 @@ use chrono::{DateTime, Utc};
 #[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
 pub struct UserStory {
     pub id: String, // US-uuid
+    #[serde(default)]
+    pub key: Option<String>, // Sequential key such as US-12, assigned on creation
     pub title: String, // e.g., "As a [user type], I want [goal] so that [reason]"
     pub persona: String, // "As a [persona]"
     pub want: String,    // "I want [want]"
@@ -17,12 +19,15 @@ pub struct UserStory {
     pub tasks: Vec<String>, // IDs of linked tasks
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default = "super::initial_version")]
+    pub version: u32, // Bumped on every write, for optimistic concurrency
 }
 
 impl UserStory {
     pub fn new(id: String, title: String, persona: String, want: String, benefit: String) -> Self {
         Self {
             id,
+            key: None,
             title,
             persona,
             want,
@@ -35,6 +40,7 @@ impl UserStory {
             tasks: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
         }
     }
 }
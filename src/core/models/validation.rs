@@ -30,7 +30,7 @@ impl Validator {
 
     /// Validates a JSON value against the loaded schema.
     /// Returns Ok(()) if valid, or an Error with details if invalid.
-    pub fn validate<'v>(&self, data: &'v Value) -> Result<()> { // 'v for data lifetime
+    pub fn validate(&self, data: &Value) -> Result<()> {
         // jsonschema::Validator::validate returns Result<(), ErrorIterator>
         // The compiler insists the Err variant is a single ValidationError, not an ErrorIterator.
         // Let's proceed with that assumption, despite 0.30.0 source suggesting otherwise.
@@ -58,6 +58,24 @@ pub fn is_valid_task_id(id: &str) -> bool {
     regex::Regex::new(r"^[A-Z]+-\d+$").unwrap().is_match(id)
 }
 
+/// Validates a sequential key prefix (e.g., TASK in TASK-42).
+pub fn is_valid_key_prefix(prefix: &str) -> bool {
+    regex::Regex::new(r"^[A-Z][A-Z0-9]*$").unwrap().is_match(prefix)
+}
+
+/// Color names accepted for labels, besides `#rrggbb`.
+pub const LABEL_COLORS: [&str; 10] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "grey", "gray"];
+
+/// Validates a label name: not blank, and without commas, which separate labels on the command line.
+pub fn is_valid_label(name: &str) -> bool {
+    !name.trim().is_empty() && !name.contains(',')
+}
+
+/// Validates a label color: one of [`LABEL_COLORS`] or `#rrggbb`.
+pub fn is_valid_color(color: &str) -> bool {
+    LABEL_COLORS.contains(&color.to_lowercase().as_str()) || regex::Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap().is_match(color)
+}
+
 /// Loads a schema from a file path, parses it into a serde_json::Value.
 pub fn load_schema_value_from_file(schema_path: &std::path::Path) -> Result<Value> {
     let file_content = std::fs::read_to_string(schema_path)?;
@@ -155,4 +173,14 @@ mod tests {
         assert!(!is_valid_task_id("TASK-001-EXTRA")); // Extra parts
         assert!(!is_valid_task_id("")); // Empty string
     }
+
+    #[test]
+    fn test_is_valid_key_prefix() {
+        assert!(is_valid_key_prefix("TASK"));
+        assert!(is_valid_key_prefix("BE2"));
+        assert!(!is_valid_key_prefix("task"));
+        assert!(!is_valid_key_prefix("2FA"));
+        assert!(!is_valid_key_prefix("TASK-"));
+        assert!(!is_valid_key_prefix(""));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+// llmdoc/src/core/models/work_log.rs
+
+use chrono::{DateTime, Utc};
+
+use crate::core::errors::{Error, Result};
+
+/// Hours in a working day, used for `d` in durations such as `1d 4h`.
+pub const HOURS_PER_DAY: u32 = 8;
+
+/// Time spent on a task, as recorded by `task log`.
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, Clone)]
+pub struct WorkLogEntry {
+    pub id: i64,                   // Assigned by the database; ignored when logging
+    pub task_id: String,
+    pub author: Option<String>,
+    pub started_at: DateTime<Utc>, // When the work was done
+    pub duration_minutes: u32,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkLogEntry {
+    pub fn new(task_id: String, duration_minutes: u32, author: Option<String>, note: Option<String>) -> Self {
+        let now = Utc::now();
+        Self { id: 0, task_id, author, started_at: now, duration_minutes, note, created_at: now }
+    }
+}
+
+/// Parses a duration such as `2h30m`, `1d 4h`, `45m` or `90` (minutes) into minutes.
+pub fn parse_duration(s: &str) -> Result<u32> {
+    let invalid = || Error::InvalidInput(format!("Invalid duration '{}': use e.g. 2h30m, 1d 4h or 45m", s));
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+    if compact.is_empty() {
+        return Err(invalid());
+    }
+    if let Ok(minutes) = compact.parse::<u32>() {
+        return Ok(minutes);
+    }
+
+    let mut total: u32 = 0;
+    let mut number = String::new();
+    for c in compact.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: u32 = number.parse().map_err(|_| invalid())?;
+        let unit = match c {
+            'd' => HOURS_PER_DAY * 60,
+            'h' => 60,
+            'm' => 1,
+            _ => return Err(invalid()),
+        };
+        total = value.checked_mul(unit).and_then(|v| total.checked_add(v)).ok_or_else(invalid)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Formats minutes the way `parse_duration` reads them, e.g. `2h 30m`.
+pub fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format_durations() {
+        assert_eq!(parse_duration("2h30m").unwrap(), 150);
+        assert_eq!(parse_duration("1d 4h").unwrap(), 720);
+        assert_eq!(parse_duration("45M").unwrap(), 45);
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert!(parse_duration("2x").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("3h2").is_err());
+        assert_eq!(format_duration(150), "2h 30m");
+        assert_eq!(format_duration(120), "2h");
+        assert_eq!(format_duration(0), "0m");
+    }
+}
@@ -0,0 +1,52 @@
+// llmdoc/src/core/repositories/adr_repository.rs
+
+use rusqlite::types::Value;
+
+use crate::core::models::adr::Adr;
+use crate::core::repositories::{opt_text, text, timestamp, Entity, Repository};
+
+pub type AdrRepository = Repository<Adr>;
+
+impl Entity for Adr {
+    const TABLE: &'static str = "adrs";
+    const ENTITY_TYPE: &'static str = "adr";
+    const COLUMNS: &'static [&'static str] = &["key", "title", "status", "created_at", "updated_at"];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        &self.title
+    }
+
+    fn actor(&self) -> Option<&str> {
+        self.approved_by.as_deref().or(self.created_by.as_deref())
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            opt_text(self.key.as_ref()),
+            text(&self.title),
+            text(&self.status),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+        ]
+    }
+}
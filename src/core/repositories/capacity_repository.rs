@@ -0,0 +1,83 @@
+// llmdoc/src/core/repositories/capacity_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+use crate::core::errors::Result;
+use crate::core::models::capacity::MemberAvailability;
+use crate::core::repositories::{text, timestamp};
+
+/// Access to the `sprint_capacity` table: one row per sprint and member.
+pub struct CapacityRepository;
+
+impl CapacityRepository {
+    /// Saves `availability`, replacing any the member already has for the sprint.
+    pub fn upsert(conn: &Connection, availability: &MemberAvailability) -> Result<()> {
+        conn.execute(
+            "INSERT INTO sprint_capacity (sprint_id, member, days_available, focus_factor, leave_days, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT (sprint_id, member) DO UPDATE SET
+                 member = excluded.member,
+                 days_available = excluded.days_available,
+                 focus_factor = excluded.focus_factor,
+                 leave_days = excluded.leave_days,
+                 updated_at = excluded.updated_at",
+            params_from_iter([
+                text(&availability.sprint_id),
+                text(&availability.member),
+                Value::Real(availability.days_available),
+                Value::Real(availability.focus_factor),
+                Value::Real(availability.leave_days),
+                timestamp(&availability.updated_at),
+            ]),
+        )?;
+        Ok(())
+    }
+
+    pub fn delete(conn: &Connection, sprint_id: &str, member: &str) -> Result<bool> {
+        Ok(conn.execute("DELETE FROM sprint_capacity WHERE sprint_id = ?1 AND member = ?2", [sprint_id, member])? > 0)
+    }
+
+    pub fn get(conn: &Connection, sprint_id: &str, member: &str) -> Result<Option<MemberAvailability>> {
+        Ok(Self::query(conn, "sprint_id = ?1 AND member = ?2", vec![text(sprint_id), text(member)])?.pop())
+    }
+
+    /// Every member's availability for a sprint, by member.
+    pub fn list_for_sprint(conn: &Connection, sprint_id: &str) -> Result<Vec<MemberAvailability>> {
+        Self::query(conn, "sprint_id = ?1", vec![text(sprint_id)])
+    }
+
+    fn query(conn: &Connection, condition: &str, params: Vec<Value>) -> Result<Vec<MemberAvailability>> {
+        let sql = format!(
+            "SELECT sprint_id, member, days_available, focus_factor, leave_days, updated_at
+             FROM sprint_capacity WHERE {} ORDER BY member",
+            condition
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), read_row)?;
+        let mut availability = Vec::new();
+        for row in rows {
+            availability.push(row??);
+        }
+        Ok(availability)
+    }
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<Result<MemberAvailability>> {
+    let sprint_id = row.get(0)?;
+    let member = row.get(1)?;
+    let days_available = row.get(2)?;
+    let focus_factor = row.get(3)?;
+    let leave_days = row.get(4)?;
+    let updated_at: String = row.get(5)?;
+    Ok((|| {
+        Ok(MemberAvailability {
+            sprint_id,
+            member,
+            days_available,
+            focus_factor,
+            leave_days,
+            updated_at: crate::utils::parse_datetime(&updated_at)?,
+        })
+    })())
+}
@@ -0,0 +1,87 @@
+// llmdoc/src/core/repositories/comment_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::core::errors::Result;
+use crate::core::models::comment::Comment;
+use crate::core::repositories::{opt_text, text, timestamp, Entity, Repository};
+
+pub type CommentRepository = Repository<Comment>;
+
+impl Entity for Comment {
+    const TABLE: &'static str = "comments";
+    const ENTITY_TYPE: &'static str = "comment";
+    const COLUMNS: &'static [&'static str] = &[
+        "entity_id",
+        "entity_type",
+        "author",
+        "reply_to",
+        "created_at",
+        "updated_at",
+    ];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        self.body.lines().next().unwrap_or("")
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            text(&self.entity_id),
+            text(&self.entity_type),
+            opt_text(self.author.as_ref()),
+            opt_text(self.reply_to.as_ref()),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+        ]
+    }
+
+    fn actor(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+}
+
+impl Repository<Comment> {
+    /// Comments on one entity, oldest first.
+    pub fn list_for_entity(conn: &Connection, entity_id: &str) -> Result<Vec<Comment>> {
+        // Timestamps have second precision, so fall back to insertion order rather than the random ID
+        Self::query(
+            conn,
+            "SELECT data FROM comments WHERE entity_id = ?1 AND deleted_at IS NULL ORDER BY created_at, rowid",
+            vec![text(entity_id)],
+        )
+    }
+
+    /// Every live comment, oldest first.
+    pub fn list_all(conn: &Connection) -> Result<Vec<Comment>> {
+        Self::query(conn, "SELECT data FROM comments WHERE deleted_at IS NULL ORDER BY created_at, rowid", Vec::new())
+    }
+
+    /// Live comments matching a full-text query, best match first. Every word must appear.
+    pub fn search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<Comment>> {
+        // Quote each word so FTS5 operators and punctuation in user input are matched literally
+        let terms: Vec<String> = query.split_whitespace().map(|w| format!("\"{}\"", w.replace('"', "\"\""))).collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        Self::query(
+            conn,
+            "SELECT c.data FROM comments_fts f JOIN comments c ON c.id = f.comment_id
+             WHERE comments_fts MATCH ?1 AND c.deleted_at IS NULL
+             ORDER BY bm25(comments_fts) LIMIT ?2",
+            vec![text(terms.join(" ")), Value::Integer(limit as i64)],
+        )
+    }
+}
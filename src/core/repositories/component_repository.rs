@@ -0,0 +1,49 @@
+// llmdoc/src/core/repositories/component_repository.rs
+
+use rusqlite::types::Value;
+
+use crate::core::models::component::Component;
+use crate::core::repositories::{opt_text, text, timestamp, Entity, Repository};
+
+pub type ComponentRepository = Repository<Component>;
+
+impl Entity for Component {
+    const TABLE: &'static str = "components";
+    const ENTITY_TYPE: &'static str = "component";
+    const COLUMNS: &'static [&'static str] = &["key", "name", "component_type", "owner", "created_at", "updated_at"];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        &self.name
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            opt_text(self.key.as_ref()),
+            text(&self.name),
+            text(&self.component_type),
+            opt_text(self.owner.as_ref()),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+        ]
+    }
+}
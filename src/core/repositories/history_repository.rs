@@ -0,0 +1,190 @@
+// llmdoc/src/core/repositories/history_repository.rs
+
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+use serde_json::Value as Json;
+use std::str::FromStr;
+
+use crate::core::errors::{Error, Result};
+use crate::core::models::history::{ChangeType, HistoryEntry};
+use crate::core::repositories::{opt_text, text};
+
+/// Fields that change on every write and would only add noise to the history.
+const IGNORED_FIELDS: &[&str] = &["updated_at", "updated_by", "version"];
+
+/// Append-only access to the `entity_history` table.
+pub struct HistoryRepository;
+
+impl HistoryRepository {
+    pub fn record_created(conn: &Connection, entity_type: &str, entity_id: &str, document: &Json, actor: Option<&str>) -> Result<()> {
+        let version = Self::next_version(conn, entity_type, entity_id)?;
+        Self::append(conn, entity_type, entity_id, version, ChangeType::Created, None, None, Some(document), actor)
+    }
+
+    /// Records one entry per top-level field that differs between `before` and `after`.
+    /// Returns the number of changed fields.
+    pub fn record_updated(conn: &Connection, entity_type: &str, entity_id: &str, before: &Json, after: &Json, actor: Option<&str>) -> Result<usize> {
+        let changes = diff_fields(before, after);
+        if changes.is_empty() {
+            return Ok(0);
+        }
+        let version = Self::next_version(conn, entity_type, entity_id)?;
+        for (field, old, new) in &changes {
+            Self::append(conn, entity_type, entity_id, version, ChangeType::Updated, Some(field), Some(old), Some(new), actor)?;
+        }
+        Ok(changes.len())
+    }
+
+    pub fn record_deleted(conn: &Connection, entity_type: &str, entity_id: &str, document: &Json, actor: Option<&str>) -> Result<()> {
+        let version = Self::next_version(conn, entity_type, entity_id)?;
+        Self::append(conn, entity_type, entity_id, version, ChangeType::Deleted, None, Some(document), None, actor)
+    }
+
+    pub fn record_restored(conn: &Connection, entity_type: &str, entity_id: &str, document: Option<&Json>, actor: Option<&str>) -> Result<()> {
+        let version = Self::next_version(conn, entity_type, entity_id)?;
+        Self::append(conn, entity_type, entity_id, version, ChangeType::Restored, None, None, document, actor)
+    }
+
+    pub fn record_purged(conn: &Connection, entity_type: &str, entity_id: &str) -> Result<()> {
+        let version = Self::next_version(conn, entity_type, entity_id)?;
+        Self::append(conn, entity_type, entity_id, version, ChangeType::Purged, None, None, None, None)
+    }
+
+    /// All entries for one entity, oldest first.
+    pub fn list_for(conn: &Connection, entity_type: &str, entity_id: &str) -> Result<Vec<HistoryEntry>> {
+        Self::query(conn, "entity_type = ?1 AND entity_id = ?2", vec![text(entity_type), text(entity_id)])
+    }
+
+    /// All entries for an entity ID (of any type), oldest first.
+    pub fn list_for_entity(conn: &Connection, entity_id: &str) -> Result<Vec<HistoryEntry>> {
+        Self::query(conn, "entity_id = ?1", vec![text(entity_id)])
+    }
+
+    /// All entries for an entity type, oldest first.
+    pub fn list_for_type(conn: &Connection, entity_type: &str) -> Result<Vec<HistoryEntry>> {
+        Self::query(conn, "entity_type = ?1", vec![text(entity_type)])
+    }
+
+    /// All entries for the tasks that were in `sprint_id` at some point, oldest first.
+    pub fn list_for_sprint_tasks(conn: &Connection, sprint_id: &str) -> Result<Vec<HistoryEntry>> {
+        Self::query(
+            conn,
+            "entity_type = 'task' AND entity_id IN (
+                SELECT entity_id FROM entity_history
+                WHERE entity_type = 'task' AND (
+                    (field IS NULL AND json_valid(new_value) AND json_extract(new_value, '$.sprint_id') = ?1)
+                    OR (field = 'sprint_id' AND new_value = json_quote(?1))
+                )
+            )",
+            vec![text(sprint_id)],
+        )
+    }
+
+    /// The whole change log, grouped by entity and oldest first.
+    pub fn query_all(conn: &Connection) -> Result<Vec<HistoryEntry>> {
+        Self::query(conn, "1 = 1", Vec::new())
+    }
+
+    fn query(conn: &Connection, condition: &str, params: Vec<Value>) -> Result<Vec<HistoryEntry>> {
+        let sql = format!(
+            "SELECT id, entity_type, entity_id, version, change_type, field, old_value, new_value, changed_by, changed_at
+             FROM entity_history WHERE {} ORDER BY entity_id, version, id",
+            condition
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), read_row)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row??);
+        }
+        Ok(entries)
+    }
+
+    fn next_version(conn: &Connection, entity_type: &str, entity_id: &str) -> Result<u32> {
+        let version: u32 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM entity_history WHERE entity_type = ?1 AND entity_id = ?2",
+            [entity_type, entity_id],
+            |row| row.get(0),
+        )?;
+        Ok(version)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn append(
+        conn: &Connection,
+        entity_type: &str,
+        entity_id: &str,
+        version: u32,
+        change_type: ChangeType,
+        field: Option<&str>,
+        old_value: Option<&Json>,
+        new_value: Option<&Json>,
+        actor: Option<&str>,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO entity_history (entity_type, entity_id, version, change_type, field, old_value, new_value, changed_by, changed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params_from_iter([
+                text(entity_type),
+                text(entity_id),
+                Value::Integer(version.into()),
+                text(&change_type),
+                opt_text(field),
+                opt_text(old_value),
+                opt_text(new_value),
+                opt_text(actor),
+                text(crate::utils::format_datetime(Utc::now())),
+            ]),
+        )?;
+        Ok(())
+    }
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<Result<HistoryEntry>> {
+    let change_type: String = row.get(4)?;
+    let old_value: Option<String> = row.get(6)?;
+    let new_value: Option<String> = row.get(7)?;
+    let changed_at: String = row.get(9)?;
+    let id = row.get(0)?;
+    let entity_type = row.get(1)?;
+    let entity_id = row.get(2)?;
+    let version = row.get(3)?;
+    let field = row.get(5)?;
+    let changed_by = row.get(8)?;
+    Ok((|| {
+        Ok(HistoryEntry {
+            id,
+            entity_type,
+            entity_id,
+            version,
+            change_type: ChangeType::from_str(&change_type).map_err(|e| Error::InvalidInput(e.to_string()))?,
+            field,
+            old_value: old_value.map(|v| serde_json::from_str(&v)).transpose()?,
+            new_value: new_value.map(|v| serde_json::from_str(&v)).transpose()?,
+            changed_by,
+            changed_at: crate::utils::parse_datetime(&changed_at)?,
+        })
+    })())
+}
+
+/// Top-level fields whose values differ, as `(field, old, new)`; missing fields compare as `null`.
+pub fn diff_fields(before: &Json, after: &Json) -> Vec<(String, Json, Json)> {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|field| !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter_map(|field| {
+            let old = before.get(field).cloned().unwrap_or(Json::Null);
+            let new = after.get(field).cloned().unwrap_or(Json::Null);
+            (old != new).then(|| (field.clone(), old, new))
+        })
+        .collect()
+}
@@ -0,0 +1,242 @@
+// llmdoc/src/core/repositories/key_repository.rs
+
+//! Sequential keys such as `TASK-42`, and resolution of what a user typed to an entity ID.
+//!
+//! Keys are numbered per entity type from the `key_sequences` table and are never
+//! reused, even after the entity is purged. Anything that takes an ID also accepts
+//! the entity's key (in any case) or a unique prefix of its UUID.
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+
+use crate::core::errors::{Error, Result};
+use crate::core::repositories::trash_repository::TABLE_ENTITY_TYPES;
+use crate::core::repositories::{
+    escape_like, text, CommentRepository, Entity, EntityKind, Reference, Repository, SprintRepository, TaskRepository,
+};
+
+/// Shortest UUID prefix accepted, so a stray digit does not match something at random.
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// How an input is matched against a table, in the order the matches are tried.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lookup {
+    Id,
+    Key,
+    Prefix,
+}
+
+pub const LOOKUPS: [Lookup; 3] = [Lookup::Id, Lookup::Key, Lookup::Prefix];
+
+/// Access to the `key_sequences` counters, and ID resolution across entity types.
+pub struct KeyRepository;
+
+impl KeyRepository {
+    /// Takes the next key `PREFIX-n` for `kind`, skipping any that is already an ID or key in its table.
+    pub fn next_key(conn: &Connection, kind: &EntityKind, prefix: &str) -> Result<String> {
+        let mut next: i64 = conn
+            .query_row(
+                "SELECT next_value FROM key_sequences WHERE entity_type = ?1",
+                [kind.entity_type],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(1);
+        let taken_sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE id = ?1 COLLATE NOCASE OR key = ?1 COLLATE NOCASE",
+            kind.table
+        );
+        let key = loop {
+            let key = format!("{}-{}", prefix, next);
+            next += 1;
+            let taken: i64 = conn.query_row(&taken_sql, [&key], |row| row.get(0))?;
+            if taken == 0 {
+                break key;
+            }
+        };
+        conn.execute(
+            "INSERT INTO key_sequences (entity_type, next_value) VALUES (?1, ?2)
+             ON CONFLICT(entity_type) DO UPDATE SET next_value = excluded.next_value",
+            params_from_iter([text(kind.entity_type), Value::Integer(next)]),
+        )?;
+        Ok(key)
+    }
+
+    /// The key a new `kind` entity is stored with: `key` once it is checked to be free, or the
+    /// next `prefix` key if it has none. `None` for entity types without keys.
+    pub fn key_for_new(conn: &Connection, kind: &EntityKind, key: Option<&str>, prefix: &str) -> Result<Option<String>> {
+        if !kind.has_keys() {
+            return Ok(None);
+        }
+        match key {
+            Some(key) => {
+                if let Some(other) = Self::find_ids(conn, kind, Lookup::Key, key, true)?.first() {
+                    return Err(key_taken(key, other));
+                }
+                Ok(Some(key.to_string()))
+            }
+            None => Self::next_key(conn, kind, prefix).map(Some),
+        }
+    }
+
+    /// IDs in `kind`'s table that `input` matches by `lookup`, in ID order.
+    pub fn find_ids(conn: &Connection, kind: &EntityKind, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<String>> {
+        let input = input.trim();
+        let (condition, params) = match lookup {
+            Lookup::Id => ("id = ?1", vec![text(input)]),
+            Lookup::Key if kind.has_keys() => ("key = ?1 COLLATE NOCASE", vec![text(input)]),
+            Lookup::Prefix if input.len() >= MIN_PREFIX_LEN => {
+                let escaped = escape_like(input);
+                (
+                    "(id LIKE ?1 ESCAPE '\\' OR id LIKE ?2 ESCAPE '\\')",
+                    vec![text(format!("{}%", escaped)), text(format!("%-{}%", escaped))],
+                )
+            }
+            _ => return Ok(Vec::new()),
+        };
+        let live = if include_deleted { "" } else { " AND deleted_at IS NULL" };
+        let sql = format!("SELECT id FROM {} WHERE {}{} ORDER BY id", kind.table, condition, live);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), |row| row.get::<_, String>(0))?;
+        let mut ids = Vec::new();
+        for id in rows {
+            let id = id?;
+            if lookup != Lookup::Prefix || uuid_starts_with(&id, input) {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// The ID of the `entity_type` row that `input` names. See [`Repository::resolve_id`].
+    /// Entity types kept by the [`Store`](crate::core::store::Store) are resolved there instead.
+    pub fn resolve(conn: &Connection, entity_type: &str, input: &str, include_deleted: bool) -> Result<Option<String>> {
+        match entity_type {
+            "task" => TaskRepository::resolve_id(conn, input, include_deleted),
+            "sprint" => SprintRepository::resolve_id(conn, input, include_deleted),
+            "comment" => CommentRepository::resolve_id(conn, input, include_deleted),
+            _ => Err(Error::InvalidInput(format!("Unknown entity type: {}", entity_type))),
+        }
+    }
+
+    /// Everything in the tables of [`TABLE_ENTITY_TYPES`] that `input` matches by `lookup`.
+    pub fn find_in_tables(conn: &Connection, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<Reference>> {
+        let mut found = Vec::new();
+        for entity_type in TABLE_ENTITY_TYPES {
+            let ids = match *entity_type {
+                "task" => TaskRepository::find_ids(conn, lookup, input, include_deleted)?,
+                _ => SprintRepository::find_ids(conn, lookup, input, include_deleted)?,
+            };
+            found.extend(ids.into_iter().map(|id| Reference { entity_type: entity_type.to_string(), id }));
+        }
+        Ok(found)
+    }
+}
+
+impl<E: Entity> Repository<E> {
+    /// Gives `entity` the next key for its type, or checks the key it was given is free.
+    /// Entities without a `key` column are left alone.
+    pub fn assign_key(conn: &Connection, entity: &mut E, prefix: &str) -> Result<()> {
+        if let Some(key) = KeyRepository::key_for_new(conn, &EntityKind::of::<E>(), entity.key(), prefix)? {
+            entity.set_key(key);
+        }
+        Ok(())
+    }
+
+    /// The ID of the row that `input` names: its exact ID, its key in any case, or a prefix of
+    /// at least [`MIN_PREFIX_LEN`] characters of its UUID (with or without a type prefix such
+    /// as `adr-`). `None` if nothing matches; an error if a UUID prefix matches several rows.
+    pub fn resolve_id(conn: &Connection, input: &str, include_deleted: bool) -> Result<Option<String>> {
+        resolve_with(input, |lookup| Self::find_ids(conn, lookup, input, include_deleted))
+    }
+
+    fn find_ids(conn: &Connection, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<String>> {
+        KeyRepository::find_ids(conn, &EntityKind::of::<E>(), lookup, input, include_deleted)
+    }
+}
+
+/// The one ID that `find` matches, trying each [`Lookup`] in turn. An error if a lookup matches several.
+pub fn resolve_with(input: &str, mut find: impl FnMut(Lookup) -> Result<Vec<String>>) -> Result<Option<String>> {
+    for lookup in LOOKUPS {
+        let mut ids = find(lookup)?;
+        if ids.len() > 1 {
+            return Err(ambiguous(input, ids.into_iter()));
+        }
+        if let Some(id) = ids.pop() {
+            return Ok(Some(id));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether `id` is a UUID, optionally behind a type prefix like `adr-`, that starts with `prefix`.
+pub(crate) fn uuid_starts_with(id: &str, prefix: &str) -> bool {
+    let prefix = prefix.to_lowercase();
+    let id = id.to_lowercase();
+    let uuid = match id.split_once('-') {
+        Some((word, rest)) if !word.chars().all(|c| c.is_ascii_hexdigit()) => rest,
+        _ => id.as_str(),
+    };
+    uuid::Uuid::parse_str(uuid).is_ok() && (id.starts_with(&prefix) || uuid.starts_with(&prefix))
+}
+
+/// The error for a new entity given a key that `other` already has.
+pub(crate) fn key_taken(key: &str, other: &str) -> Error {
+    Error::ValidationError(format!("Key {} is already used by {}", key, other))
+}
+
+pub(crate) fn ambiguous(input: &str, matches: impl Iterator<Item = String>) -> Error {
+    Error::InvalidInput(format!(
+        "'{}' matches more than one item ({}); type more of the ID",
+        input,
+        matches.collect::<Vec<_>>().join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Adr, Task, TaskType};
+    use crate::core::repositories::AdrRepository;
+    use crate::core::schema;
+
+    #[test]
+    fn test_keys_are_sequential_and_resolve() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        schema::migrate_up(&mut conn, None)?;
+
+        // TASK-2 is already taken as an ID, so numbering skips it
+        let mut taken = Task::new("TASK-2".to_string(), "Named".to_string(), TaskType::Task);
+        TaskRepository::assign_key(&conn, &mut taken, "TASK")?;
+        TaskRepository::insert(&conn, &taken)?;
+        assert_eq!(taken.key.as_deref(), Some("TASK-1"));
+        let mut second = Task::new(crate::utils::new_uuid_v4_str(), "Generated".to_string(), TaskType::Task);
+        TaskRepository::assign_key(&conn, &mut second, "TASK")?;
+        TaskRepository::insert(&conn, &second)?;
+        assert_eq!(second.key.as_deref(), Some("TASK-3"));
+
+        let mut clash = Task::new("TASK-9".to_string(), "Clash".to_string(), TaskType::Task);
+        clash.key = Some("task-3".to_string());
+        assert!(TaskRepository::assign_key(&conn, &mut clash, "TASK").is_err());
+
+        assert_eq!(TaskRepository::resolve_id(&conn, "TASK-2", false)?.as_deref(), Some("TASK-2"));
+        assert_eq!(TaskRepository::resolve_id(&conn, "task-3", false)?, Some(second.id.clone()));
+        assert_eq!(TaskRepository::resolve_id(&conn, &second.id[..8], false)?, Some(second.id.clone()));
+        assert_eq!(TaskRepository::resolve_id(&conn, &second.id[..2], false)?, None);
+        assert_eq!(TaskRepository::resolve_id(&conn, "TASK-404", false)?, None);
+
+        let mut adr = Adr::new("Use SQLite".to_string(), String::new(), String::new(), String::new());
+        AdrRepository::assign_key(&conn, &mut adr, "ADR")?;
+        AdrRepository::insert(&conn, &adr)?;
+        let uuid_part = adr.id.trim_start_matches("adr-");
+        assert_eq!(AdrRepository::resolve_id(&conn, &uuid_part[..6], false)?, Some(adr.id.clone()));
+        assert_eq!(AdrRepository::resolve_id(&conn, "adr-1", false)?, Some(adr.id.clone()));
+        let found = KeyRepository::find_in_tables(&conn, Lookup::Key, "task-3", false)?;
+        assert_eq!(found, vec![Reference { entity_type: "task".to_string(), id: second.id.clone() }]);
+
+        TaskRepository::delete(&conn, "TASK-2")?;
+        assert_eq!(TaskRepository::resolve_id(&conn, "TASK-1", false)?, None);
+        assert_eq!(TaskRepository::resolve_id(&conn, "TASK-1", true)?.as_deref(), Some("TASK-2"));
+        Ok(())
+    }
+}
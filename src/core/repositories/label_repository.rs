@@ -0,0 +1,86 @@
+// llmdoc/src/core/repositories/label_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+use crate::core::errors::Result;
+use crate::core::models::label::Label;
+use crate::core::repositories::{opt_text, text, timestamp};
+
+/// Access to the `labels` registry. Names are compared ignoring case.
+pub struct LabelRepository;
+
+impl LabelRepository {
+    pub fn insert(conn: &Connection, label: &Label) -> Result<()> {
+        conn.execute(
+            "INSERT INTO labels (name, description, color, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params_from_iter([
+                text(&label.name),
+                opt_text(label.description.as_ref()),
+                opt_text(label.color.as_ref()),
+                timestamp(&label.created_at),
+                timestamp(&label.updated_at),
+            ]),
+        )?;
+        Ok(())
+    }
+
+    /// Saves `label` under `name`, which may differ from its new name. Returns whether it existed.
+    pub fn update(conn: &Connection, name: &str, label: &Label) -> Result<bool> {
+        let changed = conn.execute(
+            "UPDATE labels SET name = ?1, description = ?2, color = ?3, updated_at = ?4 WHERE name = ?5",
+            params_from_iter([
+                text(&label.name),
+                opt_text(label.description.as_ref()),
+                opt_text(label.color.as_ref()),
+                timestamp(&label.updated_at),
+                text(name),
+            ]),
+        )?;
+        Ok(changed > 0)
+    }
+
+    pub fn delete(conn: &Connection, name: &str) -> Result<bool> {
+        Ok(conn.execute("DELETE FROM labels WHERE name = ?1", [name])? > 0)
+    }
+
+    pub fn get(conn: &Connection, name: &str) -> Result<Option<Label>> {
+        Ok(Self::query(conn, "name = ?1", vec![text(name)])?.pop())
+    }
+
+    /// Every registered label, by name.
+    pub fn list(conn: &Connection) -> Result<Vec<Label>> {
+        Self::query(conn, "1 = 1", Vec::new())
+    }
+
+    fn query(conn: &Connection, condition: &str, params: Vec<Value>) -> Result<Vec<Label>> {
+        let sql = format!(
+            "SELECT name, description, color, created_at, updated_at FROM labels WHERE {} ORDER BY name COLLATE NOCASE",
+            condition
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), read_row)?;
+        let mut labels = Vec::new();
+        for row in rows {
+            labels.push(row??);
+        }
+        Ok(labels)
+    }
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<Result<Label>> {
+    let name = row.get(0)?;
+    let description = row.get(1)?;
+    let color = row.get(2)?;
+    let created_at: String = row.get(3)?;
+    let updated_at: String = row.get(4)?;
+    Ok((|| {
+        Ok(Label {
+            name,
+            description,
+            color,
+            created_at: crate::utils::parse_datetime(&created_at)?,
+            updated_at: crate::utils::parse_datetime(&updated_at)?,
+        })
+    })())
+}
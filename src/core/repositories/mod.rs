@@ -0,0 +1,740 @@
+// llmdoc/src/core/repositories/mod.rs
+
+//! Typed persistence layer shared by every service.
+//!
+//! Each entity is stored in its own table with a handful of indexed, typed
+//! columns for the fields we filter and sort on, plus a `data` column holding
+//! the full model as JSON. The JSON document is the source of truth; the typed
+//! columns are derived from it on every write via [`Entity::column_values`].
+//!
+//! Repository functions are synchronous and operate on a borrowed
+//! `rusqlite::Connection`, so services call them from inside
+//! `DbConnection::with_connection` and can combine several calls on the
+//! same connection.
+//!
+//! Every insert, update and delete also appends to `entity_history` on the
+//! same connection, so the change log can never drift from the data.
+//!
+//! Updates use optimistic concurrency: every row carries a `version` that is
+//! bumped on each write, and an update whose entity is not at the stored
+//! version fails with [`Error::Conflict`] instead of overwriting.
+//!
+//! Deleting sets a `deleted_at` tombstone; tombstoned rows are invisible to
+//! every read except [`Repository::list_deleted`] until they are restored or
+//! purged.
+
+pub mod adr_repository;
+pub mod capacity_repository;
+pub mod comment_repository;
+pub mod component_repository;
+pub mod history_repository;
+pub mod key_repository;
+pub mod label_repository;
+pub mod sprint_repository;
+pub mod task_repository;
+pub mod trash_repository;
+pub mod user_story_repository;
+pub mod work_log_repository;
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+use crate::core::errors::{Error, Result};
+
+pub use adr_repository::AdrRepository;
+pub use capacity_repository::CapacityRepository;
+pub use comment_repository::CommentRepository;
+pub use component_repository::ComponentRepository;
+pub use history_repository::HistoryRepository;
+pub use key_repository::KeyRepository;
+pub use label_repository::LabelRepository;
+pub use sprint_repository::SprintRepository;
+pub use task_repository::TaskRepository;
+pub use trash_repository::{Reference, TrashRepository};
+pub use user_story_repository::UserStoryRepository;
+pub use work_log_repository::WorkLogRepository;
+
+/// A model persisted by [`Repository`].
+pub trait Entity: Serialize + DeserializeOwned + Send + 'static {
+    /// Table holding this entity.
+    const TABLE: &'static str;
+    /// Name recorded in `entity_history.entity_type`.
+    const ENTITY_TYPE: &'static str;
+    /// Typed columns stored next to `id` and `data`, in the order returned by `column_values`.
+    const COLUMNS: &'static [&'static str];
+
+    fn id(&self) -> &str;
+
+    /// Sequential key such as `TASK-42`. Only entities with a `key` column have one.
+    fn key(&self) -> Option<&str> {
+        None
+    }
+
+    /// Sets the key; ignored by entities without a `key` column.
+    fn set_key(&mut self, _key: String) {}
+
+    /// Version this copy was read at; checked and bumped by [`Repository::update`].
+    fn version(&self) -> u32;
+
+    fn set_version(&mut self, version: u32);
+
+    /// Human-readable name (title or name) used in listings.
+    fn display_name(&self) -> &str;
+
+    /// Values for [`Entity::COLUMNS`], derived from the model.
+    fn column_values(&self) -> Vec<Value>;
+
+    /// JSON document stored in the `data` column.
+    fn to_document(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Who made the latest change, recorded in the history. `None` if the model has no audit fields.
+    fn actor(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Table layout of an [`Entity`] type, for code that stores entities without knowing their type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityKind {
+    pub table: &'static str,
+    pub entity_type: &'static str,
+    pub columns: &'static [&'static str],
+}
+
+impl EntityKind {
+    pub const fn of<E: Entity>() -> Self {
+        Self { table: E::TABLE, entity_type: E::ENTITY_TYPE, columns: E::COLUMNS }
+    }
+
+    /// Whether entities of this kind have a sequential key.
+    pub fn has_keys(&self) -> bool {
+        self.columns.contains(&"key")
+    }
+}
+
+/// An entity as it is written: its JSON document plus the values derived from it that are
+/// indexed, checked and shown without deserializing the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub id: String,
+    pub key: Option<String>,
+    pub version: u32,
+    /// [`Entity::display_name`]
+    pub name: String,
+    /// [`Entity::actor`]
+    pub actor: Option<String>,
+    /// Values for the kind's typed columns, in order.
+    pub columns: Vec<Value>,
+    pub document: serde_json::Value,
+}
+
+impl Record {
+    pub fn of<E: Entity>(entity: &E) -> Result<Self> {
+        Ok(Self {
+            id: entity.id().to_string(),
+            key: entity.key().map(str::to_string),
+            version: entity.version(),
+            name: entity.display_name().to_string(),
+            actor: entity.actor().map(str::to_string),
+            columns: entity.column_values(),
+            document: serde_json::from_str(&entity.to_document()?)?,
+        })
+    }
+
+    pub fn into_entity<E: Entity>(self) -> Result<E> {
+        Ok(serde_json::from_value(self.document)?)
+    }
+
+    /// Sets the key in the document and in the `key` column.
+    pub fn set_key(&mut self, kind: &EntityKind, key: String) {
+        if let Some(i) = kind.columns.iter().position(|c| *c == "key") {
+            self.columns[i] = text(&key);
+        }
+        self.document["key"] = serde_json::Value::String(key.clone());
+        self.key = Some(key);
+    }
+
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+        self.document["version"] = version.into();
+    }
+
+    /// Value of one of the kind's typed columns, as text. `None` if it is null or not a column.
+    pub fn column_text(&self, kind: &EntityKind, column: &str) -> Option<String> {
+        let i = kind.columns.iter().position(|c| *c == column)?;
+        match self.columns.get(i)? {
+            Value::Text(s) => Some(s.clone()),
+            Value::Integer(n) => Some(n.to_string()),
+            Value::Real(x) => Some(x.to_string()),
+            Value::Null | Value::Blob(_) => None,
+        }
+    }
+}
+
+/// The writes of [`Repository`] on a [`Record`] of a given [`EntityKind`], for callers that
+/// handle entities without knowing their type, such as a [`Store`](crate::core::store::Store).
+pub struct RecordRepository;
+
+impl RecordRepository {
+    pub fn insert(conn: &Connection, kind: &EntityKind, record: &Record) -> Result<()> {
+        let columns = kind.columns.join(", ");
+        let placeholders = (1..=kind.columns.len() + 3)
+            .map(|i| format!("?{}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!("INSERT INTO {} (id, {}, version, data) VALUES ({})", kind.table, columns, placeholders);
+
+        let mut values = vec![Value::Text(record.id.clone())];
+        values.extend(record.columns.iter().cloned());
+        values.push(Value::Integer(record.version.into()));
+        values.push(Value::Text(record.document.to_string()));
+        conn.execute(&sql, params_from_iter(values))?;
+
+        HistoryRepository::record_created(conn, kind.entity_type, &record.id, &record.document, record.actor.as_deref())
+    }
+
+    /// Overwrites an existing row, provided it is still at `record.version`, and bumps the
+    /// version on both the row and `record`. Returns `false` if no row has the record's ID,
+    /// and [`Error::Conflict`] if the row has been written since `record` was read.
+    pub fn update(conn: &Connection, kind: &EntityKind, record: &mut Record) -> Result<bool> {
+        let Some(before) = Self::get(conn, kind, &record.id)? else {
+            return Ok(false);
+        };
+        let expected = record.version;
+        let current = stored_version(&before);
+        if current != expected {
+            return Err(conflict(kind, &record.id, expected, before));
+        }
+
+        let assignments = kind
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{} = ?{}", column, i + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let n = kind.columns.len();
+        let sql = format!(
+            "UPDATE {} SET {}, version = ?{}, data = ?{} WHERE id = ?{} AND version = ?{} AND deleted_at IS NULL",
+            kind.table,
+            assignments,
+            n + 1,
+            n + 2,
+            n + 3,
+            n + 4
+        );
+
+        record.set_version(expected + 1);
+        let mut values = record.columns.clone();
+        values.push(Value::Integer((expected + 1).into()));
+        values.push(Value::Text(record.document.to_string()));
+        values.push(Value::Text(record.id.clone()));
+        values.push(Value::Integer(expected.into()));
+        if conn.execute(&sql, params_from_iter(values))? == 0 {
+            // Another connection wrote the row between our read and our write
+            record.set_version(expected);
+            let current = Self::get(conn, kind, &record.id)?.unwrap_or(before);
+            return Err(conflict(kind, &record.id, expected, current));
+        }
+
+        HistoryRepository::record_updated(conn, kind.entity_type, &record.id, &before, &record.document, record.actor.as_deref())?;
+        Ok(true)
+    }
+
+    /// Tombstones a row. Returns `false` if it did not exist or was already deleted.
+    pub fn delete(conn: &Connection, kind: &EntityKind, id: &str) -> Result<bool> {
+        let Some(before) = Self::get(conn, kind, id)? else {
+            return Ok(false);
+        };
+        let sql = format!("UPDATE {} SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL", kind.table);
+        conn.execute(&sql, params_from_iter([timestamp(&Utc::now()), text(id)]))?;
+
+        HistoryRepository::record_deleted(conn, kind.entity_type, id, &before, None)?;
+        Ok(true)
+    }
+
+    /// Clears the tombstone of a deleted row. Returns `false` if there is no deleted row with that ID.
+    pub fn restore(conn: &Connection, kind: &EntityKind, id: &str) -> Result<bool> {
+        let sql = format!("UPDATE {} SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL", kind.table);
+        if conn.execute(&sql, [id])? == 0 {
+            return Ok(false);
+        }
+
+        let after = Self::get(conn, kind, id)?;
+        HistoryRepository::record_restored(conn, kind.entity_type, id, after.as_ref(), None)?;
+        Ok(true)
+    }
+
+    /// Permanently removes a deleted row. Live rows are never purged.
+    pub fn purge(conn: &Connection, kind: &EntityKind, id: &str) -> Result<bool> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1 AND deleted_at IS NOT NULL", kind.table);
+        if conn.execute(&sql, [id])? == 0 {
+            return Ok(false);
+        }
+
+        HistoryRepository::record_purged(conn, kind.entity_type, id)?;
+        Ok(true)
+    }
+
+    /// The stored document of a live row.
+    pub fn get(conn: &Connection, kind: &EntityKind, id: &str) -> Result<Option<serde_json::Value>> {
+        let sql = format!("SELECT data FROM {} WHERE id = ?1 AND deleted_at IS NULL", kind.table);
+        let data: Option<String> = conn.query_row(&sql, [id], |row| row.get(0)).optional()?;
+        data.map(|d| serde_json::from_str(&d).map_err(Into::into)).transpose()
+    }
+
+    /// Documents of the live rows, oldest first.
+    pub fn list(conn: &Connection, kind: &EntityKind) -> Result<Vec<serde_json::Value>> {
+        let sql = format!("SELECT data FROM {} WHERE deleted_at IS NULL ORDER BY created_at, rowid", kind.table);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut documents = Vec::new();
+        for data in rows {
+            documents.push(serde_json::from_str(&data?)?);
+        }
+        Ok(documents)
+    }
+
+    /// Documents of the deleted rows with their deletion time, oldest deletion first.
+    pub fn list_deleted(conn: &Connection, kind: &EntityKind) -> Result<Vec<(serde_json::Value, DateTime<Utc>)>> {
+        let sql = format!("SELECT data, deleted_at FROM {} WHERE deleted_at IS NOT NULL ORDER BY deleted_at, id", kind.table);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        let mut deleted = Vec::new();
+        for row in rows {
+            let (data, deleted_at) = row?;
+            deleted.push((serde_json::from_str(&data)?, crate::utils::parse_datetime(&deleted_at)?));
+        }
+        Ok(deleted)
+    }
+}
+
+/// Generic CRUD over an [`Entity`] table.
+pub struct Repository<E>(PhantomData<E>);
+
+impl<E: Entity> Repository<E> {
+    const KIND: EntityKind = EntityKind::of::<E>();
+
+    pub fn insert(conn: &Connection, entity: &E) -> Result<()> {
+        RecordRepository::insert(conn, &Self::KIND, &Record::of(entity)?)
+    }
+
+    /// Overwrites an existing row, provided it is still at `entity.version()`, and bumps the
+    /// version on both the row and `entity`. Returns `false` if no row has the entity's ID,
+    /// and [`Error::Conflict`] if the row has been written since `entity` was read.
+    pub fn update(conn: &Connection, entity: &mut E) -> Result<bool> {
+        let mut record = Record::of(entity)?;
+        let updated = RecordRepository::update(conn, &Self::KIND, &mut record)?;
+        entity.set_version(record.version);
+        Ok(updated)
+    }
+
+    /// Tombstones a row. Returns `false` if it did not exist or was already deleted.
+    pub fn delete(conn: &Connection, id: &str) -> Result<bool> {
+        RecordRepository::delete(conn, &Self::KIND, id)
+    }
+
+    /// Clears the tombstone of a deleted row. Returns `false` if there is no deleted row with that ID.
+    pub fn restore(conn: &Connection, id: &str) -> Result<bool> {
+        RecordRepository::restore(conn, &Self::KIND, id)
+    }
+
+    /// Permanently removes a deleted row. Live rows are never purged.
+    pub fn purge(conn: &Connection, id: &str) -> Result<bool> {
+        RecordRepository::purge(conn, &Self::KIND, id)
+    }
+
+    /// Deleted rows, oldest deletion first.
+    pub fn list_deleted(conn: &Connection) -> Result<Vec<Tombstone>> {
+        RecordRepository::list_deleted(conn, &Self::KIND)?
+            .into_iter()
+            .map(|(document, deleted_at)| Tombstone::of::<E>(document, deleted_at))
+            .collect()
+    }
+
+    pub fn get(conn: &Connection, id: &str) -> Result<Option<E>> {
+        RecordRepository::get(conn, &Self::KIND, id)?
+            .map(|d| serde_json::from_value(d).map_err(Into::into))
+            .transpose()
+    }
+
+    pub fn exists(conn: &Connection, id: &str) -> Result<bool> {
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE id = ?1 AND deleted_at IS NULL", E::TABLE);
+        let count: i64 = conn.query_row(&sql, [id], |row| row.get(0))?;
+        Ok(count > 0)
+    }
+
+    pub fn list(conn: &Connection) -> Result<Vec<E>> {
+        Self::list_where(conn, "1 = 1", Vec::new())
+    }
+
+    /// Lists live rows matching a SQL condition over the typed columns, oldest first.
+    pub fn list_where(conn: &Connection, condition: &str, params: Vec<Value>) -> Result<Vec<E>> {
+        let sql = format!(
+            "SELECT data FROM {} WHERE deleted_at IS NULL AND ({}) ORDER BY created_at, rowid",
+            E::TABLE,
+            condition
+        );
+        Self::query(conn, &sql, params)
+    }
+
+    /// Runs a query whose single selected column is the `data` document.
+    /// The caller is responsible for filtering out tombstoned rows.
+    pub fn query(conn: &Connection, sql: &str, params: Vec<Value>) -> Result<Vec<E>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params_from_iter(params), |row| row.get::<_, String>(0))?;
+        let mut entities = Vec::new();
+        for data in rows {
+            entities.push(serde_json::from_str(&data?)?);
+        }
+        Ok(entities)
+    }
+
+    /// Case-insensitive substring search over the full JSON document.
+    pub fn search_text(conn: &Connection, query: &str) -> Result<Vec<E>> {
+        Self::list_where(conn, "data LIKE ?1 ESCAPE '\\'", vec![Value::Text(format!("%{}%", escape_like(query)))])
+    }
+
+    pub fn count(conn: &Connection) -> Result<u64> {
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE deleted_at IS NULL", E::TABLE);
+        let count: i64 = conn.query_row(&sql, [], |row| row.get(0))?;
+        Ok(count as u64)
+    }
+}
+
+/// A soft-deleted row, as shown in the trash.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tombstone {
+    pub entity_type: String,
+    pub id: String,
+    pub name: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl Tombstone {
+    /// The trash entry for a deleted `E` document.
+    pub fn of<E: Entity>(document: serde_json::Value, deleted_at: DateTime<Utc>) -> Result<Self> {
+        let entity: E = serde_json::from_value(document)?;
+        Ok(Self {
+            entity_type: E::ENTITY_TYPE.to_string(),
+            id: entity.id().to_string(),
+            name: entity.display_name().to_string(),
+            deleted_at,
+        })
+    }
+}
+
+/// The error for an update of `id` at `expected_version` when `current` has been stored since.
+pub(crate) fn conflict(kind: &EntityKind, id: &str, expected_version: u32, current: serde_json::Value) -> Error {
+    Error::Conflict {
+        entity_type: kind.entity_type.to_string(),
+        id: id.to_string(),
+        expected_version,
+        current_version: stored_version(&current),
+        current: Box::new(current),
+    }
+}
+
+/// Version recorded in a stored document; documents written before versioning count as version 1.
+pub(crate) fn stored_version(document: &serde_json::Value) -> u32 {
+    document
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .map_or_else(crate::core::models::initial_version, |v| v as u32)
+}
+
+/// Text column value.
+pub(crate) fn text(value: impl ToString) -> Value {
+    Value::Text(value.to_string())
+}
+
+/// `value` with the `LIKE` wildcards escaped, for patterns that say `ESCAPE '\'`.
+pub(crate) fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Nullable text column value.
+pub(crate) fn opt_text(value: Option<impl ToString>) -> Value {
+    value.map_or(Value::Null, |v| Value::Text(v.to_string()))
+}
+
+/// Nullable integer column value.
+pub(crate) fn opt_int(value: Option<impl Into<i64>>) -> Value {
+    value.map_or(Value::Null, |v| Value::Integer(v.into()))
+}
+
+/// Timestamp column value, stored as RFC 3339 so lexical order matches time order.
+pub(crate) fn timestamp(value: &DateTime<Utc>) -> Value {
+    Value::Text(crate::utils::format_datetime(*value))
+}
+
+/// Nullable timestamp column value.
+pub(crate) fn opt_timestamp(value: Option<&DateTime<Utc>>) -> Value {
+    value.map_or(Value::Null, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Sprint, Task, TaskStatus, TaskType};
+    use crate::core::schema;
+
+    fn migrated_connection() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        schema::migrate_up(&mut conn, None).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_task_round_trip() -> Result<()> {
+        let conn = migrated_connection();
+        let mut task = Task::new("TASK-001".to_string(), "Write docs".to_string(), TaskType::Task);
+        task.labels = vec!["docs".to_string()];
+        TaskRepository::insert(&conn, &task)?;
+
+        let loaded = TaskRepository::get(&conn, "TASK-001")?.expect("task should exist");
+        assert_eq!(loaded.title, "Write docs");
+        assert_eq!(loaded.labels, vec!["docs".to_string()]);
+
+        task.set_status(TaskStatus::Done, Some("alice".to_string()));
+        assert!(TaskRepository::update(&conn, &mut task)?);
+        let status: String = conn.query_row("SELECT status FROM tasks WHERE id = 'TASK-001'", [], |row| row.get(0))?;
+        assert_eq!(status, "Done");
+
+        assert_eq!(TaskRepository::search_text(&conn, "write")?.len(), 1);
+        assert!(TaskRepository::delete(&conn, "TASK-001")?);
+        assert!(TaskRepository::get(&conn, "TASK-001")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_writes_are_recorded_in_history() -> Result<()> {
+        let conn = migrated_connection();
+        let mut task = Task::new("TASK-003".to_string(), "Audit".to_string(), TaskType::Task);
+        task.created_by = Some("alice".to_string());
+        TaskRepository::insert(&conn, &task)?;
+
+        task.set_status(TaskStatus::InProgress, Some("bob".to_string()));
+        task.assignee = Some("bob".to_string());
+        TaskRepository::update(&conn, &mut task)?;
+        // An update that changes nothing but the timestamp is not recorded
+        task.updated_at = Utc::now();
+        TaskRepository::update(&conn, &mut task)?;
+        TaskRepository::delete(&conn, "TASK-003")?;
+
+        let history = HistoryRepository::list_for(&conn, "task", "TASK-003")?;
+        let versions: Vec<u32> = history.iter().map(|e| e.version).collect();
+        assert_eq!(versions, vec![1, 2, 2, 3]);
+        assert_eq!(history[0].changed_by.as_deref(), Some("alice"));
+
+        let fields: Vec<&str> = history[1..3].iter().filter_map(|e| e.field.as_deref()).collect();
+        assert_eq!(fields, vec!["assignee", "status"]);
+        assert_eq!(history[2].old_value, Some(serde_json::json!("Todo")));
+        assert_eq!(history[2].new_value, Some(serde_json::json!("InProgress")));
+        assert_eq!(history[2].changed_by.as_deref(), Some("bob"));
+        assert_eq!(history[3].change_type, crate::core::models::ChangeType::Deleted);
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_delete_restore_and_purge() -> Result<()> {
+        let conn = migrated_connection();
+        let base = Task::new("TASK-010".to_string(), "Base".to_string(), TaskType::Task);
+        let mut dependent = Task::new("TASK-011".to_string(), "Dependent".to_string(), TaskType::Task);
+        dependent.dependencies = vec!["TASK-010".to_string()];
+        TaskRepository::insert(&conn, &base)?;
+        TaskRepository::insert(&conn, &dependent)?;
+
+        assert!(TaskRepository::delete(&conn, "TASK-010")?);
+        assert!(!TaskRepository::delete(&conn, "TASK-010")?);
+        assert!(TaskRepository::get(&conn, "TASK-010")?.is_none());
+        assert_eq!(TaskRepository::list(&conn)?.len(), 1);
+        assert_eq!(TaskRepository::count(&conn)?, 1);
+        assert_eq!(
+            TrashRepository::references_to(&conn, "TASK-010")?,
+            vec![Reference { entity_type: "task".to_string(), id: "TASK-011".to_string() }]
+        );
+
+        let trash = TrashRepository::list(&conn, None)?;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].name, "Base");
+
+        // Live rows can never be purged
+        assert!(!TaskRepository::purge(&conn, "TASK-011")?);
+
+        assert_eq!(TrashRepository::restore(&conn, "TASK-010")?, Some("task"));
+        assert!(TaskRepository::get(&conn, "TASK-010")?.is_some());
+
+        TaskRepository::delete(&conn, "TASK-010")?;
+        assert!(TrashRepository::purge_deleted_before(&conn, Utc::now() - chrono::Duration::days(1))?.is_empty());
+        let purged = TrashRepository::purge_deleted_before(&conn, Utc::now() + chrono::Duration::seconds(1))?;
+        assert_eq!(purged.len(), 1);
+        assert!(TrashRepository::list(&conn, None)?.is_empty());
+
+        let last = HistoryRepository::list_for(&conn, "task", "TASK-010")?.pop().unwrap();
+        assert_eq!(last.change_type, crate::core::models::ChangeType::Purged);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stale_update_is_rejected_with_conflict() -> Result<()> {
+        let conn = migrated_connection();
+        TaskRepository::insert(&conn, &Task::new("TASK-020".to_string(), "Shared".to_string(), TaskType::Task))?;
+        let mut mine = TaskRepository::get(&conn, "TASK-020")?.unwrap();
+        let mut theirs = TaskRepository::get(&conn, "TASK-020")?.unwrap();
+
+        theirs.assignee = Some("bob".to_string());
+        assert!(TaskRepository::update(&conn, &mut theirs)?);
+        assert_eq!(theirs.version, 2);
+
+        mine.assignee = Some("alice".to_string());
+        match TaskRepository::update(&conn, &mut mine) {
+            Err(Error::Conflict { expected_version, current_version, current, .. }) => {
+                assert_eq!((expected_version, current_version), (1, 2));
+                assert_eq!(current["assignee"], "bob");
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        assert_eq!(mine.version, 1);
+
+        let stored = TaskRepository::get(&conn, "TASK-020")?.unwrap();
+        assert_eq!(stored.assignee.as_deref(), Some("bob"));
+        let version: u32 = conn.query_row("SELECT version FROM tasks WHERE id = 'TASK-020'", [], |row| row.get(0))?;
+        assert_eq!(version, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_tasks_filters_sorts_and_paginates() -> Result<()> {
+        use crate::core::models::{Priority, TaskFilter, TaskSortField};
+        let conn = migrated_connection();
+        for (id, priority, labels, status) in [
+            ("TASK-101", Priority::Low, vec!["api"], TaskStatus::Todo),
+            ("TASK-102", Priority::Critical, vec!["api", "db"], TaskStatus::InProgress),
+            ("TASK-103", Priority::High, vec!["db"], TaskStatus::Done),
+            ("TASK-104", Priority::Medium, vec![], TaskStatus::Todo),
+        ] {
+            let mut task = Task::new(id.to_string(), format!("Work on {}", id), TaskType::Task);
+            task.priority = priority;
+            task.labels = labels.into_iter().map(String::from).collect();
+            task.set_status(status, None);
+            TaskRepository::insert(&conn, &task)?;
+        }
+        let ids = |tasks: Vec<Task>| tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+
+        let filter = TaskFilter { labels_any: vec!["api".to_string(), "db".to_string()], ..Default::default() };
+        assert_eq!(TaskRepository::count_matching(&conn, &filter)?, 3);
+        let filter = TaskFilter { labels_all: vec!["api".to_string(), "db".to_string()], ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-102"]);
+        let filter = TaskFilter { statuses: vec![TaskStatus::Todo], text: Some("104".to_string()), ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-104"]);
+        let filter = TaskFilter { completed: crate::core::models::DateRange { from: Some(Utc::now() - chrono::Duration::hours(1)), to: None }, ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-103"]);
+
+        let filter = TaskFilter { sort_by: TaskSortField::Priority, descending: true, limit: Some(2), offset: 1, ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-103", "TASK-104"]);
+        assert_eq!(TaskRepository::count_matching(&conn, &filter)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_label_filters_ignore_case_and_text_filters_match_literally() -> Result<()> {
+        use crate::core::models::TaskFilter;
+        let conn = migrated_connection();
+        for (id, title, labels) in [
+            ("TASK-1", "Cut 100% of waste", vec!["API", "Backend"]),
+            ("TASK-2", "Cut 1000 lines", vec!["api"]),
+            ("TASK-3", "Rename user_id", vec!["db"]),
+            ("TASK-4", "Rename userXid", vec![]),
+        ] {
+            let mut task = Task::new(id.to_string(), title.to_string(), TaskType::Task);
+            task.labels = labels.into_iter().map(String::from).collect();
+            TaskRepository::insert(&conn, &task)?;
+        }
+        let ids = |tasks: Vec<Task>| tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+
+        let filter = TaskFilter { labels_any: vec!["Api".to_string()], ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-1", "TASK-2"]);
+        let filter = TaskFilter { labels_all: vec!["api".to_string(), "BACKEND".to_string()], ..Default::default() };
+        assert_eq!(ids(TaskRepository::find(&conn, &filter)?), vec!["TASK-1"]);
+
+        for (query, expected) in [("100%", vec!["TASK-1"]), ("user_id", vec!["TASK-3"])] {
+            let filter = TaskFilter { text: Some(query.to_string()), ..Default::default() };
+            assert_eq!(ids(TaskRepository::find(&conn, &filter)?), expected);
+            assert_eq!(ids(TaskRepository::search_text(&conn, query)?), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tasks_created_in_the_same_second_page_in_insertion_order() -> Result<()> {
+        use crate::core::models::TaskFilter;
+        let conn = migrated_connection();
+        let created_at = Utc::now();
+        let inserted = ["TASK-9", "TASK-3", "TASK-7", "TASK-1", "TASK-5"];
+        for id in inserted {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskType::Task);
+            (task.created_at, task.updated_at) = (created_at, created_at);
+            TaskRepository::insert(&conn, &task)?;
+        }
+
+        for descending in [false, true] {
+            let mut pages = Vec::new();
+            for offset in 0..inserted.len() as u32 {
+                let filter = TaskFilter { descending, limit: Some(1), offset, ..Default::default() };
+                pages.extend(TaskRepository::find(&conn, &filter)?.into_iter().map(|t| t.id));
+            }
+            let mut expected: Vec<&str> = inserted.to_vec();
+            if descending {
+                expected.reverse();
+            }
+            assert_eq!(pages, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sprint_tasks_are_loaded_from_task_table() -> Result<()> {
+        let conn = migrated_connection();
+        let sprint = Sprint::new("Sprint 1".to_string(), Utc::now(), Utc::now());
+        SprintRepository::insert(&conn, &sprint)?;
+
+        let mut task = Task::new("TASK-002".to_string(), "Plan".to_string(), TaskType::Task);
+        task.sprint_id = Some(sprint.id.clone());
+        TaskRepository::insert(&conn, &task)?;
+
+        let loaded = SprintRepository::get_with_tasks(&conn, &sprint.id)?.expect("sprint should exist");
+        assert_eq!(loaded.tasks.len(), 1);
+        assert_eq!(loaded.tasks[0].id, "TASK-002");
+        Ok(())
+    }
+
+    #[test]
+    fn test_sprint_task_history_covers_tasks_that_left_the_sprint() -> Result<()> {
+        let conn = migrated_connection();
+        let sprint = Sprint::new("Sprint 1".to_string(), Utc::now(), Utc::now());
+        SprintRepository::insert(&conn, &sprint)?;
+
+        let mut planned = Task::new("TASK-1".to_string(), "Planned".to_string(), TaskType::Task);
+        planned.sprint_id = Some(sprint.id.clone());
+        TaskRepository::insert(&conn, &planned)?;
+        let mut moved_in = Task::new("TASK-2".to_string(), "Moved in".to_string(), TaskType::Task);
+        TaskRepository::insert(&conn, &moved_in)?;
+        moved_in.sprint_id = Some(sprint.id.clone());
+        TaskRepository::update(&conn, &mut moved_in)?;
+        moved_in.sprint_id = None;
+        TaskRepository::update(&conn, &mut moved_in)?;
+        TaskRepository::insert(&conn, &Task::new("TASK-3".to_string(), "Elsewhere".to_string(), TaskType::Task))?;
+
+        let history = HistoryRepository::list_for_sprint_tasks(&conn, &sprint.id)?;
+        let entries: Vec<(&str, Option<&str>)> = history.iter().map(|e| (e.entity_id.as_str(), e.field.as_deref())).collect();
+        assert_eq!(entries, vec![("TASK-1", None), ("TASK-2", None), ("TASK-2", Some("sprint_id")), ("TASK-2", Some("sprint_id"))]);
+        Ok(())
+    }
+}
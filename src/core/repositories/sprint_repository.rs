@@ -0,0 +1,92 @@
+// llmdoc/src/core/repositories/sprint_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::core::errors::Result;
+use crate::core::models::sprint::{Sprint, SprintStatus};
+use crate::core::repositories::{opt_text, text, timestamp, Entity, Repository, TaskRepository};
+
+pub type SprintRepository = Repository<Sprint>;
+
+impl Entity for Sprint {
+    const TABLE: &'static str = "sprints";
+    const ENTITY_TYPE: &'static str = "sprint";
+    const COLUMNS: &'static [&'static str] = &[
+        "key",
+        "name",
+        "status",
+        "start_date",
+        "end_date",
+        "created_at",
+        "updated_at",
+    ];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        &self.name
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            opt_text(self.key.as_ref()),
+            text(&self.name),
+            text(&self.status),
+            timestamp(&self.start_date),
+            timestamp(&self.end_date),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+        ]
+    }
+
+    /// Sprint membership lives in `tasks.sprint_id`; the embedded task list is never persisted.
+    fn to_document(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        value["tasks"] = serde_json::Value::Array(Vec::new());
+        Ok(serde_json::to_string(&value)?)
+    }
+}
+
+impl Repository<Sprint> {
+    /// Loads a sprint with `tasks` populated from the task table.
+    pub fn get_with_tasks(conn: &Connection, id: &str) -> Result<Option<Sprint>> {
+        Self::get(conn, id)?.map(|s| Self::attach_tasks(conn, s)).transpose()
+    }
+
+    /// Lists sprints (optionally by status) with their tasks attached, ordered by start date.
+    pub fn list_with_tasks(conn: &Connection, status: Option<&SprintStatus>) -> Result<Vec<Sprint>> {
+        let sprints = match status {
+            Some(status) => Self::query(
+                conn,
+                "SELECT data FROM sprints WHERE deleted_at IS NULL AND status = ?1 ORDER BY start_date, id",
+                vec![text(status)],
+            )?,
+            None => Self::query(conn, "SELECT data FROM sprints WHERE deleted_at IS NULL ORDER BY start_date, id", Vec::new())?,
+        };
+        sprints.into_iter().map(|s| Self::attach_tasks(conn, s)).collect()
+    }
+
+    fn attach_tasks(conn: &Connection, mut sprint: Sprint) -> Result<Sprint> {
+        sprint.tasks = TaskRepository::list_by_sprint(conn, &sprint.id)?;
+        Ok(sprint)
+    }
+}
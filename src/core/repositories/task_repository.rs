@@ -0,0 +1,214 @@
+// llmdoc/src/core/repositories/task_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::core::errors::Result;
+use crate::core::models::task::Task;
+use crate::core::models::task_filter::{TaskFilter, TaskSortField};
+use crate::core::repositories::{escape_like, opt_int, opt_text, opt_timestamp, text, timestamp, Entity, Repository};
+
+pub type TaskRepository = Repository<Task>;
+
+impl Entity for Task {
+    const TABLE: &'static str = "tasks";
+    const ENTITY_TYPE: &'static str = "task";
+    const COLUMNS: &'static [&'static str] = &[
+        "key",
+        "title",
+        "status",
+        "task_type",
+        "priority",
+        "sprint_id",
+        "parent_id",
+        "assignee",
+        "story_points",
+        "start_date",
+        "due_date",
+        "created_at",
+        "updated_at",
+        "completed_at",
+    ];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        &self.title
+    }
+
+    fn actor(&self) -> Option<&str> {
+        self.updated_by.as_deref().or(self.created_by.as_deref())
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            opt_text(self.key.as_ref()),
+            text(&self.title),
+            text(format!("{:?}", self.status)),
+            text(format!("{:?}", self.task_type)),
+            text(format!("{:?}", self.priority)),
+            opt_text(self.sprint_id.as_ref()),
+            opt_text(self.parent_id.as_ref()),
+            opt_text(self.assignee.as_ref()),
+            opt_int(self.story_points),
+            opt_text(self.start_date),
+            opt_text(self.due_date),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+            opt_timestamp(self.completed_at.as_ref()),
+        ]
+    }
+}
+
+impl Repository<Task> {
+    pub fn list_by_sprint(conn: &Connection, sprint_id: &str) -> Result<Vec<Task>> {
+        Self::list_where(conn, "sprint_id = ?1", vec![text(sprint_id)])
+    }
+
+    /// Live tasks carrying `label`, compared ignoring case.
+    pub fn list_with_label(conn: &Connection, label: &str) -> Result<Vec<Task>> {
+        Self::list_where(
+            conn,
+            "EXISTS (SELECT 1 FROM json_each(tasks.data, '$.labels') WHERE value = ?1 COLLATE NOCASE)",
+            vec![text(label)],
+        )
+    }
+
+    pub fn list_children(conn: &Connection, parent_id: &str) -> Result<Vec<Task>> {
+        Self::list_where(conn, "parent_id = ?1", vec![text(parent_id)])
+    }
+
+    /// Live tasks matching `filter`, sorted and paginated as it asks. Ties are broken by
+    /// insertion order, since timestamps only have seconds precision and IDs are random.
+    pub fn find(conn: &Connection, filter: &TaskFilter) -> Result<Vec<Task>> {
+        let (condition, mut params) = filter_condition(filter);
+        let direction = if filter.descending { "DESC" } else { "ASC" };
+        let mut sql = format!(
+            "SELECT data FROM tasks WHERE deleted_at IS NULL AND {} ORDER BY {} {}, rowid {}",
+            condition,
+            sort_expression(filter.sort_by),
+            direction,
+            direction
+        );
+        if filter.limit.is_some() || filter.offset > 0 {
+            sql.push_str(&format!(" LIMIT ?{} OFFSET ?{}", params.len() + 1, params.len() + 2));
+            params.push(Value::Integer(filter.limit.map_or(-1, i64::from)));
+            params.push(Value::Integer(filter.offset.into()));
+        }
+        Self::query(conn, &sql, params)
+    }
+
+    /// Number of live tasks matching `filter`, ignoring its limit and offset.
+    pub fn count_matching(conn: &Connection, filter: &TaskFilter) -> Result<u64> {
+        let (condition, params) = filter_condition(filter);
+        let sql = format!("SELECT COUNT(*) FROM tasks WHERE deleted_at IS NULL AND {}", condition);
+        let count: i64 = conn.query_row(&sql, rusqlite::params_from_iter(params), |row| row.get(0))?;
+        Ok(count as u64)
+    }
+}
+
+/// SQL condition over the typed columns (and the labels in `data`) plus its parameters.
+fn filter_condition(filter: &TaskFilter) -> (String, Vec<Value>) {
+    let mut clauses = Vec::new();
+    let mut params = Vec::new();
+
+    let any_of: [(&str, Vec<String>); 6] = [
+        ("status", filter.statuses.iter().map(|s| format!("{:?}", s)).collect()),
+        ("task_type", filter.task_types.iter().map(|t| format!("{:?}", t)).collect()),
+        ("priority", filter.priorities.iter().map(|p| format!("{:?}", p)).collect()),
+        ("sprint_id", filter.sprint_id.iter().cloned().collect()),
+        ("parent_id", filter.parent_id.iter().cloned().collect()),
+        ("assignee", filter.assignee.iter().cloned().collect()),
+    ];
+    for (column, values) in any_of {
+        if !values.is_empty() {
+            clauses.push(format!("{} IN ({})", column, bind_all(&mut params, &values)));
+        }
+    }
+
+    if !filter.labels_any.is_empty() {
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM json_each(tasks.data, '$.labels') WHERE value COLLATE NOCASE IN ({}))",
+            bind_all(&mut params, &filter.labels_any)
+        ));
+    }
+    for label in &filter.labels_all {
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM json_each(tasks.data, '$.labels') WHERE value = {} COLLATE NOCASE)",
+            bind(&mut params, text(label))
+        ));
+    }
+
+    for (column, range) in [("created_at", &filter.created), ("updated_at", &filter.updated), ("completed_at", &filter.completed)] {
+        if let Some(from) = &range.from {
+            clauses.push(format!("{} >= {}", column, bind(&mut params, timestamp(from))));
+        }
+        if let Some(to) = &range.to {
+            clauses.push(format!("{} <= {}", column, bind(&mut params, timestamp(to))));
+        }
+    }
+
+    if let Some(due_by) = &filter.due_by {
+        clauses.push(format!(
+            "due_date <= {} AND status NOT IN ('Done', 'Cancelled')",
+            bind(&mut params, text(due_by))
+        ));
+    }
+
+    if let Some(query) = &filter.text {
+        let placeholder = bind(&mut params, text(format!("%{}%", escape_like(query))));
+        clauses.push(format!(
+            "(title LIKE {0} ESCAPE '\\' OR json_extract(data, '$.description') LIKE {0} ESCAPE '\\')",
+            placeholder
+        ));
+    }
+
+    if clauses.is_empty() {
+        ("1 = 1".to_string(), params)
+    } else {
+        (clauses.join(" AND "), params)
+    }
+}
+
+/// Appends a parameter and returns its placeholder.
+fn bind(params: &mut Vec<Value>, value: Value) -> String {
+    params.push(value);
+    format!("?{}", params.len())
+}
+
+fn bind_all(params: &mut Vec<Value>, values: &[String]) -> String {
+    values.iter().map(|v| bind(params, text(v))).collect::<Vec<_>>().join(", ")
+}
+
+fn sort_expression(field: TaskSortField) -> &'static str {
+    match field {
+        TaskSortField::Created => "created_at",
+        TaskSortField::Updated => "updated_at",
+        // Open tasks sort after completed ones when ascending
+        TaskSortField::Completed => "completed_at IS NULL, completed_at",
+        TaskSortField::Priority => "CASE priority WHEN 'Low' THEN 0 WHEN 'Medium' THEN 1 WHEN 'High' THEN 2 ELSE 3 END",
+        TaskSortField::Status => "CASE status WHEN 'Todo' THEN 0 WHEN 'InProgress' THEN 1 WHEN 'Blocked' THEN 2 WHEN 'Done' THEN 3 ELSE 4 END",
+        TaskSortField::Title => "title COLLATE NOCASE",
+        TaskSortField::Points => "story_points IS NULL, story_points",
+        TaskSortField::Due => "due_date IS NULL, due_date",
+        TaskSortField::Id => "id",
+    }
+}
@@ -0,0 +1,111 @@
+// llmdoc/src/core/repositories/trash_repository.rs
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::core::errors::Result;
+use crate::core::repositories::{SprintRepository, TaskRepository, Tombstone};
+
+/// Soft-deletable entity types whose tables are written by SQL jobs, in the order they are
+/// searched by ID. The other ones are kept by the [`Store`](crate::core::store::Store) and
+/// come after these; see [`STORED_KINDS`](crate::core::store::STORED_KINDS).
+pub const TABLE_ENTITY_TYPES: &[&str] = &["task", "sprint"];
+
+/// Queries returning the IDs of live entities that point at `?1`.
+const REFERENCE_QUERIES: &[(&str, &str)] = &[
+    (
+        "task",
+        "SELECT t.id FROM tasks t, json_each(t.data, '$.dependencies') d
+         WHERE d.value = ?1 AND t.deleted_at IS NULL",
+    ),
+    ("task", "SELECT id FROM tasks WHERE sprint_id = ?1 AND deleted_at IS NULL"),
+    ("task", "SELECT id FROM tasks WHERE parent_id = ?1 AND deleted_at IS NULL"),
+];
+
+/// A live entity that still points at another one by ID.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reference {
+    pub entity_type: String,
+    pub id: String,
+}
+
+impl std::fmt::Display for Reference {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.entity_type, self.id)
+    }
+}
+
+/// Cross-entity view of the soft-deleted rows of [`TABLE_ENTITY_TYPES`].
+pub struct TrashRepository;
+
+impl TrashRepository {
+    /// Deleted rows of every type (or just `entity_type`), oldest deletion first.
+    pub fn list(conn: &Connection, entity_type: Option<&str>) -> Result<Vec<Tombstone>> {
+        let mut tombstones = Vec::new();
+        for candidate in TABLE_ENTITY_TYPES {
+            if entity_type.is_none_or(|t| t == *candidate) {
+                tombstones.extend(Self::list_typed(conn, candidate)?);
+            }
+        }
+        tombstones.sort_by(|a, b| a.deleted_at.cmp(&b.deleted_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(tombstones)
+    }
+
+    /// Restores the deleted row with this ID. Returns its entity type, or `None` if nothing was restored.
+    pub fn restore(conn: &Connection, id: &str) -> Result<Option<&'static str>> {
+        for entity_type in TABLE_ENTITY_TYPES {
+            let restored = match *entity_type {
+                "task" => TaskRepository::restore(conn, id)?,
+                _ => SprintRepository::restore(conn, id)?,
+            };
+            if restored {
+                return Ok(Some(entity_type));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Permanently removes a deleted row of the given type.
+    pub fn purge(conn: &Connection, entity_type: &str, id: &str) -> Result<bool> {
+        match entity_type {
+            "task" => TaskRepository::purge(conn, id),
+            "sprint" => SprintRepository::purge(conn, id),
+            _ => Ok(false),
+        }
+    }
+
+    /// Permanently removes every row deleted before `cutoff`. Returns what was purged.
+    pub fn purge_deleted_before(conn: &Connection, cutoff: DateTime<Utc>) -> Result<Vec<Tombstone>> {
+        let mut purged = Vec::new();
+        for tombstone in Self::list(conn, None)? {
+            if tombstone.deleted_at < cutoff && Self::purge(conn, &tombstone.entity_type, &tombstone.id)? {
+                purged.push(tombstone);
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Live tasks whose `dependencies`, `sprint_id` or `parent_id` still point at `id`.
+    pub fn references_to(conn: &Connection, id: &str) -> Result<Vec<Reference>> {
+        let mut references = Vec::new();
+        for (entity_type, sql) in REFERENCE_QUERIES {
+            let mut stmt = conn.prepare(sql)?;
+            let ids = stmt.query_map([id], |row| row.get::<_, String>(0))?;
+            for referrer in ids {
+                let reference = Reference { entity_type: entity_type.to_string(), id: referrer? };
+                if reference.id != id && !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+        Ok(references)
+    }
+
+    fn list_typed(conn: &Connection, entity_type: &str) -> Result<Vec<Tombstone>> {
+        match entity_type {
+            "task" => TaskRepository::list_deleted(conn),
+            _ => SprintRepository::list_deleted(conn),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// llmdoc/src/core/repositories/user_story_repository.rs
+
+use rusqlite::types::Value;
+
+use crate::core::models::user_story::UserStory;
+use crate::core::repositories::{opt_int, opt_text, text, timestamp, Entity, Repository};
+
+pub type UserStoryRepository = Repository<UserStory>;
+
+impl Entity for UserStory {
+    const TABLE: &'static str = "user_stories";
+    const ENTITY_TYPE: &'static str = "user_story";
+    const COLUMNS: &'static [&'static str] = &[
+        "key",
+        "title",
+        "priority",
+        "epic_id",
+        "story_points",
+        "created_at",
+        "updated_at",
+    ];
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    fn display_name(&self) -> &str {
+        &self.title
+    }
+
+    fn column_values(&self) -> Vec<Value> {
+        vec![
+            opt_text(self.key.as_ref()),
+            text(&self.title),
+            text(format!("{:?}", self.priority)),
+            opt_text(self.epic_id.as_ref()),
+            opt_int(self.story_points),
+            timestamp(&self.created_at),
+            timestamp(&self.updated_at),
+        ]
+    }
+}
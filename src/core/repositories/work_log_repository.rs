@@ -0,0 +1,86 @@
+// llmdoc/src/core/repositories/work_log_repository.rs
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+use crate::core::errors::Result;
+use crate::core::models::work_log::WorkLogEntry;
+use crate::core::repositories::{opt_text, text, timestamp};
+
+/// Access to the `work_log` table of time entries.
+pub struct WorkLogRepository;
+
+impl WorkLogRepository {
+    /// Stores `entry` and returns its new ID.
+    pub fn insert(conn: &Connection, entry: &WorkLogEntry) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO work_log (task_id, author, started_at, duration_minutes, note, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params_from_iter([
+                text(&entry.task_id),
+                opt_text(entry.author.as_ref()),
+                timestamp(&entry.started_at),
+                Value::Integer(entry.duration_minutes.into()),
+                opt_text(entry.note.as_ref()),
+                timestamp(&entry.created_at),
+            ]),
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Entries for one task, oldest first.
+    pub fn list_for_task(conn: &Connection, task_id: &str) -> Result<Vec<WorkLogEntry>> {
+        Self::query(conn, "task_id = ?1", vec![text(task_id)])
+    }
+
+    /// Every entry, oldest first.
+    pub fn list_all(conn: &Connection) -> Result<Vec<WorkLogEntry>> {
+        Self::query(conn, "1 = 1", Vec::new())
+    }
+
+    /// Minutes logged against one task.
+    pub fn total_for_task(conn: &Connection, task_id: &str) -> Result<u32> {
+        let total: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(duration_minutes), 0) FROM work_log WHERE task_id = ?1",
+            [task_id],
+            |row| row.get(0),
+        )?;
+        Ok(total as u32)
+    }
+
+    fn query(conn: &Connection, condition: &str, params: Vec<Value>) -> Result<Vec<WorkLogEntry>> {
+        let sql = format!(
+            "SELECT id, task_id, author, started_at, duration_minutes, note, created_at
+             FROM work_log WHERE {} ORDER BY started_at, id",
+            condition
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(params), read_row)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row??);
+        }
+        Ok(entries)
+    }
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<Result<WorkLogEntry>> {
+    let started_at: String = row.get(3)?;
+    let created_at: String = row.get(6)?;
+    let id = row.get(0)?;
+    let task_id = row.get(1)?;
+    let author = row.get(2)?;
+    let duration_minutes = row.get(4)?;
+    let note = row.get(5)?;
+    Ok((|| {
+        Ok(WorkLogEntry {
+            id,
+            task_id,
+            author,
+            started_at: crate::utils::parse_datetime(&started_at)?,
+            duration_minutes,
+            note,
+            created_at: crate::utils::parse_datetime(&created_at)?,
+        })
+    })())
+}
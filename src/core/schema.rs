@@ -0,0 +1,796 @@
+// llmdoc/src/core/schema.rs
+
+//! Versioned schema migrations for the llmdocs SQLite database.
+//!
+//! Every change to the database layout is recorded as a numbered
+//! [`SchemaMigration`] with an `up` and a `down` script. Applied versions are
+//! tracked in the `schema_version` table so existing `.llmdocs/llmdocs.db`
+//! files are upgraded in order instead of relying on `CREATE TABLE IF NOT EXISTS`.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::core::errors::{Error, Result};
+
+/// A single, numbered schema change.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaMigration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+/// All known migrations, ordered by version. Versions must be contiguous and start at 1.
+pub const MIGRATIONS: &[SchemaMigration] = &[
+    SchemaMigration {
+        version: 1,
+        name: "initial_schema",
+        up: "CREATE TABLE IF NOT EXISTS adr (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                date TEXT NOT NULL,
+                deciders TEXT,
+                consulted TEXT,
+                informed TEXT,
+                decision TEXT NOT NULL,
+                alternatives TEXT,
+                consequences TEXT
+            );
+            CREATE TABLE IF NOT EXISTS sprints (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                start_date TEXT,
+                end_date TEXT,
+                goal TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS tasks (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                description TEXT,
+                status TEXT NOT NULL,
+                task_type TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                sprint_id TEXT,
+                assignee TEXT,
+                story_points INTEGER,
+                labels TEXT,
+                dependencies TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                created_by TEXT,
+                updated_by TEXT,
+                completed_at TEXT
+            );",
+        down: "DROP TABLE IF EXISTS tasks;
+            DROP TABLE IF EXISTS sprints;
+            DROP TABLE IF EXISTS adr;",
+    },
+    SchemaMigration {
+        version: 2,
+        name: "typed_entity_tables",
+        // Every entity gets indexed typed columns plus the full model as JSON in `data`.
+        // Rows from the version 1 tables are carried over into the new layout.
+        up: "ALTER TABLE tasks RENAME TO tasks_v1;
+            ALTER TABLE sprints RENAME TO sprints_v1;
+            ALTER TABLE adr RENAME TO adr_v1;
+
+            CREATE TABLE tasks (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                task_type TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                sprint_id TEXT,
+                assignee TEXT,
+                story_points INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                completed_at TEXT,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_tasks_status ON tasks(status);
+            CREATE INDEX idx_tasks_task_type ON tasks(task_type);
+            CREATE INDEX idx_tasks_sprint_id ON tasks(sprint_id);
+            CREATE INDEX idx_tasks_assignee ON tasks(assignee);
+            INSERT INTO tasks (id, title, status, task_type, priority, sprint_id, assignee, story_points,
+                               created_at, updated_at, completed_at, data)
+            SELECT id, title, status, task_type, priority, sprint_id, assignee, story_points,
+                   created_at, updated_at, completed_at,
+                   json_object(
+                       'id', id, 'title', title, 'description', description, 'status', status,
+                       'task_type', task_type, 'priority', priority, 'sprint_id', sprint_id,
+                       'assignee', assignee, 'story_points', story_points,
+                       'labels', CASE WHEN json_valid(labels) THEN json(labels) ELSE json_array() END,
+                       'dependencies', CASE WHEN json_valid(dependencies) THEN json(dependencies) ELSE json_array() END,
+                       'created_at', created_at, 'updated_at', updated_at, 'created_by', created_by,
+                       'updated_by', updated_by, 'completed_at', completed_at)
+            FROM tasks_v1;
+            DROP TABLE tasks_v1;
+
+            CREATE TABLE sprints (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                status TEXT NOT NULL,
+                start_date TEXT NOT NULL,
+                end_date TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_sprints_status ON sprints(status);
+            CREATE INDEX idx_sprints_start_date ON sprints(start_date);
+            INSERT INTO sprints (id, name, status, start_date, end_date, created_at, updated_at, data)
+            SELECT id, name, 'Planning', COALESCE(start_date, created_at), COALESCE(end_date, created_at),
+                   created_at, updated_at,
+                   json_object(
+                       'id', id, 'name', name, 'description', NULL,
+                       'start_date', COALESCE(start_date, created_at), 'end_date', COALESCE(end_date, created_at),
+                       'goals', CASE WHEN goal IS NULL THEN json_array() ELSE json_array(goal) END,
+                       'tasks', json_array(), 'status', 'Planning', 'velocity', NULL, 'capacity', NULL,
+                       'created_at', created_at, 'updated_at', updated_at, 'retrospective', NULL)
+            FROM sprints_v1;
+            DROP TABLE sprints_v1;
+
+            CREATE TABLE adrs (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_adrs_status ON adrs(status);
+            INSERT INTO adrs (id, title, status, created_at, updated_at, data)
+            SELECT id, title, status, date, date,
+                   json_object(
+                       'id', id, 'title', title, 'status', status, 'context', '', 'decision', decision,
+                       'consequences', COALESCE(consequences, ''),
+                       'alternatives', CASE WHEN json_valid(alternatives) THEN json(alternatives) ELSE json_array() END,
+                       'related_adrs', json_array(), 'created_at', date, 'updated_at', date,
+                       'created_by', deciders, 'approved_by', NULL, 'approved_at', NULL)
+            FROM adr_v1;
+            DROP TABLE adr_v1;
+
+            CREATE TABLE components (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                component_type TEXT NOT NULL,
+                owner TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_components_type ON components(component_type);
+
+            CREATE TABLE user_stories (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                epic_id TEXT,
+                story_points INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_user_stories_epic_id ON user_stories(epic_id);",
+        down: "DROP TABLE user_stories;
+            DROP TABLE components;
+
+            ALTER TABLE tasks RENAME TO tasks_v2;
+            CREATE TABLE tasks (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                description TEXT,
+                status TEXT NOT NULL,
+                task_type TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                sprint_id TEXT,
+                assignee TEXT,
+                story_points INTEGER,
+                labels TEXT,
+                dependencies TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                created_by TEXT,
+                updated_by TEXT,
+                completed_at TEXT
+            );
+            INSERT INTO tasks
+            SELECT id, title, json_extract(data, '$.description'), status, task_type, priority, sprint_id,
+                   assignee, story_points, json_extract(data, '$.labels'), json_extract(data, '$.dependencies'),
+                   created_at, updated_at, json_extract(data, '$.created_by'), json_extract(data, '$.updated_by'),
+                   completed_at
+            FROM tasks_v2;
+            DROP TABLE tasks_v2;
+
+            ALTER TABLE sprints RENAME TO sprints_v2;
+            CREATE TABLE sprints (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                start_date TEXT,
+                end_date TEXT,
+                goal TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            INSERT INTO sprints
+            SELECT id, name, start_date, end_date, json_extract(data, '$.goals[0]'), created_at, updated_at
+            FROM sprints_v2;
+            DROP TABLE sprints_v2;
+
+            CREATE TABLE adr (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                date TEXT NOT NULL,
+                deciders TEXT,
+                consulted TEXT,
+                informed TEXT,
+                decision TEXT NOT NULL,
+                alternatives TEXT,
+                consequences TEXT
+            );
+            INSERT INTO adr (id, title, status, date, deciders, decision, alternatives, consequences)
+            SELECT id, title, status, created_at, json_extract(data, '$.created_by'),
+                   json_extract(data, '$.decision'), json_extract(data, '$.alternatives'),
+                   json_extract(data, '$.consequences')
+            FROM adrs;
+            DROP TABLE adrs;",
+    },
+    SchemaMigration {
+        version: 3,
+        name: "entity_history",
+        up: "
+            CREATE TABLE entity_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                change_type TEXT NOT NULL,
+                field TEXT,
+                old_value TEXT,
+                new_value TEXT,
+                changed_by TEXT,
+                changed_at TEXT NOT NULL
+            );
+            CREATE INDEX idx_entity_history_entity ON entity_history(entity_type, entity_id, version);
+            CREATE INDEX idx_entity_history_entity_id ON entity_history(entity_id);",
+        down: "
+            DROP TABLE entity_history;",
+    },
+    SchemaMigration {
+        version: 4,
+        name: "soft_delete",
+        up: "
+            ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+            CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at);
+            ALTER TABLE sprints ADD COLUMN deleted_at TEXT;
+            CREATE INDEX idx_sprints_deleted_at ON sprints(deleted_at);
+            ALTER TABLE adrs ADD COLUMN deleted_at TEXT;
+            CREATE INDEX idx_adrs_deleted_at ON adrs(deleted_at);
+            ALTER TABLE components ADD COLUMN deleted_at TEXT;
+            CREATE INDEX idx_components_deleted_at ON components(deleted_at);
+            ALTER TABLE user_stories ADD COLUMN deleted_at TEXT;
+            CREATE INDEX idx_user_stories_deleted_at ON user_stories(deleted_at);",
+        down: "
+            DROP INDEX idx_tasks_deleted_at;
+            ALTER TABLE tasks DROP COLUMN deleted_at;
+            DROP INDEX idx_sprints_deleted_at;
+            ALTER TABLE sprints DROP COLUMN deleted_at;
+            DROP INDEX idx_adrs_deleted_at;
+            ALTER TABLE adrs DROP COLUMN deleted_at;
+            DROP INDEX idx_components_deleted_at;
+            ALTER TABLE components DROP COLUMN deleted_at;
+            DROP INDEX idx_user_stories_deleted_at;
+            ALTER TABLE user_stories DROP COLUMN deleted_at;",
+    },
+    SchemaMigration {
+        version: 5,
+        name: "entity_version",
+        up: "
+            ALTER TABLE tasks ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            UPDATE tasks SET data = json_set(data, '$.version', 1);
+            ALTER TABLE sprints ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            UPDATE sprints SET data = json_set(data, '$.version', 1);
+            ALTER TABLE adrs ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            UPDATE adrs SET data = json_set(data, '$.version', 1);
+            ALTER TABLE components ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            UPDATE components SET data = json_set(data, '$.version', 1);
+            ALTER TABLE user_stories ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            UPDATE user_stories SET data = json_set(data, '$.version', 1);",
+        down: "
+            UPDATE tasks SET data = json_remove(data, '$.version');
+            ALTER TABLE tasks DROP COLUMN version;
+            UPDATE sprints SET data = json_remove(data, '$.version');
+            ALTER TABLE sprints DROP COLUMN version;
+            UPDATE adrs SET data = json_remove(data, '$.version');
+            ALTER TABLE adrs DROP COLUMN version;
+            UPDATE components SET data = json_remove(data, '$.version');
+            ALTER TABLE components DROP COLUMN version;
+            UPDATE user_stories SET data = json_remove(data, '$.version');
+            ALTER TABLE user_stories DROP COLUMN version;",
+    },
+    SchemaMigration {
+        version: 6,
+        name: "task_hierarchy",
+        up: "
+            ALTER TABLE tasks ADD COLUMN parent_id TEXT;
+            UPDATE tasks SET parent_id = json_extract(data, '$.parent_id');
+            CREATE INDEX idx_tasks_parent_id ON tasks(parent_id);",
+        down: "
+            DROP INDEX idx_tasks_parent_id;
+            UPDATE tasks SET data = json_remove(data, '$.parent_id');
+            ALTER TABLE tasks DROP COLUMN parent_id;",
+    },
+    SchemaMigration {
+        version: 7,
+        name: "work_log",
+        up: "
+            CREATE TABLE work_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                author TEXT,
+                started_at TEXT NOT NULL,
+                duration_minutes INTEGER NOT NULL,
+                note TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX idx_work_log_task_id ON work_log(task_id);
+            CREATE INDEX idx_work_log_author ON work_log(author);",
+        down: "
+            DROP TABLE work_log;",
+    },
+    SchemaMigration {
+        version: 8,
+        name: "comments",
+        up: "
+            CREATE TABLE comments (
+                id TEXT PRIMARY KEY,
+                entity_id TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                author TEXT,
+                reply_to TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                deleted_at TEXT,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX idx_comments_entity_id ON comments(entity_id);
+            CREATE INDEX idx_comments_deleted_at ON comments(deleted_at);
+
+            -- Full-text index over live comment bodies, kept in step by triggers
+            CREATE VIRTUAL TABLE comments_fts USING fts5(body, comment_id UNINDEXED);
+            CREATE TRIGGER comments_fts_insert AFTER INSERT ON comments WHEN new.deleted_at IS NULL BEGIN
+                INSERT INTO comments_fts (body, comment_id) VALUES (json_extract(new.data, '$.body'), new.id);
+            END;
+            CREATE TRIGGER comments_fts_update AFTER UPDATE ON comments BEGIN
+                DELETE FROM comments_fts WHERE comment_id = old.id;
+                INSERT INTO comments_fts (body, comment_id)
+                SELECT json_extract(new.data, '$.body'), new.id WHERE new.deleted_at IS NULL;
+            END;
+            CREATE TRIGGER comments_fts_delete AFTER DELETE ON comments BEGIN
+                DELETE FROM comments_fts WHERE comment_id = old.id;
+            END;",
+        down: "
+            DROP TRIGGER comments_fts_delete;
+            DROP TRIGGER comments_fts_update;
+            DROP TRIGGER comments_fts_insert;
+            DROP TABLE comments_fts;
+            DROP TABLE comments;",
+    },
+    SchemaMigration {
+        version: 9,
+        name: "sequential_keys",
+        up: "
+            CREATE TABLE key_sequences (
+                entity_type TEXT PRIMARY KEY,
+                next_value INTEGER NOT NULL
+            );
+
+            -- Existing tasks are numbered by age, after the highest TASK-<n> already used as an ID
+            ALTER TABLE tasks ADD COLUMN key TEXT;
+            UPDATE tasks SET key = numbered.key, data = json_set(data, '$.key', numbered.key)
+            FROM (
+                SELECT id, 'TASK-' || (ROW_NUMBER() OVER (ORDER BY created_at, rowid) + (
+                    SELECT COALESCE(MAX(CAST(substr(id, 6) AS INTEGER)), 0) FROM tasks
+                    WHERE id GLOB 'TASK-[0-9]*' AND substr(id, 6) NOT GLOB '*[^0-9]*'
+                )) AS key
+                FROM tasks
+            ) AS numbered
+            WHERE tasks.id = numbered.id;
+            CREATE UNIQUE INDEX idx_tasks_key ON tasks(key COLLATE NOCASE);
+            INSERT INTO key_sequences (entity_type, next_value)
+            SELECT 'task', COALESCE(MAX(CAST(substr(key, 6) AS INTEGER)), 0) + 1 FROM tasks;
+
+            -- Existing sprints are numbered by age, after the highest SPRINT-<n> already used as an ID
+            ALTER TABLE sprints ADD COLUMN key TEXT;
+            UPDATE sprints SET key = numbered.key, data = json_set(data, '$.key', numbered.key)
+            FROM (
+                SELECT id, 'SPRINT-' || (ROW_NUMBER() OVER (ORDER BY created_at, rowid) + (
+                    SELECT COALESCE(MAX(CAST(substr(id, 8) AS INTEGER)), 0) FROM sprints
+                    WHERE id GLOB 'SPRINT-[0-9]*' AND substr(id, 8) NOT GLOB '*[^0-9]*'
+                )) AS key
+                FROM sprints
+            ) AS numbered
+            WHERE sprints.id = numbered.id;
+            CREATE UNIQUE INDEX idx_sprints_key ON sprints(key COLLATE NOCASE);
+            INSERT INTO key_sequences (entity_type, next_value)
+            SELECT 'sprint', COALESCE(MAX(CAST(substr(key, 8) AS INTEGER)), 0) + 1 FROM sprints;
+
+            -- Existing adrs are numbered by age, after the highest ADR-<n> already used as an ID
+            ALTER TABLE adrs ADD COLUMN key TEXT;
+            UPDATE adrs SET key = numbered.key, data = json_set(data, '$.key', numbered.key)
+            FROM (
+                SELECT id, 'ADR-' || (ROW_NUMBER() OVER (ORDER BY created_at, rowid) + (
+                    SELECT COALESCE(MAX(CAST(substr(id, 5) AS INTEGER)), 0) FROM adrs
+                    WHERE id GLOB 'ADR-[0-9]*' AND substr(id, 5) NOT GLOB '*[^0-9]*'
+                )) AS key
+                FROM adrs
+            ) AS numbered
+            WHERE adrs.id = numbered.id;
+            CREATE UNIQUE INDEX idx_adrs_key ON adrs(key COLLATE NOCASE);
+            INSERT INTO key_sequences (entity_type, next_value)
+            SELECT 'adr', COALESCE(MAX(CAST(substr(key, 5) AS INTEGER)), 0) + 1 FROM adrs;
+
+            -- Existing components are numbered by age, after the highest COMP-<n> already used as an ID
+            ALTER TABLE components ADD COLUMN key TEXT;
+            UPDATE components SET key = numbered.key, data = json_set(data, '$.key', numbered.key)
+            FROM (
+                SELECT id, 'COMP-' || (ROW_NUMBER() OVER (ORDER BY created_at, rowid) + (
+                    SELECT COALESCE(MAX(CAST(substr(id, 6) AS INTEGER)), 0) FROM components
+                    WHERE id GLOB 'COMP-[0-9]*' AND substr(id, 6) NOT GLOB '*[^0-9]*'
+                )) AS key
+                FROM components
+            ) AS numbered
+            WHERE components.id = numbered.id;
+            CREATE UNIQUE INDEX idx_components_key ON components(key COLLATE NOCASE);
+            INSERT INTO key_sequences (entity_type, next_value)
+            SELECT 'component', COALESCE(MAX(CAST(substr(key, 6) AS INTEGER)), 0) + 1 FROM components;
+
+            -- Existing user_stories are numbered by age, after the highest US-<n> already used as an ID
+            ALTER TABLE user_stories ADD COLUMN key TEXT;
+            UPDATE user_stories SET key = numbered.key, data = json_set(data, '$.key', numbered.key)
+            FROM (
+                SELECT id, 'US-' || (ROW_NUMBER() OVER (ORDER BY created_at, rowid) + (
+                    SELECT COALESCE(MAX(CAST(substr(id, 4) AS INTEGER)), 0) FROM user_stories
+                    WHERE id GLOB 'US-[0-9]*' AND substr(id, 4) NOT GLOB '*[^0-9]*'
+                )) AS key
+                FROM user_stories
+            ) AS numbered
+            WHERE user_stories.id = numbered.id;
+            CREATE UNIQUE INDEX idx_user_stories_key ON user_stories(key COLLATE NOCASE);
+            INSERT INTO key_sequences (entity_type, next_value)
+            SELECT 'user_story', COALESCE(MAX(CAST(substr(key, 4) AS INTEGER)), 0) + 1 FROM user_stories;",
+        down: "
+            DROP INDEX idx_user_stories_key;
+            UPDATE user_stories SET data = json_remove(data, '$.key');
+            ALTER TABLE user_stories DROP COLUMN key;
+            DROP INDEX idx_components_key;
+            UPDATE components SET data = json_remove(data, '$.key');
+            ALTER TABLE components DROP COLUMN key;
+            DROP INDEX idx_adrs_key;
+            UPDATE adrs SET data = json_remove(data, '$.key');
+            ALTER TABLE adrs DROP COLUMN key;
+            DROP INDEX idx_sprints_key;
+            UPDATE sprints SET data = json_remove(data, '$.key');
+            ALTER TABLE sprints DROP COLUMN key;
+            DROP INDEX idx_tasks_key;
+            UPDATE tasks SET data = json_remove(data, '$.key');
+            ALTER TABLE tasks DROP COLUMN key;
+            DROP TABLE key_sequences;",
+    },
+    SchemaMigration {
+        version: 10,
+        name: "task_dates",
+        up: "
+            ALTER TABLE tasks ADD COLUMN start_date TEXT;
+            ALTER TABLE tasks ADD COLUMN due_date TEXT;
+            UPDATE tasks SET start_date = json_extract(data, '$.start_date'), due_date = json_extract(data, '$.due_date');
+            CREATE INDEX idx_tasks_due_date ON tasks(due_date);",
+        down: "
+            DROP INDEX idx_tasks_due_date;
+            UPDATE tasks SET data = json_remove(data, '$.start_date', '$.due_date');
+            ALTER TABLE tasks DROP COLUMN due_date;
+            ALTER TABLE tasks DROP COLUMN start_date;",
+    },
+    SchemaMigration {
+        version: 11,
+        name: "labels",
+        up: "
+            CREATE TABLE labels (
+                name TEXT PRIMARY KEY COLLATE NOCASE,
+                description TEXT,
+                color TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+        down: "
+            DROP TABLE labels;",
+    },
+    SchemaMigration {
+        version: 12,
+        name: "sprint_capacity",
+        up: "
+            CREATE TABLE sprint_capacity (
+                sprint_id TEXT NOT NULL,
+                member TEXT NOT NULL COLLATE NOCASE,
+                days_available REAL NOT NULL,
+                focus_factor REAL NOT NULL,
+                leave_days REAL NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (sprint_id, member)
+            );",
+        down: "
+            DROP TABLE sprint_capacity;",
+    },
+];
+
+/// Applied/pending state of a single migration, as reported by `db migrate status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl MigrationStatus {
+    pub fn is_applied(&self) -> bool {
+        self.applied_at.is_some()
+    }
+}
+
+/// Returns the highest schema version known to this build.
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Creates the `schema_version` bookkeeping table if it does not exist yet.
+pub fn ensure_version_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Returns the highest applied schema version, or 0 for a fresh database.
+pub fn current_version(conn: &Connection) -> Result<u32> {
+    ensure_version_table(conn)?;
+    let version: Option<u32> = conn
+        .query_row("SELECT MAX(version) FROM schema_version", [], |row| row.get(0))
+        .optional()?
+        .flatten();
+    Ok(version.unwrap_or(0))
+}
+
+/// Lists every known migration together with the time it was applied, if at all.
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    ensure_version_table(conn)?;
+    let mut stmt = conn.prepare("SELECT applied_at FROM schema_version WHERE version = ?1")?;
+    let mut statuses = Vec::with_capacity(MIGRATIONS.len());
+    for migration in MIGRATIONS {
+        let applied_at: Option<String> = stmt
+            .query_row([migration.version], |row| row.get(0))
+            .optional()?;
+        let applied_at = applied_at
+            .map(|s| crate::utils::parse_datetime(&s))
+            .transpose()?;
+        statuses.push(MigrationStatus {
+            version: migration.version,
+            name: migration.name.to_string(),
+            applied_at,
+        });
+    }
+    Ok(statuses)
+}
+
+/// Returns the migrations that `migrate_up` would apply for the given target.
+pub fn pending(conn: &Connection, target: Option<u32>) -> Result<Vec<SchemaMigration>> {
+    let current = current_version(conn)?;
+    let target = target.unwrap_or_else(latest_version);
+    check_known_version(current)?;
+    check_known_version(target)?;
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current && m.version <= target)
+        .copied()
+        .collect())
+}
+
+/// Applies pending migrations up to `target` (or the latest version), each in its own transaction.
+/// Returns the versions that were applied.
+pub fn migrate_up(conn: &mut Connection, target: Option<u32>) -> Result<Vec<u32>> {
+    let to_apply = pending(conn, target)?;
+    let mut applied = Vec::with_capacity(to_apply.len());
+    for migration in to_apply {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.up).map_err(|e| {
+            Error::DatabaseOperation(format!("Migration {} ({}) failed: {}", migration.version, migration.name, e))
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.name, crate::utils::format_datetime(Utc::now())],
+        )?;
+        tx.commit()?;
+        tracing::info!("Applied schema migration {} ({})", migration.version, migration.name);
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+/// Rolls back applied migrations, newest first, until the schema is at `target`.
+/// Returns the versions that were reverted.
+pub fn migrate_down(conn: &mut Connection, target: u32) -> Result<Vec<u32>> {
+    let current = current_version(conn)?;
+    check_known_version(current)?;
+    if target > current {
+        return Err(Error::InvalidInput(format!(
+            "Cannot migrate down to version {}: current schema version is {}",
+            target, current
+        )));
+    }
+    let mut reverted = Vec::new();
+    for migration in MIGRATIONS.iter().rev().filter(|m| m.version > target && m.version <= current) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.down).map_err(|e| {
+            Error::DatabaseOperation(format!("Rollback of migration {} ({}) failed: {}", migration.version, migration.name, e))
+        })?;
+        tx.execute("DELETE FROM schema_version WHERE version = ?1", [migration.version])?;
+        tx.commit()?;
+        tracing::info!("Reverted schema migration {} ({})", migration.version, migration.name);
+        reverted.push(migration.version);
+    }
+    Ok(reverted)
+}
+
+fn check_known_version(version: u32) -> Result<()> {
+    if version > latest_version() {
+        return Err(Error::DatabaseOperation(format!(
+            "Database schema version {} is newer than the latest version supported by this build ({})",
+            version,
+            latest_version()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn test_migrations_are_contiguous() {
+        for (idx, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, idx + 1, "migration {} is out of order", migration.name);
+        }
+    }
+
+    #[test]
+    fn test_migrate_up_and_down() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        assert_eq!(current_version(&conn)?, 0);
+
+        let applied = migrate_up(&mut conn, None)?;
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(current_version(&conn)?, latest_version());
+        assert!(table_exists(&conn, "tasks"));
+        assert!(status(&conn)?.iter().all(MigrationStatus::is_applied));
+
+        // Re-running is a no-op.
+        assert!(migrate_up(&mut conn, None)?.is_empty());
+
+        let reverted = migrate_down(&mut conn, 0)?;
+        assert_eq!(reverted.len(), MIGRATIONS.len());
+        assert_eq!(current_version(&conn)?, 0);
+        assert!(!table_exists(&conn, "tasks"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        assert!(migrate_up(&mut conn, Some(latest_version() + 1)).is_err());
+        migrate_up(&mut conn, None)?;
+        assert!(migrate_down(&mut conn, latest_version() + 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrades_a_populated_baseline_database() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        migrate_up(&mut conn, Some(1))?;
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, status, task_type, priority, labels, dependencies, created_at, updated_at, created_by)
+             VALUES ('legacy-b', 'Second', 'Todo', 'Feature', 'High', '[\"ci\"]', '[\"legacy-a\"]',
+                     '2024-01-02T00:00:00Z', '2024-01-02T00:00:00Z', 'alice'),
+                    ('legacy-a', 'First', 'Done', 'Bug', 'Low', 'not json', NULL,
+                     '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z', NULL),
+                    ('TASK-7', 'Imported', 'Todo', 'Chore', 'Medium', NULL, NULL,
+                     '2024-01-03T00:00:00Z', '2024-01-03T00:00:00Z', NULL);
+             INSERT INTO sprints (id, name, start_date, end_date, goal, created_at, updated_at)
+             VALUES ('sprint-1', 'Sprint 1', '2024-01-01', '2024-01-14', 'Ship it',
+                     '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+             INSERT INTO adr (id, title, status, date, deciders, decision, alternatives, consequences)
+             VALUES ('adr-1', 'Use SQLite', 'Accepted', '2024-01-05T00:00:00Z', 'bob', 'Embed it',
+                     '[\"Postgres\"]', NULL);",
+        )?;
+
+        migrate_up(&mut conn, None)?;
+        assert_eq!(current_version(&conn)?, latest_version());
+
+        let tasks = conn
+            .prepare("SELECT id, key, version, json_extract(data, '$.key'), json_extract(data, '$.labels'), json_extract(data, '$.dependencies'), json_extract(data, '$.created_by') FROM tasks ORDER BY key")?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let own = |s: &str| s.to_string();
+        assert_eq!(
+            tasks,
+            vec![
+                // Keys continue after the highest TASK-<n> ID and follow creation order
+                (own("TASK-7"), own("TASK-10"), 1, own("TASK-10"), own("[]"), own("[]"), None),
+                (own("legacy-a"), own("TASK-8"), 1, own("TASK-8"), own("[]"), own("[]"), None),
+                (own("legacy-b"), own("TASK-9"), 1, own("TASK-9"), own("[\"ci\"]"), own("[\"legacy-a\"]"), Some(own("alice"))),
+            ]
+        );
+
+        let sprint: (String, String, String, String) = conn.query_row(
+            "SELECT key, status, json_extract(data, '$.goals'), json_extract(data, '$.end_date') FROM sprints WHERE id = 'sprint-1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        assert_eq!(sprint, (own("SPRINT-1"), own("Planning"), own("[\"Ship it\"]"), own("2024-01-14")));
+
+        let adr: (String, String, String, String, String) = conn.query_row(
+            "SELECT key, created_at, json_extract(data, '$.created_by'), json_extract(data, '$.decision'), json_extract(data, '$.alternatives') FROM adrs WHERE id = 'adr-1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+        assert_eq!(adr, (own("ADR-1"), own("2024-01-05T00:00:00Z"), own("bob"), own("Embed it"), own("[\"Postgres\"]")));
+        assert!(!table_exists(&conn, "adr"));
+
+        let next_keys = conn
+            .prepare("SELECT entity_type, next_value FROM key_sequences ORDER BY entity_type")?
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        assert_eq!(
+            next_keys,
+            vec![
+                (own("adr"), 2),
+                (own("component"), 1),
+                (own("sprint"), 2),
+                (own("task"), 11),
+                (own("user_story"), 1),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,590 @@
+// llmdoc/src/core/store.rs
+
+//! Storage backends behind [`DbConnection`](crate::core::database::DbConnection).
+//!
+//! A [`Store`] persists entities through two kinds of operation:
+//!
+//! * entity operations (get, list, insert, update, soft-delete, restore, purge and key
+//!   lookup) on the documents of [`STORED_KINDS`], which services call through the typed
+//!   helpers on `DbConnection`, and
+//! * jobs, which run the repositories' SQL for everything that is queried relationally:
+//!   tasks, sprints, comments, labels, capacity and the change log.
+//!
+//! Two backends exist:
+//!
+//! * [`SqliteStore`], a pooled database file (the default). Its entity operations are the
+//!   repositories' writes, so stored entities live in their tables like everything else.
+//! * [`MemoryStore`], which keeps the stored entities in its own collections and runs jobs
+//!   against a private in-memory SQLite database. Nothing touches the filesystem and
+//!   everything is lost with the process.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use deadpool_sqlite::{Pool, Runtime};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::app_config::{Config, StorageBackend};
+use crate::core::errors::{Error, Result};
+use crate::core::models::{Adr, Component, UserStory};
+use crate::core::repositories::key_repository::{key_taken, uuid_starts_with, Lookup, MIN_PREFIX_LEN};
+use crate::core::repositories::{conflict, EntityKind, HistoryRepository, KeyRepository, Record, RecordRepository};
+
+/// Work run against a store's connection. Results are passed back by the caller's closure.
+pub type StoreJob = Box<dyn FnOnce(&mut Connection) + Send + 'static>;
+
+/// Entity types persisted through a store's entity operations rather than by jobs, in the
+/// order they are searched by ID (after the tables of
+/// [`TABLE_ENTITY_TYPES`](crate::core::repositories::trash_repository::TABLE_ENTITY_TYPES)).
+pub const STORED_KINDS: [EntityKind; 3] = [EntityKind::of::<Adr>(), EntityKind::of::<Component>(), EntityKind::of::<UserStory>()];
+
+/// The document field through which each of [`STORED_KINDS`] points at other entities by ID.
+pub const STORED_REFERENCES: [(EntityKind, &str); 3] = [
+    (EntityKind::of::<UserStory>(), "tasks"),
+    (EntityKind::of::<Adr>(), "related_adrs"),
+    (EntityKind::of::<Component>(), "dependencies"),
+];
+
+/// The one of [`STORED_KINDS`] called `entity_type`, if any.
+pub fn stored_kind(entity_type: &str) -> Option<EntityKind> {
+    STORED_KINDS.into_iter().find(|kind| kind.entity_type == entity_type)
+}
+
+/// Persists entities: the documents of [`STORED_KINDS`] through the entity operations, and
+/// everything else through SQL jobs. Every entity write is recorded in the change log.
+#[async_trait]
+pub trait Store: Send + Sync {
+    /// Runs `job` with exclusive use of a connection, off the async executor.
+    async fn run(&self, job: StoreJob) -> Result<()>;
+
+    /// On-disk location of the data, or `None` if it only lives in memory.
+    fn database_path(&self) -> Option<PathBuf>;
+
+    /// The live `kind` entity with this ID.
+    async fn get(&self, kind: EntityKind, id: &str) -> Result<Option<serde_json::Value>>;
+
+    /// The live `kind` entities, oldest first.
+    async fn list(&self, kind: EntityKind) -> Result<Vec<serde_json::Value>>;
+
+    /// Stores a new entity, giving it the next `key_prefix` key if it has none, and returns it
+    /// as stored. A key it already has must be free.
+    async fn insert(&self, kind: EntityKind, record: Record, key_prefix: &str) -> Result<Record>;
+
+    /// Overwrites the live entity with the record's ID, provided it is still at
+    /// `record.version`, and returns the record at the next version. `None` if there is no
+    /// such entity, and [`Error::Conflict`] if it has been written since `record` was read.
+    async fn update(&self, kind: EntityKind, record: Record) -> Result<Option<Record>>;
+
+    /// Moves a live entity to the trash. Returns `false` if there is no live entity with that ID.
+    async fn delete(&self, kind: EntityKind, id: &str) -> Result<bool>;
+
+    /// Takes an entity out of the trash. Returns `false` if there is no deleted entity with that ID.
+    async fn restore(&self, kind: EntityKind, id: &str) -> Result<bool>;
+
+    /// Permanently removes a deleted entity. Live entities are never purged.
+    async fn purge(&self, kind: EntityKind, id: &str) -> Result<bool>;
+
+    /// Deleted `kind` entities with their deletion time, oldest deletion first.
+    async fn list_deleted(&self, kind: EntityKind) -> Result<Vec<(serde_json::Value, DateTime<Utc>)>>;
+
+    /// IDs of the `kind` entities that `input` matches by `lookup`, in ID order.
+    /// See [`KeyRepository::find_ids`].
+    async fn find_ids(&self, kind: EntityKind, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<String>>;
+}
+
+/// Runs `f` as a job on `store` and hands back what it returns.
+pub async fn run_with<S, F, T>(store: &S, f: F) -> Result<T>
+where
+    S: Store + ?Sized,
+    F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    store
+        .run(Box::new(move |conn| {
+            let _ = sender.send(f(conn));
+        }))
+        .await?;
+    receiver
+        .recv()
+        .map_err(|_| Error::DatabaseOperation("Database job finished without a result".to_string()))?
+}
+
+/// Picks the backend configured by `backend`, or by a `:memory:` database URL.
+pub async fn open(config: &Config) -> Result<Arc<dyn Store>> {
+    if config.backend == StorageBackend::Memory {
+        return Ok(Arc::new(MemoryStore::new()?));
+    }
+    open_url(&config.database_url).await
+}
+
+/// Opens the database file at `database_url`, or a [`MemoryStore`] for an in-memory URL.
+/// An empty URL is rejected rather than silently giving a database that is never saved.
+pub async fn open_url(database_url: &str) -> Result<Arc<dyn Store>> {
+    if database_url.trim().is_empty() {
+        return Err(Error::ConfigError(
+            "database_url is empty; set it to a database file, or to \":memory:\" for data that is not saved".to_string(),
+        ));
+    }
+    if is_memory_url(database_url) {
+        Ok(Arc::new(MemoryStore::new()?))
+    } else {
+        Ok(Arc::new(SqliteStore::open(database_url).await?))
+    }
+}
+
+/// Whether a database URL names an in-memory SQLite database rather than a file.
+pub fn is_memory_url(database_url: &str) -> bool {
+    database_url == ":memory:" || database_url.starts_with("file::memory:")
+}
+
+/// Pooled SQLite database file.
+pub struct SqliteStore {
+    pool: Pool,
+    path: PathBuf,
+}
+
+impl SqliteStore {
+    pub async fn open(database_url: &str) -> Result<Self> {
+        let cfg = deadpool_sqlite::Config::new(database_url.to_string());
+        let pool = cfg
+            .builder(Runtime::Tokio1)
+            .map_err(|e| Error::DatabaseOperation(format!("Failed to build SQLite pool builder: {}", e)))?
+            .build()
+            .map_err(|e| Error::DatabaseOperation(format!("Failed to build deadpool_sqlite pool: {}", e)))?;
+
+        // Test the connection
+        let _conn = pool.get().await
+            .map_err(|e| Error::DatabaseOperation(format!("Failed to get connection from pool during initialization: {}", e)))?;
+        tracing::info!("Database connection pool initialized successfully.");
+
+        Ok(Self { pool, path: PathBuf::from(database_url) })
+    }
+}
+
+#[async_trait]
+impl Store for SqliteStore {
+    async fn run(&self, job: StoreJob) -> Result<()> {
+        let conn = self.pool.get().await
+            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
+        conn.interact(job)
+            .await
+            .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {}", e)))
+    }
+
+    fn database_path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+
+    async fn get(&self, kind: EntityKind, id: &str) -> Result<Option<serde_json::Value>> {
+        let id = id.to_string();
+        run_with(self, move |conn| RecordRepository::get(conn, &kind, &id)).await
+    }
+
+    async fn list(&self, kind: EntityKind) -> Result<Vec<serde_json::Value>> {
+        run_with(self, move |conn| RecordRepository::list(conn, &kind)).await
+    }
+
+    async fn insert(&self, kind: EntityKind, mut record: Record, key_prefix: &str) -> Result<Record> {
+        let key_prefix = key_prefix.to_string();
+        run_with(self, move |conn| {
+            let tx = conn.transaction()?;
+            if let Some(key) = KeyRepository::key_for_new(&tx, &kind, record.key.as_deref(), &key_prefix)? {
+                record.set_key(&kind, key);
+            }
+            RecordRepository::insert(&tx, &kind, &record)?;
+            tx.commit()?;
+            Ok(record)
+        })
+        .await
+    }
+
+    async fn update(&self, kind: EntityKind, mut record: Record) -> Result<Option<Record>> {
+        run_with(self, move |conn| Ok(RecordRepository::update(conn, &kind, &mut record)?.then_some(record))).await
+    }
+
+    async fn delete(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        run_with(self, move |conn| RecordRepository::delete(conn, &kind, &id)).await
+    }
+
+    async fn restore(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        run_with(self, move |conn| RecordRepository::restore(conn, &kind, &id)).await
+    }
+
+    async fn purge(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        run_with(self, move |conn| RecordRepository::purge(conn, &kind, &id)).await
+    }
+
+    async fn list_deleted(&self, kind: EntityKind) -> Result<Vec<(serde_json::Value, DateTime<Utc>)>> {
+        run_with(self, move |conn| RecordRepository::list_deleted(conn, &kind)).await
+    }
+
+    async fn find_ids(&self, kind: EntityKind, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<String>> {
+        let input = input.to_string();
+        run_with(self, move |conn| KeyRepository::find_ids(conn, &kind, lookup, &input, include_deleted)).await
+    }
+}
+
+/// Stored entities in memory, with everything else in a private in-memory SQLite database.
+///
+/// SQLite gives every `:memory:` connection its own database, so unlike [`SqliteStore`] this
+/// keeps exactly one connection for jobs and serializes access to it. The change log lives
+/// there too, so history looks the same as on disk.
+#[derive(Clone)]
+pub struct MemoryStore {
+    conn: Arc<Mutex<Connection>>,
+    entities: Arc<Mutex<MemoryEntities>>,
+}
+
+/// What a [`MemoryStore`] keeps itself: the stored entities of each table in insertion
+/// order, and the next key number of each entity type.
+#[derive(Default)]
+struct MemoryEntities {
+    tables: HashMap<&'static str, Vec<MemoryRow>>,
+    next_keys: HashMap<&'static str, u64>,
+}
+
+struct MemoryRow {
+    record: Record,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl MemoryEntities {
+    fn rows(&mut self, kind: &EntityKind) -> &mut Vec<MemoryRow> {
+        self.tables.entry(kind.table).or_default()
+    }
+
+    fn live(&mut self, kind: &EntityKind, id: &str) -> Option<&mut MemoryRow> {
+        self.rows(kind).iter_mut().find(|row| row.record.id == id && row.deleted_at.is_none())
+    }
+
+    fn deleted(&mut self, kind: &EntityKind, id: &str) -> Option<usize> {
+        self.rows(kind).iter().position(|row| row.record.id == id && row.deleted_at.is_some())
+    }
+
+    /// Same matching as [`KeyRepository::find_ids`].
+    fn find_ids(&mut self, kind: &EntityKind, lookup: Lookup, input: &str, include_deleted: bool) -> Vec<String> {
+        let input = input.trim();
+        let mut ids: Vec<String> = self
+            .rows(kind)
+            .iter()
+            .filter(|row| include_deleted || row.deleted_at.is_none())
+            .map(|row| &row.record)
+            .filter(|record| match lookup {
+                Lookup::Id => record.id == input,
+                Lookup::Key => kind.has_keys() && record.key.as_deref().is_some_and(|key| key.eq_ignore_ascii_case(input)),
+                Lookup::Prefix => input.len() >= MIN_PREFIX_LEN && uuid_starts_with(&record.id, input),
+            })
+            .map(|record| record.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Same numbering as [`KeyRepository::next_key`]: never reused, and skipping any key
+    /// that is already an ID or key of this type.
+    fn next_key(&mut self, kind: &EntityKind, prefix: &str) -> String {
+        let mut next = self.next_keys.get(kind.entity_type).copied().unwrap_or(1);
+        let key = loop {
+            let key = format!("{}-{}", prefix, next);
+            next += 1;
+            let taken = self.rows(kind).iter().any(|row| {
+                row.record.id.eq_ignore_ascii_case(&key)
+                    || row.record.key.as_deref().is_some_and(|k| k.eq_ignore_ascii_case(&key))
+            });
+            if !taken {
+                break key;
+            }
+        };
+        self.next_keys.insert(kind.entity_type, next);
+        key
+    }
+}
+
+impl MemoryStore {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            conn: Arc::new(Mutex::new(Connection::open_in_memory()?)),
+            entities: Arc::new(Mutex::new(MemoryEntities::default())),
+        })
+    }
+
+    /// Runs `f` on the stored entities and the job connection, off the async executor.
+    /// Writes record their history before changing anything, so a failure leaves both as they were.
+    async fn with_entities<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut MemoryEntities, &Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (entities, conn) = (self.entities.clone(), self.conn.clone());
+        tokio::task::spawn_blocking(move || {
+            let mut entities = entities
+                .lock()
+                .map_err(|_| Error::DatabaseOperation("In-memory store lock poisoned".to_string()))?;
+            let conn = conn
+                .lock()
+                .map_err(|_| Error::DatabaseOperation("In-memory database lock poisoned".to_string()))?;
+            f(&mut entities, &conn)
+        })
+        .await
+        .map_err(|e| Error::DatabaseOperation(format!("In-memory store task failed: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl Store for MemoryStore {
+    async fn run(&self, job: StoreJob) -> Result<()> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn
+                .lock()
+                .map_err(|_| Error::DatabaseOperation("In-memory database lock poisoned".to_string()))?;
+            job(&mut conn);
+            Ok(())
+        })
+        .await
+        .map_err(|e| Error::DatabaseOperation(format!("In-memory database task failed: {}", e)))?
+    }
+
+    fn database_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    async fn get(&self, kind: EntityKind, id: &str) -> Result<Option<serde_json::Value>> {
+        let id = id.to_string();
+        self.with_entities(move |entities, _| Ok(entities.live(&kind, &id).map(|row| row.record.document.clone()))).await
+    }
+
+    async fn list(&self, kind: EntityKind) -> Result<Vec<serde_json::Value>> {
+        self.with_entities(move |entities, _| {
+            let mut live: Vec<&Record> = entities
+                .rows(&kind)
+                .iter()
+                .filter(|row| row.deleted_at.is_none())
+                .map(|row| &row.record)
+                .collect();
+            // Oldest first like the tables, which order by `created_at` and then insertion
+            live.sort_by_key(|record| record.column_text(&kind, "created_at"));
+            Ok(live.into_iter().map(|record| record.document.clone()).collect())
+        })
+        .await
+    }
+
+    async fn insert(&self, kind: EntityKind, mut record: Record, key_prefix: &str) -> Result<Record> {
+        let key_prefix = key_prefix.to_string();
+        self.with_entities(move |entities, conn| {
+            if entities.rows(&kind).iter().any(|row| row.record.id == record.id) {
+                return Err(Error::ValidationError(format!("{} {} already exists", kind.entity_type, record.id)));
+            }
+            if kind.has_keys() {
+                match record.key.clone() {
+                    Some(key) => {
+                        if let Some(other) = entities.find_ids(&kind, Lookup::Key, &key, true).first() {
+                            return Err(key_taken(&key, other));
+                        }
+                    }
+                    None => {
+                        let key = entities.next_key(&kind, &key_prefix);
+                        record.set_key(&kind, key);
+                    }
+                }
+            }
+            HistoryRepository::record_created(conn, kind.entity_type, &record.id, &record.document, record.actor.as_deref())?;
+            entities.rows(&kind).push(MemoryRow { record: record.clone(), deleted_at: None });
+            Ok(record)
+        })
+        .await
+    }
+
+    async fn update(&self, kind: EntityKind, mut record: Record) -> Result<Option<Record>> {
+        self.with_entities(move |entities, conn| {
+            let Some(row) = entities.live(&kind, &record.id) else {
+                return Ok(None);
+            };
+            let expected = record.version;
+            if row.record.version != expected {
+                return Err(conflict(&kind, &record.id, expected, row.record.document.clone()));
+            }
+            record.set_version(expected + 1);
+            HistoryRepository::record_updated(
+                conn,
+                kind.entity_type,
+                &record.id,
+                &row.record.document,
+                &record.document,
+                record.actor.as_deref(),
+            )?;
+            row.record = record.clone();
+            Ok(Some(record))
+        })
+        .await
+    }
+
+    async fn delete(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        self.with_entities(move |entities, conn| {
+            let Some(row) = entities.live(&kind, &id) else {
+                return Ok(false);
+            };
+            HistoryRepository::record_deleted(conn, kind.entity_type, &id, &row.record.document, None)?;
+            row.deleted_at = Some(Utc::now());
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn restore(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        self.with_entities(move |entities, conn| {
+            let Some(i) = entities.deleted(&kind, &id) else {
+                return Ok(false);
+            };
+            let row = &mut entities.rows(&kind)[i];
+            HistoryRepository::record_restored(conn, kind.entity_type, &id, Some(&row.record.document), None)?;
+            row.deleted_at = None;
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn purge(&self, kind: EntityKind, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        self.with_entities(move |entities, conn| {
+            let Some(i) = entities.deleted(&kind, &id) else {
+                return Ok(false);
+            };
+            HistoryRepository::record_purged(conn, kind.entity_type, &id)?;
+            entities.rows(&kind).remove(i);
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn list_deleted(&self, kind: EntityKind) -> Result<Vec<(serde_json::Value, DateTime<Utc>)>> {
+        self.with_entities(move |entities, _| {
+            let mut deleted: Vec<(&Record, DateTime<Utc>)> = entities
+                .rows(&kind)
+                .iter()
+                .filter_map(|row| row.deleted_at.map(|at| (&row.record, at)))
+                .collect();
+            deleted.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.id.cmp(&b.0.id)));
+            Ok(deleted.into_iter().map(|(record, at)| (record.document.clone(), at)).collect())
+        })
+        .await
+    }
+
+    async fn find_ids(&self, kind: EntityKind, lookup: Lookup, input: &str, include_deleted: bool) -> Result<Vec<String>> {
+        let input = input.to_string();
+        self.with_entities(move |entities, _| Ok(entities.find_ids(&kind, lookup, &input, include_deleted))).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::DbConnection;
+    use crate::core::models::{Task, TaskType};
+    use crate::core::repositories::Reference;
+    use crate::services::adr_service::AdrServiceTrait;
+    use crate::services::history_service::HistoryServiceTrait;
+    use crate::services::key_service::KeyServiceTrait;
+    use crate::services::stats_service::StatsServiceTrait;
+    use crate::services::task_service::TaskServiceTrait;
+    use crate::services::trash_service::TrashServiceTrait;
+    use crate::services::user_story_service::UserStoryServiceTrait;
+
+    #[tokio::test]
+    async fn test_memory_backend_keeps_data_across_calls() -> Result<()> {
+        let config = Config { backend: StorageBackend::Memory, database_url: "unused.db".to_string(), ..Config::default() };
+        let db = Arc::new(DbConnection::from_config(&config).await?);
+        db.run_migrations().await?;
+        assert!(db.database_path().is_none());
+
+        let services = crate::cli::commands::Services::new(&config, db).await?;
+        let service = services.task_service;
+        service.create_task(Task::new("TASK-1".to_string(), "In memory".to_string(), TaskType::Task)).await?;
+        assert_eq!(service.list_tasks().await?.len(), 1);
+        assert_eq!(service.get_task_history("TASK-1".to_string()).await?.len(), 1);
+        service.delete_task("TASK-1".to_string()).await?;
+        assert_eq!(services.trash_service.list_trash(None).await?.len(), 1);
+        assert!(!std::path::Path::new("unused.db").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_database_url_is_rejected() {
+        assert!(matches!(DbConnection::new("").await, Err(Error::ConfigError(_))));
+        let config = Config { database_url: " ".to_string(), ..Config::default() };
+        assert!(matches!(DbConnection::from_config(&config).await, Err(Error::ConfigError(_))));
+        // An explicit memory backend does not use the URL
+        let config = Config { backend: StorageBackend::Memory, database_url: String::new(), ..Config::default() };
+        assert!(DbConnection::from_config(&config).await.is_ok());
+    }
+
+    /// Runs the same entity operations against `db` and returns how many user stories ended up in SQLite.
+    async fn exercise_stored_entities(db: Arc<DbConnection>) -> Result<i64> {
+        db.run_migrations().await?;
+        let config = Config::default();
+        let services = crate::cli::commands::Services::new(&config, db.clone()).await?;
+
+        let adr = services
+            .adr_service
+            .create_adr(Adr::new("Use SQLite".to_string(), String::new(), String::new(), String::new()))
+            .await?;
+        assert_eq!(adr.key.as_deref(), Some("ADR-1"));
+        let mut taken = Adr::new("Clash".to_string(), String::new(), String::new(), String::new());
+        taken.key = Some("adr-1".to_string());
+        assert!(matches!(services.adr_service.create_adr(taken).await, Err(Error::ValidationError(_))));
+
+        let mut stale = adr.clone();
+        let mut accepted = adr.clone();
+        accepted.status = crate::core::models::AdrStatus::Accepted;
+        let accepted = services.adr_service.update_adr(accepted).await?;
+        assert_eq!(accepted.version, 2);
+        stale.title = "Use files".to_string();
+        assert!(matches!(services.adr_service.update_adr(stale).await, Err(Error::Conflict { current_version: 2, .. })));
+        assert_eq!(services.adr_service.list_adrs().await?[0].status, crate::core::models::AdrStatus::Accepted);
+        assert_eq!(services.key_service.resolve("adr", "adr-1").await?, adr.id);
+        assert_eq!(services.key_service.find_any(&adr.id[4..12], false).await?.map(|r| r.entity_type).as_deref(), Some("adr"));
+        assert_eq!(services.history_service.get_history(&adr.id).await?.len(), 2);
+
+        services.task_service.create_task(Task::new("TASK-1".to_string(), "Story work".to_string(), TaskType::Task)).await?;
+        let mut story = UserStory::new("US-X".to_string(), "Story".to_string(), "user".to_string(), "a".to_string(), "b".to_string());
+        story.tasks = vec!["TASK-1".to_string()];
+        services.user_story_service.create_user_story(story).await?;
+        let references = services.task_service.delete_task("TASK-1".to_string()).await?;
+        assert_eq!(references, vec![Reference { entity_type: "user_story".to_string(), id: "US-X".to_string() }]);
+
+        services.adr_service.delete_adr(&adr.id).await?;
+        assert!(services.adr_service.get_adr_by_id(&adr.id).await?.is_none());
+        let trash: Vec<String> = services.trash_service.list_trash(None).await?.into_iter().map(|t| t.entity_type).collect();
+        assert_eq!(trash, vec!["task", "adr"]);
+        let stats = services.stats_service.get_stats().await?;
+        let adr_stats = stats.entities.iter().find(|e| e.entity_type == "adr").unwrap();
+        assert_eq!((adr_stats.total, adr_stats.deleted), (0, 1));
+
+        assert_eq!(services.trash_service.restore(&adr.id).await?, "adr");
+        assert_eq!(services.adr_service.get_adr_by_id(&adr.id).await?.unwrap().version, 2);
+        services.adr_service.delete_adr(&adr.id).await?;
+        assert_eq!(services.trash_service.purge(&adr.id).await?.name, "Use SQLite");
+        assert_eq!(services.key_service.resolve_any("ADR-1").await?, "ADR-1");
+
+        assert_eq!(db.get_entity::<UserStory>("US-X").await?.unwrap().key.as_deref(), Some("US-1"));
+        db.with_connection(|conn| Ok(conn.query_row("SELECT COUNT(*) FROM user_stories", [], |row| row.get(0))?)).await
+    }
+
+    #[tokio::test]
+    async fn test_both_backends_store_entities_alike() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("llmdocs.sqlite");
+        assert_eq!(exercise_stored_entities(Arc::new(DbConnection::new(path.to_str().unwrap()).await?)).await?, 1);
+        // The memory store keeps them itself, outside its SQLite database
+        assert_eq!(exercise_stored_entities(Arc::new(DbConnection::in_memory()?)).await?, 0);
+        Ok(())
+    }
+}
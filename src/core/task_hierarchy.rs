@@ -0,0 +1,231 @@
+// llmdoc/src/core/task_hierarchy.rs
+
+//! Parent/child structure of tasks built from `Task::parent_id`.
+//!
+//! Epics contain stories, and stories contain the work items (tasks, bugs,
+//! spikes and features); a work item may also sit directly under an epic.
+//! Story points and completion roll up from the leaves: a task with children
+//! is measured by them, and its own estimate is only shown for reference.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::core::dependency_graph::is_finished;
+use crate::core::models::task::{Task, TaskStatus, TaskType};
+
+/// Task types a task of type `child` may be placed under.
+pub fn allowed_parents(child: &TaskType) -> &'static [TaskType] {
+    match child {
+        TaskType::Epic => &[],
+        TaskType::Story => &[TaskType::Epic],
+        TaskType::Task | TaskType::Bug | TaskType::Spike | TaskType::Feature => &[TaskType::Story, TaskType::Epic],
+    }
+}
+
+/// Why a `child` task cannot be placed under a `parent` task, if it cannot.
+pub fn parent_rule_violation(child: &TaskType, parent: &TaskType) -> Option<String> {
+    let allowed = allowed_parents(child);
+    if allowed.contains(parent) {
+        return None;
+    }
+    if allowed.is_empty() {
+        return Some(format!("{:?} tasks cannot have a parent", child));
+    }
+    let names: Vec<String> = allowed.iter().map(|t| format!("{:?}", t)).collect();
+    Some(format!("{:?} tasks can only belong to {} tasks, not to a {:?}", child, names.join(" or "), parent))
+}
+
+struct Node {
+    title: String,
+    task_type: TaskType,
+    status: TaskStatus,
+    story_points: Option<u8>,
+    parent_id: Option<String>,
+}
+
+/// A task, its descendants, and the story points and progress rolled up from them.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTree {
+    pub id: String,
+    pub title: String,
+    pub task_type: TaskType,
+    pub status: TaskStatus,
+    pub story_points: Option<u8>, // Own estimate
+    pub total_points: u32,        // Rolled up from the leaves; cancelled work is left out
+    pub done_points: u32,
+    pub total_items: u32,         // Leaf tasks counted in the rollup
+    pub done_items: u32,
+    pub percent_complete: f64,    // By points, or by item count when nothing is estimated
+    pub children: Vec<TaskTree>,
+}
+
+pub struct TaskHierarchy {
+    nodes: BTreeMap<String, Node>,
+    children: BTreeMap<String, Vec<String>>,
+}
+
+impl TaskHierarchy {
+    pub fn new(tasks: &[Task]) -> Self {
+        let mut nodes = BTreeMap::new();
+        for task in tasks {
+            let node = Node {
+                title: task.title.clone(),
+                task_type: task.task_type.clone(),
+                status: task.status.clone(),
+                story_points: task.story_points,
+                parent_id: task.parent_id.clone(),
+            };
+            nodes.insert(task.id.clone(), node);
+        }
+        let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (id, node) in &nodes {
+            if let Some(parent) = node.parent_id.as_ref().filter(|p| nodes.contains_key(*p)) {
+                children.entry(parent.clone()).or_default().push(id.clone());
+            }
+        }
+        Self { nodes, children }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Direct children of `id`.
+    pub fn children(&self, id: &str) -> &[String] {
+        self.children.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Checks that `task` may be saved with its current parent and type: the parent must
+    /// exist and accept its type, and its existing children must still fit under it.
+    pub fn check(&self, task: &Task) -> std::result::Result<(), String> {
+        if let Some(parent_id) = &task.parent_id {
+            if parent_id == &task.id {
+                return Err(format!("Task {} cannot be its own parent", task.id));
+            }
+            let parent = self.nodes.get(parent_id).ok_or_else(|| format!("Parent task {} not found", parent_id))?;
+            if let Some(violation) = parent_rule_violation(&task.task_type, &parent.task_type) {
+                return Err(format!("{} cannot be placed under {}: {}", task.id, parent_id, violation));
+            }
+        }
+        for child_id in self.children(&task.id) {
+            let child = &self.nodes[child_id];
+            if let Some(violation) = parent_rule_violation(&child.task_type, &task.task_type) {
+                return Err(format!("{} cannot become a {:?} while {} is under it: {}", task.id, task.task_type, child_id, violation));
+            }
+        }
+        Ok(())
+    }
+
+    /// `id` and everything below it, or `None` if it does not exist.
+    pub fn tree(&self, id: &str) -> Option<TaskTree> {
+        self.contains(id).then(|| self.subtree(id, &mut Vec::new()))
+    }
+
+    /// Every top-level task (no parent, or a parent that no longer exists) with its descendants.
+    pub fn roots(&self) -> Vec<TaskTree> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.parent_id.as_ref().is_none_or(|p| !self.contains(p)))
+            .map(|(id, _)| self.subtree(id, &mut Vec::new()))
+            .collect()
+    }
+
+    fn subtree(&self, id: &str, ancestors: &mut Vec<String>) -> TaskTree {
+        let node = &self.nodes[id];
+        ancestors.push(id.to_string());
+        // Parent links written outside the service can loop; stop rather than recurse forever
+        let mut children = Vec::new();
+        for child in self.children(id) {
+            if !ancestors.contains(child) {
+                children.push(self.subtree(child, ancestors));
+            }
+        }
+        ancestors.pop();
+
+        let mut tree = TaskTree {
+            id: id.to_string(),
+            title: node.title.clone(),
+            task_type: node.task_type.clone(),
+            status: node.status.clone(),
+            story_points: node.story_points,
+            total_points: 0,
+            done_points: 0,
+            total_items: 0,
+            done_items: 0,
+            percent_complete: 0.0,
+            children,
+        };
+        if tree.children.is_empty() {
+            if node.status != TaskStatus::Cancelled {
+                let done = node.status == TaskStatus::Done;
+                tree.total_points = node.story_points.unwrap_or(0).into();
+                tree.done_points = if done { tree.total_points } else { 0 };
+                tree.total_items = 1;
+                tree.done_items = done as u32;
+            }
+        } else {
+            for child in &tree.children {
+                tree.total_points += child.total_points;
+                tree.done_points += child.done_points;
+                tree.total_items += child.total_items;
+                tree.done_items += child.done_items;
+            }
+        }
+        tree.percent_complete = if tree.total_points > 0 {
+            100.0 * tree.done_points as f64 / tree.total_points as f64
+        } else if tree.total_items > 0 {
+            100.0 * tree.done_items as f64 / tree.total_items as f64
+        } else if is_finished(&node.status) {
+            100.0
+        } else {
+            0.0
+        };
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, task_type: TaskType, parent: Option<&str>, status: TaskStatus, points: Option<u8>) -> Task {
+        let mut task = Task::new(id.to_string(), id.to_string(), task_type);
+        task.parent_id = parent.map(String::from);
+        task.status = status;
+        task.story_points = points;
+        task
+    }
+
+    #[test]
+    fn test_rules_and_rollup() {
+        let tasks = vec![
+            task("E", TaskType::Epic, None, TaskStatus::Todo, Some(40)),
+            task("S1", TaskType::Story, Some("E"), TaskStatus::InProgress, None),
+            task("S2", TaskType::Story, Some("E"), TaskStatus::Todo, None),
+            task("T1", TaskType::Task, Some("S1"), TaskStatus::Done, Some(3)),
+            task("T2", TaskType::Bug, Some("S1"), TaskStatus::Todo, Some(5)),
+            task("T3", TaskType::Task, Some("S1"), TaskStatus::Cancelled, Some(8)),
+            task("T4", TaskType::Spike, Some("S2"), TaskStatus::Done, None),
+            task("T5", TaskType::Spike, Some("S2"), TaskStatus::Todo, None),
+        ];
+        let hierarchy = TaskHierarchy::new(&tasks);
+
+        assert!(hierarchy.check(&tasks[3]).is_ok());
+        assert!(hierarchy.check(&task("X", TaskType::Epic, Some("E"), TaskStatus::Todo, None)).is_err());
+        assert!(hierarchy.check(&task("X", TaskType::Story, Some("S1"), TaskStatus::Todo, None)).is_err());
+        assert!(hierarchy.check(&task("X", TaskType::Task, Some("T1"), TaskStatus::Todo, None)).is_err());
+        assert!(hierarchy.check(&task("X", TaskType::Task, Some("GONE"), TaskStatus::Todo, None)).is_err());
+        assert!(hierarchy.check(&task("X", TaskType::Task, Some("E"), TaskStatus::Todo, None)).is_ok());
+        // A story with tasks under it cannot turn into a task
+        assert!(hierarchy.check(&task("S1", TaskType::Task, Some("E"), TaskStatus::Todo, None)).is_err());
+
+        let roots = hierarchy.roots();
+        assert_eq!(roots.len(), 1);
+        let epic = &roots[0];
+        assert_eq!((epic.total_points, epic.done_points), (8, 3));
+        assert_eq!((epic.total_items, epic.done_items), (4, 2));
+        assert_eq!(epic.percent_complete, 37.5);
+        // Nothing estimated under S2, so it is measured by item count
+        assert_eq!(epic.children[1].percent_complete, 50.0);
+    }
+}
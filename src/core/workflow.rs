@@ -0,0 +1,240 @@
+// llmdoc/src/core/workflow.rs
+
+//! Task status workflow loaded from the `[workflow]` config section.
+//!
+//! The workflow decides which statuses a task may be in, which status it may
+//! move to next, and which fields must be filled in before it enters a status.
+//! Each task type can replace any of these. Automatic blocking and unblocking
+//! by dependency tracking is not subject to the workflow, but every task type's
+//! states must include `blocked` for it to have somewhere to go.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::app_config::WorkflowConfig;
+use crate::core::errors::{Error, Result};
+use crate::core::models::task::{Task, TaskStatus, TaskType};
+
+#[derive(Debug, Clone)]
+struct Rules {
+    states: Vec<TaskStatus>,
+    transitions: HashMap<TaskStatus, Vec<TaskStatus>>,
+    required_fields: HashMap<TaskStatus, Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    rules: Rules,
+    types: HashMap<TaskType, Rules>,
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Self::from_config(&WorkflowConfig::default()).expect("default workflow is valid")
+    }
+}
+
+impl Workflow {
+    /// Parses and checks a workflow definition, failing with a `ConfigError` naming the bad entry.
+    pub fn from_config(config: &WorkflowConfig) -> Result<Self> {
+        let rules = Rules {
+            states: parse_states(&config.states)?,
+            transitions: parse_transitions(&config.transitions)?,
+            required_fields: parse_required_fields(&config.required_fields)?,
+        };
+        check_can_block(&rules, "")?;
+        let mut types = HashMap::new();
+        for (task_type, variant) in &config.types {
+            let task_type: TaskType = task_type.parse().map_err(config_error)?;
+            let type_rules = Rules {
+                states: match &variant.states {
+                    Some(states) => parse_states(states)?,
+                    None => rules.states.clone(),
+                },
+                transitions: match &variant.transitions {
+                    Some(transitions) => parse_transitions(transitions)?,
+                    None => rules.transitions.clone(),
+                },
+                required_fields: match &variant.required_fields {
+                    Some(fields) => parse_required_fields(fields)?,
+                    None => rules.required_fields.clone(),
+                },
+            };
+            check_can_block(&type_rules, &format!(" for {:?} tasks", task_type))?;
+            types.insert(task_type, type_rules);
+        }
+        Ok(Self { rules, types })
+    }
+
+    fn rules_for(&self, task_type: &TaskType) -> &Rules {
+        self.types.get(task_type).unwrap_or(&self.rules)
+    }
+
+    /// Statuses a task of this type may move to from `from`.
+    pub fn allowed_next(&self, task_type: &TaskType, from: &TaskStatus) -> Vec<TaskStatus> {
+        let rules = self.rules_for(task_type);
+        rules
+            .transitions
+            .get(from)
+            .map(|next| next.iter().filter(|s| rules.states.contains(s)).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Checks that a new `task` may start out in its status.
+    pub fn check_initial(&self, task: &Task) -> Result<()> {
+        self.check_state(task)?;
+        self.check_required_fields(task)
+    }
+
+    /// Checks that `task`, currently stored in status `from`, may be saved in its new status.
+    /// Keeping the same status is always allowed.
+    pub fn check_transition(&self, from: &TaskStatus, task: &Task) -> Result<()> {
+        if &task.status == from {
+            return Ok(());
+        }
+        self.check_state(task)?;
+        let allowed = self.allowed_next(&task.task_type, from);
+        if !allowed.contains(&task.status) {
+            let allowed = if allowed.is_empty() {
+                "none".to_string()
+            } else {
+                allowed.iter().map(|s| format!("{:?}", s)).collect::<Vec<_>>().join(", ")
+            };
+            return Err(Error::ValidationError(format!(
+                "{} cannot move from {:?} to {:?}; allowed next states: {}",
+                task.id, from, task.status, allowed
+            )));
+        }
+        self.check_required_fields(task)
+    }
+
+    fn check_state(&self, task: &Task) -> Result<()> {
+        if self.rules_for(&task.task_type).states.contains(&task.status) {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{:?} is not a state in the workflow for {:?} tasks",
+                task.status, task.task_type
+            )))
+        }
+    }
+
+    fn check_required_fields(&self, task: &Task) -> Result<()> {
+        let Some(fields) = self.rules_for(&task.task_type).required_fields.get(&task.status) else {
+            return Ok(());
+        };
+        let document = serde_json::to_value(task)?;
+        let missing: Vec<&str> = fields
+            .iter()
+            .filter(|field| !is_set(document.get(field.as_str())))
+            .map(String::as_str)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{} needs {} before it can move to {:?}",
+                task.id,
+                missing.join(", "),
+                task.status
+            )))
+        }
+    }
+}
+
+/// Whether a task field counts as filled in: not null, and not an empty string or list.
+fn is_set(value: Option<&serde_json::Value>) -> bool {
+    match value {
+        None | Some(serde_json::Value::Null) => false,
+        Some(serde_json::Value::String(s)) => !s.trim().is_empty(),
+        Some(serde_json::Value::Array(items)) => !items.is_empty(),
+        Some(_) => true,
+    }
+}
+
+fn config_error(e: Error) -> Error {
+    Error::ConfigError(format!("Invalid workflow: {}", e))
+}
+
+/// Dependency tracking blocks tasks whatever the workflow says, so `blocked` must be a state.
+fn check_can_block(rules: &Rules, scope: &str) -> Result<()> {
+    if rules.states.contains(&TaskStatus::Blocked) {
+        Ok(())
+    } else {
+        Err(Error::ConfigError(format!(
+            "Invalid workflow: the states{} must include blocked, where dependency tracking puts waiting tasks",
+            scope
+        )))
+    }
+}
+
+fn parse_states(states: &[String]) -> Result<Vec<TaskStatus>> {
+    states.iter().map(|s| s.parse().map_err(config_error)).collect()
+}
+
+fn parse_transitions(transitions: &BTreeMap<String, Vec<String>>) -> Result<HashMap<TaskStatus, Vec<TaskStatus>>> {
+    transitions
+        .iter()
+        .map(|(from, to)| Ok((from.parse().map_err(config_error)?, parse_states(to)?)))
+        .collect()
+}
+
+fn parse_required_fields(fields: &BTreeMap<String, Vec<String>>) -> Result<HashMap<TaskStatus, Vec<String>>> {
+    // Field names are checked against a serialized task so new fields are picked up automatically
+    let known = serde_json::to_value(Task::new(String::new(), String::new(), TaskType::Task))?;
+    let mut parsed = HashMap::new();
+    for (status, names) in fields {
+        if let Some(unknown) = names.iter().find(|name| known.get(name.as_str()).is_none()) {
+            return Err(Error::ConfigError(format!("Invalid workflow: unknown task field '{}'", unknown)));
+        }
+        parsed.insert(status.parse().map_err(config_error)?, names.clone());
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::WorkflowVariant;
+
+    #[test]
+    fn test_transitions_required_fields_and_type_variants() -> Result<()> {
+        let mut config = WorkflowConfig::default();
+        config.required_fields.insert("in_progress".to_string(), vec!["story_points".to_string()]);
+        config.types.insert(
+            "spike".to_string(),
+            WorkflowVariant { required_fields: Some(BTreeMap::new()), ..Default::default() },
+        );
+        let workflow = Workflow::from_config(&config)?;
+
+        let mut task = Task::new("TASK-1".to_string(), "Task".to_string(), TaskType::Task);
+        task.status = TaskStatus::InProgress;
+        let err = workflow.check_transition(&TaskStatus::Todo, &task).unwrap_err();
+        assert!(err.to_string().contains("story_points"));
+        task.story_points = Some(3);
+        workflow.check_transition(&TaskStatus::Todo, &task)?;
+
+        let err = workflow.check_transition(&TaskStatus::Cancelled, &task).unwrap_err();
+        assert!(err.to_string().contains("allowed next states: Todo"), "{}", err);
+
+        let mut spike = Task::new("TASK-2".to_string(), "Spike".to_string(), TaskType::Spike);
+        spike.status = TaskStatus::InProgress;
+        workflow.check_transition(&TaskStatus::Todo, &spike)?;
+
+        config.required_fields.insert("done".to_string(), vec!["no_such_field".to_string()]);
+        assert!(matches!(Workflow::from_config(&config), Err(Error::ConfigError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_every_task_type_needs_a_blocked_state() {
+        let unblockable = vec!["todo".to_string(), "done".to_string()];
+        let config = WorkflowConfig { states: unblockable.clone(), ..Default::default() };
+        let err = Workflow::from_config(&config).unwrap_err();
+        assert!(matches!(err, Error::ConfigError(_)) && err.to_string().contains("blocked"), "{}", err);
+
+        let mut config = WorkflowConfig::default();
+        config.types.insert("bug".to_string(), WorkflowVariant { states: Some(unblockable), ..Default::default() });
+        let err = Workflow::from_config(&config).unwrap_err();
+        assert!(matches!(err, Error::ConfigError(_)) && err.to_string().contains("Bug"), "{}", err);
+    }
+}
@@ -26,7 +26,7 @@
 //!     initialize_app().await?;
 //!     
 //!     // Load configuration
-//!     let config = Config::load_or_create_default().await?;
+//!     let config = Config::load(None)?;
 //!     
 //!     // Use the library...
 //!     Ok(())
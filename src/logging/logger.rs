@@ -34,6 +34,7 @@ impl LogLevel {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(level_str: &str) -> Result<Self, String> {
         match level_str.to_lowercase().as_str() {
             "trace" => Ok(LogLevel::Trace),
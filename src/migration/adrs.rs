@@ -6,9 +6,9 @@ use crate::{
         errors::{Result, Error},
     },
 };
+use crate::services::adr_service::{AdrService, AdrServiceTrait};
 use regex::Regex;
 use tracing::warn;
-use serde_json;
 
 use super::MarkdownMigrator; // Import from the parent module
 
@@ -22,6 +22,9 @@ impl MarkdownMigrator {
 
         println!("📐 Migrating ADRs...");
 
+        let adr_number_regex = Regex::new(r"ADR(\d+)")?;
+        let title_regex = Regex::new(r"^#\s+(.+)")?;
+
         for entry in std::fs::read_dir(&adr_dir)? {
             let entry = entry?;
             let path = entry.path();
@@ -34,17 +37,18 @@ impl MarkdownMigrator {
                 let content = std::fs::read_to_string(&path)?;
 
                 // Extract ADR number
-                let adr_id = if let Some(cap) = Regex::new(r"ADR(\d+)")?.captures(
+                let adr_id = if let Some(cap) = adr_number_regex.captures(
                     path.file_name().unwrap().to_str().unwrap()
                 ) {
                     format!("ADR{:03}", cap[1].parse::<u32>()
-                        .map_err(|e| Error::ParseIntError(e))?) // Corrected error mapping
+                        .map_err(Error::ParseIntError)?) // Corrected error mapping
                 } else {
                     continue;
                 };
 
                 let mut adr = Adr {
                     id: adr_id.clone(),
+                    key: None,
                     title: String::new(),
                     status: AdrStatus::Accepted,
                     context: String::new(),
@@ -54,13 +58,14 @@ impl MarkdownMigrator {
                     related_adrs: vec![],
                     created_at: chrono::Utc::now(), // Use chrono::Utc
                     updated_at: chrono::Utc::now(), // Use chrono::Utc
+                    version: 1,
                     created_by: None,
                     approved_by: None,
                     approved_at: None,
                 };
 
                 // Parse title
-                if let Some(cap) = Regex::new(r"^#\s+(.+)")?.captures(&content) {
+                if let Some(cap) = title_regex.captures(&content) {
                     adr.title = cap[1].trim().to_string();
                 }
 
@@ -85,16 +90,11 @@ impl MarkdownMigrator {
                 }
 
                 if !self.dry_run {
-                    let adr_clone = adr.clone();
-                    let conn = self.db.pool.get().await.map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool for ADR transaction: {}", e)))?;
-                    conn.interact(move |conn| {
-                        let json = serde_json::to_string(&adr_clone).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?; // Map serde_json::Error to rusqlite::Error
-                        conn.execute(
-                            "INSERT INTO adrs (adr_id, data, status) VALUES (?1, ?2, ?3)",
-                            rusqlite::params![&adr_clone.id, &json, "accepted"],
-                        )?;
-                        Ok::<(), rusqlite::Error>(()) // Explicit type annotation
-                    }).await.map_err(|e| Error::DatabaseOperation(e.to_string()))??; // Added extra ? for conversion
+                    let service = AdrService::new(self.db.clone());
+                    if let Err(e) = service.create_adr(adr).await {
+                        self.stats.errors.push(format!("Failed to create ADR {}: {}", adr_id, e));
+                        continue;
+                    }
                     self.stats.adrs_migrated += 1;
                 } else {
                     println!("  Would create ADR: {} - {}", adr_id, adr.title);
@@ -6,10 +6,10 @@ use crate::{
         errors::Result,
     },
 };
+use crate::services::component_service::{ComponentService, ComponentServiceTrait};
 use regex::Regex;
 use std::collections::HashMap;
 use tracing::{warn};
-use serde_json;
 
 use super::MarkdownMigrator; // Import from the parent module
 
@@ -23,6 +23,8 @@ impl MarkdownMigrator {
 
         println!("🔧 Migrating components...");
 
+        let title_regex = Regex::new(r"^#\s+(.+)")?;
+
         for entry in std::fs::read_dir(&components_dir)? {
             let entry = entry?;
             let path = entry.path();
@@ -42,6 +44,7 @@ impl MarkdownMigrator {
 
                 let mut component = Component {
                     id: comp_id.clone(),
+                    key: None,
                     name: comp_id.clone(),
                     component_type: ComponentType::Module,
                     description: String::new(),
@@ -53,11 +56,12 @@ impl MarkdownMigrator {
                     repository_url: None,
                     created_at: chrono::Utc::now(), // Use chrono::Utc
                     updated_at: chrono::Utc::now(), // Use chrono::Utc
+                    version: 1,
                     metadata: HashMap::new(),
                 };
 
                 // Parse component name
-                if let Some(cap) = Regex::new(r"^#\s+(.+)")?.captures(&content) {
+                if let Some(cap) = title_regex.captures(&content) {
                     component.name = cap[1].trim().to_string();
                 }
 
@@ -84,19 +88,14 @@ impl MarkdownMigrator {
                 }
 
                 if !self.dry_run {
-                    let component_clone = component.clone(); // Clone the component for the async block
-                     let conn = self.db.pool.get().await.map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection from pool for component transaction: {}", e)))?;
-                     conn.interact(move |conn| {
-                         let json = serde_json::to_string(&component_clone).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?; // Map serde_json::Error to rusqlite::Error
-                          conn.execute(
-                             "INSERT INTO components (component_id, data) VALUES (?1, ?2)",
-                             rusqlite::params![&component_clone.id, &json],
-                          )?;
-                          Ok::<(), rusqlite::Error>(()) // Explicit type annotation
-                     }).await.map_err(|e| crate::core::errors::Error::DatabaseOperation(e.to_string()))??; // Added extra ? for conversion
-                     self.stats.components_migrated += 1;
-                 } else {
-                     println!("  Would create component: {}", comp_id);
+                    let service = ComponentService::new(self.db.clone());
+                    if let Err(e) = service.create_component(component).await {
+                        self.stats.errors.push(format!("Failed to create component {}: {}", comp_id, e));
+                        continue;
+                    }
+                    self.stats.components_migrated += 1;
+                } else {
+                    println!("  Would create component: {}", comp_id);
                     self.stats.components_migrated += 1;
                 }
             }
@@ -71,7 +71,7 @@ impl MarkdownMigrator {
     }
 
     fn extract_section(&self, content: &str, section_name: &str) -> Option<String> {
-        let pattern = format!(r"(?s)##\s*{}\s*\n(.*?)(?=\n##|\z)", regex::escape(section_name));
+        let pattern = format!(r"(?s)##\s*{}\s*\n(.*?)(?:\n##|\z)", regex::escape(section_name));
         Regex::new(&pattern).ok()
             .and_then(|re| re.captures(content))
             .map(|cap| cap[1].trim().to_string())
@@ -141,4 +141,119 @@ pub async fn migrate_command(
 ) -> Result<()> {
     let mut migrator = MarkdownMigrator::new(db, docs_dir, dry_run);
     migrator.migrate().await
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::task_service::TaskServiceTrait;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/migration/fixtures");
+
+    fn fixture(name: &str) -> String {
+        std::fs::read_to_string(PathBuf::from(FIXTURES).join("agile").join(name)).unwrap()
+    }
+
+    fn sections(pattern: &str, content: &str) -> Vec<(String, String)> {
+        let re = Regex::new(pattern).unwrap();
+        re.captures_iter(content)
+            .map(|cap| (cap[1].to_string(), cap[cap.len() - 1].to_string()))
+            .collect()
+    }
+
+    async fn migrated_fixtures() -> Result<Arc<DbConnection>> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let mut migrator = MarkdownMigrator::new(db.clone(), PathBuf::from(FIXTURES), false);
+        migrator.migrate().await?;
+        assert!(migrator.stats.errors.is_empty(), "{:?}", migrator.stats.errors);
+        assert_eq!((migrator.stats.tasks_found, migrator.stats.tasks_migrated), (3, 3));
+        assert_eq!((migrator.stats.stories_found, migrator.stats.stories_migrated), (2, 2));
+        Ok(db)
+    }
+
+    #[test]
+    fn test_task_sections_end_at_the_next_task_heading() {
+        let own = |id: &str, body: &str| (id.to_string(), body.to_string());
+        assert_eq!(
+            sections(tasks::TASK_SECTION_PATTERN, &fixture("tasks.md")),
+            vec![
+                own("TASK-1", "**Status:** done\n**Type:** feature\n**Labels:** ci, build\n\n#### Notes\nRuns on every push.\n\n"),
+                own("TASK-2", "**Status:** in progress\n**Assignee:** alice\n**Story Points:** 3\n#hashtag line\n##not-a-heading\n\n"),
+                own("TASK-3", "**Type:** bug\n**Dependencies:** TASK-1, TASK-2\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_story_sections_end_at_the_next_story_heading() {
+        let own = |title: &str, body: &str| (title.to_string(), body.to_string());
+        assert_eq!(
+            sections(user_stories::STORY_SECTION_PATTERN, &fixture("user-stories.md")),
+            vec![
+                own(
+                    "Export to CSV",
+                    "As a project manager, I want to export tasks to CSV so that I can share them.\n\n\
+                     Acceptance Criteria:\n- Every task is a row\n* Headers match the table\n\n#### Out of scope\n- Excel\n\n",
+                ),
+                own("Dark mode", "As an engineer, I want a dark theme so that my eyes rest.\n##tight is still body\n"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrates_fixture_tasks() -> Result<()> {
+        let db = migrated_fixtures().await?;
+        let service = crate::services::task_service::TaskService::new(db);
+        let mut tasks = service.list_tasks().await?;
+        tasks.sort_by(|a, b| a.id.cmp(&b.id));
+        let summary: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.title.as_str(), t.status.clone(), t.task_type.clone(), t.assignee.as_deref(), t.story_points))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("TASK-1", "Set up CI", TaskStatus::Done, TaskType::Feature, None, None),
+                ("TASK-2", "Write docs", TaskStatus::InProgress, TaskType::Task, Some("alice"), Some(3)),
+                // TASK-2 is unfinished, so its dependant starts out blocked
+                ("TASK-3", "Fix login", TaskStatus::Blocked, TaskType::Bug, None, None),
+            ]
+        );
+        assert_eq!(tasks[0].labels, vec!["ci", "build"]);
+        assert_eq!(tasks[2].dependencies, vec!["TASK-1", "TASK-2"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrates_fixture_user_stories() -> Result<()> {
+        use crate::services::user_story_service::{UserStoryService, UserStoryServiceTrait};
+
+        let db = migrated_fixtures().await?;
+        let mut stories = UserStoryService::new(db).list_user_stories().await?;
+        stories.sort_by(|a, b| a.id.cmp(&b.id));
+        let summary: Vec<_> = stories
+            .iter()
+            .map(|s| (s.id.as_str(), s.title.as_str(), s.persona.as_str(), s.want.as_str(), s.benefit.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("US-001", "Export to CSV", "project manager", "to export tasks to CSV", "I can share them"),
+                ("US-002", "Dark mode", "engineer", "a dark theme", "my eyes rest"),
+            ]
+        );
+        assert_eq!(stories[0].acceptance_criteria, vec!["Every task is a row", "Headers match the table"]);
+        assert!(stories[1].acceptance_criteria.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sections_end_at_the_next_heading() {
+        let migrator = MarkdownMigrator::new(Arc::new(DbConnection::in_memory().unwrap()), PathBuf::new(), true);
+        let content = "# ADR\n## Context\nWhy\n\n## Decision\nWhat\n## Consequences\nThen";
+        assert_eq!(migrator.extract_section(content, "Context").as_deref(), Some("Why"));
+        assert_eq!(migrator.extract_section(content, "Decision").as_deref(), Some("What"));
+        assert_eq!(migrator.extract_section(content, "Consequences").as_deref(), Some("Then"));
+        assert_eq!(migrator.extract_section(content, "Status"), None);
+    }
+}
@@ -65,7 +65,8 @@ impl MarkdownMigrator {
         // Parse sprint details
         let mut sprint = Sprint {
             id: sprint_id.clone(),
-            name: format!("Sprint {}", sprint_id.split('-').last().unwrap()),
+            key: None,
+            name: format!("Sprint {}", sprint_id.split('-').next_back().unwrap()),
             description: None,
             start_date: Utc::now(),
             end_date: Utc::now(),
@@ -76,7 +77,9 @@ impl MarkdownMigrator {
             capacity: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
             retrospective: None,
+            carry_over: None,
         };
 
         // Parse sprint name
@@ -88,14 +91,14 @@ impl MarkdownMigrator {
         if let Some(cap) = Regex::new(r"(\d{4}-\d{2}-\d{2})\s*-\s*(\d{4}-\d{2}-\d{2})")?.captures(&content) {
             sprint.start_date = DateTime::from_naive_utc_and_offset(
                 NaiveDate::parse_from_str(&cap[1], "%Y-%m-%d")
-                    .map_err(|e| Error::Parse(e))? // Corrected error mapping
+                    .map_err(Error::Parse)? // Corrected error mapping
                     .and_hms_opt(0, 0, 0)
                     .ok_or_else(|| Error::InvalidInput("Invalid time components for start date".to_string()))?, // Corrected error mapping
                 Utc,
             );
             sprint.end_date = DateTime::from_naive_utc_and_offset(
                 NaiveDate::parse_from_str(&cap[2], "%Y-%m-%d")
-                    .map_err(|e| Error::Parse(e))? // Corrected error mapping
+                    .map_err(Error::Parse)? // Corrected error mapping
                     .and_hms_opt(23, 59, 59)
                     .ok_or_else(|| Error::InvalidInput("Invalid time components for end date".to_string()))?, // Corrected error mapping
                 Utc,
@@ -103,7 +106,7 @@ impl MarkdownMigrator {
         }
 
         // Parse goals
-        if let Some(goals_section) = Regex::new(r"(?s)##\s*Goals?\s*\n(.*?)(?=##|\z)")?.captures(&content) {
+        if let Some(goals_section) = Regex::new(r"(?s)##\s*Goals?\s*\n(.*?)(?:##|\z)")?.captures(&content) {
             sprint.goals = goals_section[1]
                 .lines()
                 .filter_map(|line| {
@@ -15,6 +15,13 @@ use tracing::{warn};
 
 use super::MarkdownMigrator; // Import from the parent module
 
+/// A `## ID: title` or `### ID: title` heading and its body, which runs up to the next line
+/// starting with `## ` or `### `. The `regex` crate has no lookahead, so body lines are
+/// spelled out: blank, not starting with `#`, a lone `#` or `# ...`, or `##`/`###`
+/// followed by something other than whitespace.
+pub(super) const TASK_SECTION_PATTERN: &str =
+    r"(?m)^###?\s+(\w+-\d+):\s*(.+?)$\n((?:(?:[^#\n].*|#(?:[^#\n].*)?|##[^#\s].*|###\S.*)?\n)*)";
+
 impl MarkdownMigrator {
     pub(super) async fn migrate_tasks(&mut self) -> Result<()> {
         let tasks_file = self.docs_dir.join("agile/tasks.md");
@@ -27,9 +34,7 @@ impl MarkdownMigrator {
         let content = std::fs::read_to_string(&tasks_file)?;
 
         // Parse task sections with improved regex
-        let task_regex = Regex::new(
-            r"(?m)^###?\s+(\w+-\d+):\s*(.+?)$\n((?:(?!^###?\s).*\n)*)"
-        )?;
+        let task_regex = Regex::new(TASK_SECTION_PATTERN)?;
 
         let pb = ProgressBar::new(task_regex.captures_iter(&content).count() as u64);
         pb.set_style(
@@ -3,15 +3,21 @@
 use crate::{
     core::{
         models::{UserStory, Priority},
-        errors::{Result, Error},
+        errors::Result,
     },
 };
+use crate::services::user_story_service::{UserStoryService, UserStoryServiceTrait};
 use regex::Regex;
 use tracing::warn;
-use serde_json;
 
 use super::MarkdownMigrator; // Import from the parent module
 
+/// A `## title` heading and its body, which runs up to the next line starting with `## `.
+/// Without lookahead in the `regex` crate, body lines are blank, not starting with `#`,
+/// a lone `#` or `# ...`, or `##` followed by something other than whitespace.
+pub(super) const STORY_SECTION_PATTERN: &str =
+    r"(?m)^##\s+(.+?)$\n((?:(?:[^#\n].*|#(?:[^#\n].*)?|##\S.*)?\n)*)";
+
 impl MarkdownMigrator {
     pub(super) async fn migrate_user_stories(&mut self) -> Result<()> {
         let stories_file = self.docs_dir.join("agile/user-stories.md");
@@ -24,25 +30,23 @@ impl MarkdownMigrator {
         let content = std::fs::read_to_string(&stories_file)?;
 
         // Parse story sections
-        let story_regex = Regex::new(r"(?m)^##\s+(.+?)$\n((?:(?!^##\s).*\n)*)")?;
+        let story_regex = Regex::new(STORY_SECTION_PATTERN)?;
+        let story_pattern = Regex::new(
+            r"(?i)as\s+a[n]?\s+(.+?),?\s+i\s+want\s+(.+?)\s+so\s+that\s+(.+?)(?:\.|$)"
+        )?;
+        let acceptance_regex = Regex::new(r"(?s)Acceptance Criteria:?\s*\n(.*?)(?:\n##|\z)")?;
 
-        let mut story_counter = 1;
-        for cap in story_regex.captures_iter(&content) {
+        for (story_counter, cap) in (1..).zip(story_regex.captures_iter(&content)) {
             self.stats.stories_found += 1;
 
             let title = &cap[1];
             let body = &cap[2];
 
             let story_id = format!("US-{:03}", story_counter);
-            story_counter += 1;
-
-            // Parse user story pattern
-            let story_pattern = Regex::new(
-                r"(?i)as\s+a[n]?\s+(.+?),?\s+i\s+want\s+(.+?)\s+so\s+that\s+(.+?)(?:\.|$)"
-            )?;
 
             let mut story = UserStory {
                 id: story_id.clone(),
+                key: None,
                 title: title.trim().to_string(),
                 persona: "user".to_string(),
                 want: title.trim().to_string(),
@@ -55,6 +59,7 @@ impl MarkdownMigrator {
                 tasks: vec![],
                 created_at: chrono::Utc::now(), // Use chrono::Utc
                 updated_at: chrono::Utc::now(), // Use chrono::Utc
+                version: 1,
             };
 
             if let Some(cap) = story_pattern.captures(body) {
@@ -64,7 +69,7 @@ impl MarkdownMigrator {
             }
 
             // Parse acceptance criteria
-            if let Some(ac_section) = Regex::new(r"(?s)Acceptance Criteria:?\s*\n(.*?)(?=\n##|\z)")?.captures(body) {
+            if let Some(ac_section) = acceptance_regex.captures(body) {
                 story.acceptance_criteria = ac_section[1]
                     .lines()
                     .filter_map(|line| {
@@ -85,16 +90,11 @@ impl MarkdownMigrator {
 
             if !self.dry_run {
                 // Store user story in database
-                let story_clone = story.clone();
-                let conn = self.db.pool.get().await.map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool for user story transaction: {}", e)))?;
-                conn.interact(move |conn| {
-                    let json = serde_json::to_string(&story_clone).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?; // Map serde_json::Error to rusqlite::Error
-                    conn.execute(
-                        "INSERT INTO user_stories (story_id, data) VALUES (?1, ?2)",
-                        rusqlite::params![&story_clone.id, &json],
-                    )?;
-                    Ok::<(), rusqlite::Error>(()) // Explicit type annotation
-                }).await.map_err(|e| Error::DatabaseOperation(e.to_string()))??; // Added extra ? for conversion
+                let service = UserStoryService::new(self.db.clone());
+                if let Err(e) = service.create_user_story(story).await {
+                    self.stats.errors.push(format!("Failed to create user story {}: {}", story_id, e));
+                    continue;
+                }
                 self.stats.stories_migrated += 1;
             } else {
                 println!("  Would create story: {} - {}", story_id, title);
@@ -0,0 +1,295 @@
+// llmdoc/src/services/capacity_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use crate::app_config::CapacityConfig;
+use crate::core::database::DbConnection;
+use crate::core::models::capacity::{member_key, same_member, MemberAvailability};
+use crate::core::models::task::TaskStatus;
+use crate::core::repositories::{CapacityRepository, SprintRepository, TaskRepository};
+
+/// One member's planned work in a sprint against what they can take on.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberLoad {
+    pub member: String,
+    pub availability: Option<MemberAvailability>, // None if none was recorded
+    pub capacity_points: Option<f64>,
+    pub load_points: u32,                         // Points of their tasks in the sprint, done or not
+    pub tasks: u32,
+    pub unestimated_tasks: u32,
+}
+
+impl MemberLoad {
+    /// Points planned beyond capacity, if the member has a capacity and is over it.
+    pub fn over_by(&self) -> Option<f64> {
+        self.capacity_points
+            .filter(|capacity| f64::from(self.load_points) > *capacity)
+            .map(|capacity| f64::from(self.load_points) - capacity)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapacityReport {
+    pub sprint_id: String,
+    pub sprint_name: String,
+    pub points_per_day: f64,
+    pub members: Vec<MemberLoad>,
+    pub unassigned_points: u32,
+    pub total_capacity: f64, // Of the members with recorded availability
+    pub total_load: u32,
+}
+
+impl CapacityReport {
+    /// Members planned beyond their capacity.
+    pub fn overcommitted(&self) -> impl Iterator<Item = &MemberLoad> {
+        self.members.iter().filter(|m| m.over_by().is_some())
+    }
+}
+
+#[async_trait]
+pub trait CapacityServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// Records a member's availability for a sprint. Values not given keep what was recorded
+    /// before; a new record needs `days_available` and takes the configured focus factor.
+    async fn set_availability(
+        &self,
+        sprint_id: &str,
+        member: &str,
+        days_available: Option<f64>,
+        focus_factor: Option<f64>,
+        leave_days: Option<f64>,
+    ) -> Result<MemberAvailability>;
+    async fn remove_availability(&self, sprint_id: &str, member: &str) -> Result<()>;
+    /// Every member with availability or tasks in the sprint, with their load.
+    async fn get_capacity(&self, sprint_id: &str) -> Result<CapacityReport>;
+}
+
+#[derive(Clone)]
+pub struct CapacityService {
+    db_connection: Arc<DbConnection>,
+    config: CapacityConfig,
+}
+
+impl CapacityService {
+    pub fn with_config(mut self, config: CapacityConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Load against capacity for every member of a sprint, for use inside another service's unit of work.
+    pub fn report_in(conn: &Connection, sprint_id: &str, points_per_day: f64) -> Result<CapacityReport> {
+        let sprint = SprintRepository::get(conn, sprint_id)?
+            .ok_or_else(|| Error::NotFound(format!("Sprint {} not found", sprint_id)))?;
+        let mut members: BTreeMap<String, MemberLoad> = BTreeMap::new();
+        for availability in CapacityRepository::list_for_sprint(conn, sprint_id)? {
+            members.insert(member_key(&availability.member), MemberLoad {
+                member: availability.member.clone(),
+                capacity_points: Some(round(availability.capacity_points(points_per_day))),
+                availability: Some(availability),
+                load_points: 0,
+                tasks: 0,
+                unestimated_tasks: 0,
+            });
+        }
+
+        let mut unassigned_points = 0;
+        for task in TaskRepository::list_by_sprint(conn, sprint_id)? {
+            if task.status == TaskStatus::Cancelled {
+                continue;
+            }
+            let points = u32::from(task.story_points.unwrap_or(0));
+            let Some(assignee) = task.assignee.as_deref().filter(|a| !a.trim().is_empty()) else {
+                unassigned_points += points;
+                continue;
+            };
+            let load = members.entry(member_key(assignee)).or_insert_with(|| MemberLoad {
+                member: assignee.trim().to_string(),
+                availability: None,
+                capacity_points: None,
+                load_points: 0,
+                tasks: 0,
+                unestimated_tasks: 0,
+            });
+            load.load_points += points;
+            load.tasks += 1;
+            load.unestimated_tasks += task.story_points.is_none() as u32;
+        }
+
+        let members: Vec<MemberLoad> = members.into_values().collect();
+        Ok(CapacityReport {
+            sprint_id: sprint.id,
+            sprint_name: sprint.name,
+            points_per_day,
+            total_capacity: round(members.iter().filter_map(|m| m.capacity_points).sum()),
+            total_load: members.iter().map(|m| m.load_points).sum::<u32>() + unassigned_points,
+            unassigned_points,
+            members,
+        })
+    }
+
+    /// Fails with a `ValidationError` if capacity is enforced and any of `members` is planned
+    /// beyond their capacity in the sprint. Members without recorded availability are not checked.
+    pub fn check_capacity_in(conn: &Connection, sprint_id: &str, members: &[String], config: &CapacityConfig) -> Result<()> {
+        if !config.enforce || members.is_empty() || !SprintRepository::exists(conn, sprint_id)? {
+            return Ok(());
+        }
+        let report = Self::report_in(conn, sprint_id, config.points_per_day)?;
+        let over: Vec<String> = report
+            .overcommitted()
+            .filter(|load| members.iter().any(|m| same_member(m, &load.member)))
+            .map(|load| format!("{} ({} of {} points)", load.member, load.load_points, load.capacity_points.unwrap_or(0.0)))
+            .collect();
+        if over.is_empty() {
+            return Ok(());
+        }
+        Err(Error::ValidationError(format!("Over capacity in sprint {}: {}", report.sprint_name, over.join(", "))))
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[async_trait]
+impl CapacityServiceTrait for CapacityService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection, config: CapacityConfig::default() }
+    }
+
+    async fn set_availability(
+        &self,
+        sprint_id: &str,
+        member: &str,
+        days_available: Option<f64>,
+        focus_factor: Option<f64>,
+        leave_days: Option<f64>,
+    ) -> Result<MemberAvailability> {
+        let (sprint_id, member) = (sprint_id.to_string(), member.trim().to_string());
+        if member.is_empty() {
+            return Err(Error::ValidationError("Member name cannot be empty".to_string()));
+        }
+        let default_focus = self.config.focus_factor;
+        self.db_connection.transaction(move |tx| {
+            if !SprintRepository::exists(tx, &sprint_id)? {
+                return Err(Error::NotFound(format!("Sprint {} not found", sprint_id)));
+            }
+            let mut availability = match CapacityRepository::get(tx, &sprint_id, &member)? {
+                Some(existing) => existing,
+                None => {
+                    let days = days_available.ok_or_else(|| {
+                        Error::ValidationError(format!("Give the days {} is available in the sprint", member))
+                    })?;
+                    MemberAvailability::new(sprint_id.clone(), member.clone(), days, default_focus, 0.0)
+                }
+            };
+            availability.member = member;
+            availability.days_available = days_available.unwrap_or(availability.days_available);
+            availability.focus_factor = focus_factor.unwrap_or(availability.focus_factor);
+            availability.leave_days = leave_days.unwrap_or(availability.leave_days);
+            availability.updated_at = chrono::Utc::now();
+
+            if ![availability.days_available, availability.focus_factor, availability.leave_days].iter().all(|v| v.is_finite()) {
+                return Err(Error::ValidationError("Days available, focus factor and leave must be finite numbers".to_string()));
+            }
+            if availability.days_available < 0.0 || availability.leave_days < 0.0 {
+                return Err(Error::ValidationError("Days available and leave cannot be negative".to_string()));
+            }
+            if availability.leave_days > availability.days_available {
+                return Err(Error::ValidationError(format!(
+                    "Leave ({} days) is more than the days available ({})",
+                    availability.leave_days, availability.days_available
+                )));
+            }
+            if !(availability.focus_factor > 0.0 && availability.focus_factor <= 1.0) {
+                return Err(Error::ValidationError(format!(
+                    "Focus factor must be more than 0 and at most 1, not {}",
+                    availability.focus_factor
+                )));
+            }
+            CapacityRepository::upsert(tx, &availability)?;
+            Ok(availability)
+        }).await
+    }
+
+    async fn remove_availability(&self, sprint_id: &str, member: &str) -> Result<()> {
+        let (sprint_id, member) = (sprint_id.to_string(), member.trim().to_string());
+        self.db_connection.with_connection(move |conn| {
+            if !CapacityRepository::delete(conn, &sprint_id, &member)? {
+                return Err(Error::NotFound(format!("No availability recorded for {} in sprint {}", member, sprint_id)));
+            }
+            Ok(())
+        }).await
+    }
+
+    async fn get_capacity(&self, sprint_id: &str) -> Result<CapacityReport> {
+        let sprint_id = sprint_id.to_string();
+        let points_per_day = self.config.points_per_day;
+        self.db_connection.with_connection(move |conn| Self::report_in(conn, &sprint_id, points_per_day)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::sprint::Sprint;
+    use crate::core::models::task::{Task, TaskType};
+    use crate::services::sprint_service::{SprintService, SprintServiceTrait};
+    use crate::services::task_service::{TaskService, TaskServiceTrait};
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_load_against_capacity_warns_or_rejects() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let sprint = SprintService::new(db.clone())
+            .create_sprint(Sprint::new("S1".to_string(), Utc::now(), Utc::now() + Duration::days(14)))
+            .await?;
+        let config = CapacityConfig { points_per_day: 1.0, focus_factor: 0.5, enforce: false };
+        let capacity = CapacityService::new(db.clone()).with_config(config.clone());
+
+        // 10 days less 2 of leave, at half focus: 4 points
+        let alice = capacity.set_availability(&sprint.id, "Alice", Some(10.0), None, Some(2.0)).await?;
+        assert_eq!((alice.focus_factor, alice.capacity_points(1.0)), (0.5, 4.0));
+        assert!(capacity.set_availability(&sprint.id, "bob", None, None, None).await.is_err());
+        assert!(capacity.set_availability(&sprint.id, "alice", None, Some(1.5), None).await.is_err());
+        for (days, leave) in [(f64::INFINITY, None), (f64::NAN, None), (10.0, Some(f64::NEG_INFINITY))] {
+            let result = capacity.set_availability(&sprint.id, "alice", Some(days), None, leave).await;
+            assert!(matches!(result, Err(Error::ValidationError(_))));
+        }
+
+        let tasks = TaskService::new(db.clone());
+        // Assignees match members however they are spaced or cased
+        for (id, points, assignee) in [("TASK-1", Some(3), "alice"), ("TASK-2", Some(2), " ALICE"), ("TASK-3", None, "Alice ")] {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskType::Task);
+            task.assignee = Some(assignee.to_string());
+            task.story_points = points;
+            tasks.create_task(task).await?;
+        }
+        tasks.bulk_assign_sprint(vec!["TASK-1".to_string(), "TASK-2".to_string(), "TASK-3".to_string()], sprint.id.clone(), None).await?;
+
+        let report = capacity.get_capacity(&sprint.id).await?;
+        let load = &report.members[0];
+        assert_eq!((load.member.as_str(), load.load_points, load.tasks, load.unestimated_tasks), ("Alice", 5, 3, 1));
+        assert_eq!(load.over_by(), Some(1.0));
+        assert_eq!(report.overcommitted().count(), 1);
+
+        let enforcing = TaskService::new(db.clone()).with_capacity(CapacityConfig { enforce: true, ..config });
+        let mut task = Task::new("TASK-4".to_string(), "TASK-4".to_string(), TaskType::Task);
+        task.assignee = Some("ALICE".to_string());
+        task.story_points = Some(1);
+        enforcing.create_task(task).await?;
+        let result = enforcing.bulk_assign_sprint(vec!["TASK-4".to_string()], sprint.id.clone(), None).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        assert_eq!(capacity.get_capacity(&sprint.id).await?.members[0].tasks, 3);
+
+        capacity.set_availability(&sprint.id, "alice", Some(20.0), None, None).await?;
+        enforcing.bulk_assign_sprint(vec!["TASK-4".to_string()], sprint.id.clone(), None).await?;
+        capacity.remove_availability(&sprint.id, " ALICE ").await?;
+        assert!(capacity.get_capacity(&sprint.id).await?.members[0].capacity_points.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+// llmdoc/src/services/comment_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::models::comment::Comment;
+use crate::core::repositories::CommentRepository;
+use crate::services::key_service::{KeyService, KeyServiceTrait};
+
+#[async_trait]
+pub trait CommentServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// Adds `comment` to the thread of the entity it names by ID or key, filling in the
+    /// entity type and ID. A reply must answer a comment on the same entity.
+    async fn add_comment(&self, comment: Comment) -> Result<Comment>;
+    /// The thread on one entity, oldest first.
+    async fn list_comments(&self, entity_id: &str) -> Result<Vec<Comment>>;
+}
+
+#[derive(Clone)]
+pub struct CommentService {
+    db_connection: Arc<DbConnection>,
+}
+
+#[async_trait]
+impl CommentServiceTrait for CommentService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn add_comment(&self, mut comment: Comment) -> Result<Comment> {
+        if comment.body.trim().is_empty() {
+            return Err(Error::ValidationError("Comment body cannot be empty".to_string()));
+        }
+        let entity = KeyService::new(self.db_connection.clone())
+            .find_any(&comment.entity_id, false)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Nothing with ID {} to comment on", comment.entity_id)))?;
+        comment.entity_type = entity.entity_type;
+        comment.entity_id = entity.id;
+        self.db_connection.transaction(move |tx| {
+            if let Some(reply_to) = &comment.reply_to {
+                let reply_to = CommentRepository::resolve_id(tx, reply_to, false)?.unwrap_or_else(|| reply_to.clone());
+                let parent = CommentRepository::get(tx, &reply_to)?
+                    .ok_or_else(|| Error::NotFound(format!("Comment {} not found", reply_to)))?;
+                if parent.entity_id != comment.entity_id {
+                    return Err(Error::ValidationError(format!(
+                        "Comment {} is on {}, not {}",
+                        reply_to, parent.entity_id, comment.entity_id
+                    )));
+                }
+                comment.reply_to = Some(reply_to);
+            }
+            CommentRepository::insert(tx, &comment)?;
+            Ok(comment)
+        }).await
+    }
+
+    async fn list_comments(&self, entity_id: &str) -> Result<Vec<Comment>> {
+        let entity_id = entity_id.to_string();
+        self.db_connection.with_connection(move |conn| CommentRepository::list_for_entity(conn, &entity_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::{Task, TaskType};
+    use crate::services::search_service::{SearchService, SearchServiceTrait};
+    use crate::services::task_service::{TaskService, TaskServiceTrait};
+
+    #[tokio::test]
+    async fn test_threads_are_attached_to_entities_and_searchable() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        TaskService::new(db.clone())
+            .create_task(Task::new("TASK-1".to_string(), "Commented".to_string(), TaskType::Task))
+            .await?;
+        TaskService::new(db.clone())
+            .create_task(Task::new("TASK-2".to_string(), "Other".to_string(), TaskType::Task))
+            .await?;
+        let service = CommentService::new(db.clone());
+
+        let first = service
+            .add_comment(Comment::new("TASK-1".to_string(), "Should we cache the **tokenizer**?".to_string(), Some("alice".to_string())))
+            .await?;
+        assert_eq!(first.entity_type, "task");
+        let mut reply = Comment::new("TASK-1".to_string(), "Yes, per worker".to_string(), Some("bob".to_string()));
+        reply.reply_to = Some(first.id.clone());
+        service.add_comment(reply).await?;
+
+        let mut stray = Comment::new("TASK-2".to_string(), "Wrong thread".to_string(), None);
+        stray.reply_to = Some(first.id.clone());
+        assert!(matches!(service.add_comment(stray).await, Err(Error::ValidationError(_))));
+        assert!(matches!(
+            service.add_comment(Comment::new("TASK-404".to_string(), "Hello".to_string(), None)).await,
+            Err(Error::NotFound(_))
+        ));
+        assert!(service.add_comment(Comment::new("TASK-1".to_string(), "  ".to_string(), None)).await.is_err());
+        assert_eq!(service.list_comments("TASK-1").await?.len(), 2);
+
+        let search = SearchService::new(db);
+        let found = search.search_comments("tokenizer", 10).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, first.id);
+        assert!(search.search_comments("\"unbalanced", 10).await?.is_empty());
+        Ok(())
+    }
+}
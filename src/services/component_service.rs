@@ -1,137 +1,68 @@
 // llmdoc/src/services/component_service.rs
 
-use crate::core::errors::Result;
+use crate::core::errors::{Error, Result};
 use async_trait::async_trait;
 use std::sync::Arc;
+use crate::app_config::KeysConfig;
 use crate::core::database::DbConnection;
 use crate::core::models::component::Component;
+use crate::core::repositories::Reference;
 
 #[async_trait]
 pub trait ComponentServiceTrait: Send + Sync {
     fn new(db_connection: Arc<DbConnection>) -> Self;
     async fn create_component(&self, component: Component) -> Result<Component>;
     async fn get_component_by_id(&self, id: &str) -> Result<Option<Component>>;
+    /// Saves `component` if the stored copy is still at `component.version`, returning it at the next version.
+    /// Fails with `Error::Conflict` if it was changed in the meantime.
     async fn update_component(&self, component: Component) -> Result<Component>;
-    async fn delete_component(&self, id: &str) -> Result<()>;
+    async fn delete_component(&self, id: &str) -> Result<Vec<Reference>>;
     async fn list_components(&self) -> Result<Vec<Component>>;
 }
 
 #[derive(Clone)]
 pub struct ComponentService {
     db_connection: Arc<DbConnection>,
+    key_prefix: String,
+}
+
+impl ComponentService {
+    /// Prefix for the keys of new components, instead of `COMP`.
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
 }
 
 #[async_trait]
 impl ComponentServiceTrait for ComponentService {
     fn new(db_connection: Arc<DbConnection>) -> Self {
-        Self { db_connection }
+        Self { db_connection, key_prefix: KeysConfig::default().component }
     }
 
     async fn create_component(&self, component: Component) -> Result<Component> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let component_json = serde_json::to_string(&component)?;
-        
-        conn.interact(move |conn| {
-            conn.execute(
-                "INSERT INTO components (component_id, data) VALUES (?1, ?2)",
-                rusqlite::params![&component.id, &component_json],
-            )?;
-            Ok(component)
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+        self.db_connection.insert_entity(&component, &self.key_prefix).await
     }
 
     async fn get_component_by_id(&self, id: &str) -> Result<Option<Component>> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let component_id = id.to_string();
-        
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare("SELECT data FROM components WHERE component_id = ?1")?;
-            let mut rows = stmt.query_map([&component_id], |row| {
-                let data: String = row.get(0)?;
-                Ok(data)
-            })?;
-            
-            if let Some(row) = rows.next() {
-                let data = row?;
-                let component: Component = serde_json::from_str(&data)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-                Ok(Some(component))
-            } else {
-                Ok(None)
-            }
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+        self.db_connection.get_entity(id).await
     }
 
     async fn update_component(&self, component: Component) -> Result<Component> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let component_json = serde_json::to_string(&component)?;
-        let component_id = component.id.clone();
-        
-        conn.interact(move |conn| {
-            conn.execute(
-                "UPDATE components SET data = ?1 WHERE component_id = ?2",
-                rusqlite::params![&component_json, &component_id],
-            )?;
-            Ok(component)
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+        self.db_connection
+            .update_entity(&component)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Component {} not found", component.id)))
     }
 
-    async fn delete_component(&self, id: &str) -> Result<()> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let component_id = id.to_string();
-        
-        conn.interact(move |conn| {
-            conn.execute(
-                "DELETE FROM components WHERE component_id = ?1",
-                rusqlite::params![&component_id],
-            )?;
-            Ok(())
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+    async fn delete_component(&self, id: &str) -> Result<Vec<Reference>> {
+        if !self.db_connection.delete_entity::<Component>(id).await? {
+            return Err(Error::NotFound(format!("Component {} not found", id)));
+        }
+        self.db_connection.references_to(id).await
     }
 
     async fn list_components(&self) -> Result<Vec<Component>> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare("SELECT data FROM components")?;
-            let component_iter = stmt.query_map([], |row| {
-                let data: String = row.get(0)?;
-                Ok(data)
-            })?;
-            
-            let mut components = Vec::new();
-            for component_result in component_iter {
-                let data = component_result?;
-                let component: Component = serde_json::from_str(&data)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-                components.push(component);
-            }
-            
-            Ok(components)
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+        self.db_connection.list_entities().await
     }
-}
\ No newline at end of file
+}
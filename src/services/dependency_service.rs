@@ -0,0 +1,122 @@
+// llmdoc/src/services/dependency_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use rusqlite::Connection;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::dependency_graph::{DependencyGraph, DependencyPlan, DependencyTree};
+use crate::core::models::task::{Task, TaskStatus};
+use crate::core::repositories::TaskRepository;
+
+/// Recorded as `updated_by` when a task is blocked or unblocked automatically.
+pub const DEPENDENCY_ACTOR: &str = "dependency tracking";
+
+/// Direct dependency relationships of one task.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskDependencies {
+    pub task_id: String,
+    pub blocked_by: Vec<String>, // Open tasks it depends on
+    pub blocks: Vec<String>,     // Tasks that depend on it
+}
+
+#[async_trait]
+pub trait DependencyServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    async fn get_dependencies(&self, task_id: &str) -> Result<TaskDependencies>;
+    async fn get_tree(&self, task_id: &str, reverse: bool) -> Result<DependencyTree>;
+    async fn get_plan(&self) -> Result<DependencyPlan>;
+    /// Open tasks that can be started now, i.e. the first wave of the plan.
+    async fn get_ready_tasks(&self) -> Result<Vec<Task>>;
+}
+
+#[derive(Clone)]
+pub struct DependencyService {
+    db_connection: Arc<DbConnection>,
+}
+
+impl DependencyService {
+    /// Fails with a `ValidationError` naming the cycle if `task`'s dependencies would create one.
+    pub fn check_cycles_in(conn: &Connection, task: &Task) -> Result<()> {
+        let graph = DependencyGraph::new(&TaskRepository::list(conn)?);
+        match graph.find_cycle(&task.id, &task.dependencies) {
+            Some(cycle) => Err(Error::ValidationError(format!("Dependency cycle: {}", cycle.join(" → ")))),
+            None => Ok(()),
+        }
+    }
+
+    /// Blocks or unblocks `changed` and the tasks depending on them to match the state of
+    /// their dependencies. Returns the tasks whose status was changed.
+    pub fn sync_blocking_in(conn: &Connection, changed: &[String]) -> Result<Vec<Task>> {
+        let graph = DependencyGraph::new(&TaskRepository::list(conn)?);
+        let mut candidates: BTreeSet<String> = changed.iter().cloned().collect();
+        for id in changed {
+            candidates.extend(graph.blocks(id));
+        }
+
+        let mut updated = Vec::new();
+        for id in candidates {
+            let Some(status) = graph.desired_status(&id) else {
+                continue;
+            };
+            if let Some(mut task) = TaskRepository::get(conn, &id)? {
+                let before = (status == TaskStatus::Blocked).then(|| task.status.clone());
+                task.set_status(status, Some(DEPENDENCY_ACTOR.to_string()));
+                task.status_before_block = before;
+                TaskRepository::update(conn, &mut task)?;
+                updated.push(task);
+            }
+        }
+        Ok(updated)
+    }
+}
+
+#[async_trait]
+impl DependencyServiceTrait for DependencyService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn get_dependencies(&self, task_id: &str) -> Result<TaskDependencies> {
+        let task_id = task_id.to_string();
+        self.db_connection.with_connection(move |conn| {
+            let graph = DependencyGraph::new(&TaskRepository::list(conn)?);
+            if !graph.contains(&task_id) {
+                return Err(Error::NotFound(format!("Task {} not found", task_id)));
+            }
+            Ok(TaskDependencies { blocked_by: graph.blocked_by(&task_id), blocks: graph.blocks(&task_id), task_id })
+        }).await
+    }
+
+    async fn get_tree(&self, task_id: &str, reverse: bool) -> Result<DependencyTree> {
+        let task_id = task_id.to_string();
+        self.db_connection.with_connection(move |conn| {
+            let graph = DependencyGraph::new(&TaskRepository::list(conn)?);
+            if !graph.contains(&task_id) {
+                return Err(Error::NotFound(format!("Task {} not found", task_id)));
+            }
+            Ok(graph.tree(&task_id, reverse))
+        }).await
+    }
+
+    async fn get_plan(&self) -> Result<DependencyPlan> {
+        self.db_connection.with_connection(|conn| Ok(DependencyGraph::new(&TaskRepository::list(conn)?).plan())).await
+    }
+
+    async fn get_ready_tasks(&self) -> Result<Vec<Task>> {
+        self.db_connection.with_connection(|conn| {
+            let tasks = TaskRepository::list(conn)?;
+            let plan = DependencyGraph::new(&tasks).plan();
+            let ready: BTreeSet<&String> = plan.waves.first().map(|wave| wave.iter().collect()).unwrap_or_default();
+            // Work already in progress, or blocked for reasons outside the graph, is not startable
+            let mut tasks: Vec<Task> = tasks
+                .into_iter()
+                .filter(|t| t.status == TaskStatus::Todo && ready.contains(&t.id))
+                .collect();
+            // Most urgent first
+            tasks.sort_by_key(|t| std::cmp::Reverse(t.priority.clone() as u8));
+            Ok(tasks)
+        }).await
+    }
+}
@@ -1,10 +1,15 @@
 // llmdoc/src/services/export_service.rs
 
 use crate::core::errors::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 use async_trait::async_trait;
 use crate::core::database::DbConnection;
+use crate::core::models::comment::Comment;
+use crate::core::models::history::HistoryEntry;
 use crate::core::models::task::Task; // Example model to export
+use crate::core::models::{Adr, Component, UserStory};
+use crate::core::repositories::{CommentRepository, HistoryRepository, SprintRepository, TaskRepository};
 use std::fs;
 use std::path::Path;
 
@@ -12,8 +17,9 @@ use std::path::Path;
 pub trait ExportServiceTrait: Send + Sync {
     fn new(db_connection: Arc<DbConnection>) -> Self;
     async fn export_tasks_to_markdown(&self, tasks: Vec<Task>, path: &str) -> Result<()>;
-    async fn export_all_tasks_to_markdown(&self, path: &str) -> Result<()>;
-    // Add other export methods as needed
+    async fn export_all_tasks_to_markdown(&self, path: &str, include_history: bool) -> Result<()>;
+    /// Writes every entity, and optionally the full change log, as a single JSON document.
+    async fn export_all_to_json(&self, path: &str, include_history: bool) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -28,79 +34,175 @@ impl ExportServiceTrait for ExportService {
     }
 
     async fn export_tasks_to_markdown(&self, tasks: Vec<Task>, path: &str) -> Result<()> {
-        let mut markdown_content = String::new();
-        markdown_content.push_str("# Tasks Export\n\n");
+        write_file(path, &render_tasks_markdown(&tasks, &HashMap::new(), None))
+    }
+    
+    async fn export_all_tasks_to_markdown(&self, path: &str, include_history: bool) -> Result<()> {
+        let (tasks, comments, history) = self.db_connection.with_connection(move |conn| {
+            let tasks = TaskRepository::list(conn)?;
+            let comments = CommentRepository::list_all(conn)?;
+            let history = if include_history {
+                Some(HistoryRepository::list_for_type(conn, "task")?)
+            } else {
+                None
+            };
+            Ok((tasks, comments, history))
+        }).await?;
         
-        for task in tasks {
-            markdown_content.push_str(&format!("## {}\n\n", task.title));
-            markdown_content.push_str(&format!("**ID:** {}\n\n", task.id));
-            markdown_content.push_str(&format!("**Status:** {:?}\n\n", task.status));
-            markdown_content.push_str(&format!("**Type:** {:?}\n\n", task.task_type));
-            markdown_content.push_str(&format!("**Priority:** {:?}\n\n", task.priority));
-            
-            if let Some(description) = &task.description {
-                markdown_content.push_str(&format!("**Description:** {}\n\n", description));
-            }
-            
-            if let Some(assignee) = &task.assignee {
-                markdown_content.push_str(&format!("**Assignee:** {}\n\n", assignee));
-            }
-            
-            if let Some(sprint_id) = &task.sprint_id {
-                markdown_content.push_str(&format!("**Sprint:** {}\n\n", sprint_id));
-            }
-            
-            if let Some(points) = task.story_points {
-                markdown_content.push_str(&format!("**Story Points:** {}\n\n", points));
-            }
-            
-            if !task.labels.is_empty() {
-                markdown_content.push_str(&format!("**Labels:** {}\n\n", task.labels.join(", ")));
+        let mut comments_by_entity: HashMap<String, Vec<Comment>> = HashMap::new();
+        for comment in comments {
+            comments_by_entity.entry(comment.entity_id.clone()).or_default().push(comment);
+        }
+        let history = history.map(group_by_entity);
+        write_file(path, &render_tasks_markdown(&tasks, &comments_by_entity, history.as_ref()))
+    }
+
+    async fn export_all_to_json(&self, path: &str, include_history: bool) -> Result<()> {
+        let adrs = self.db_connection.list_entities::<Adr>().await?;
+        let components = self.db_connection.list_entities::<Component>().await?;
+        let user_stories = self.db_connection.list_entities::<UserStory>().await?;
+        let document = self.db_connection.with_connection(move |conn| {
+            let mut document = serde_json::json!({
+                "exported_at": crate::utils::format_datetime(chrono::Utc::now()),
+                "tasks": TaskRepository::list(conn)?,
+                "sprints": SprintRepository::list(conn)?,
+                "adrs": adrs,
+                "components": components,
+                "user_stories": user_stories,
+                "comments": CommentRepository::list_all(conn)?,
+            });
+            if include_history {
+                let history = HistoryRepository::query_all(conn)?;
+                document["history"] = serde_json::to_value(history)?;
             }
-            
-            markdown_content.push_str(&format!("**Created:** {}\n\n", task.created_at.format("%Y-%m-%d %H:%M:%S")));
-            markdown_content.push_str(&format!("**Updated:** {}\n\n", task.updated_at.format("%Y-%m-%d %H:%M:%S")));
-            
-            markdown_content.push_str("---\n\n");
+            Ok(document)
+        }).await?;
+
+        write_file(path, &serde_json::to_string_pretty(&document)?)
+    }
+}
+
+/// Groups change log entries by entity ID, keeping their order.
+fn group_by_entity(history: Vec<HistoryEntry>) -> HashMap<String, Vec<HistoryEntry>> {
+    let mut grouped: HashMap<String, Vec<HistoryEntry>> = HashMap::new();
+    for entry in history {
+        grouped.entry(entry.entity_id.clone()).or_default().push(entry);
+    }
+    grouped
+}
+
+fn render_tasks_markdown(
+    tasks: &[Task],
+    comments: &HashMap<String, Vec<Comment>>,
+    history: Option<&HashMap<String, Vec<HistoryEntry>>>,
+) -> String {
+    let mut markdown_content = String::new();
+    markdown_content.push_str("# Tasks Export\n\n");
+    
+    for task in tasks {
+        markdown_content.push_str(&format!("## {}\n\n", task.title));
+        markdown_content.push_str(&format!("**ID:** {}\n\n", task.id));
+        markdown_content.push_str(&format!("**Status:** {:?}\n\n", task.status));
+        markdown_content.push_str(&format!("**Type:** {:?}\n\n", task.task_type));
+        markdown_content.push_str(&format!("**Priority:** {:?}\n\n", task.priority));
+        
+        if let Some(description) = &task.description {
+            markdown_content.push_str(&format!("**Description:** {}\n\n", description));
+        }
+        
+        if let Some(assignee) = &task.assignee {
+            markdown_content.push_str(&format!("**Assignee:** {}\n\n", assignee));
+        }
+        
+        if let Some(sprint_id) = &task.sprint_id {
+            markdown_content.push_str(&format!("**Sprint:** {}\n\n", sprint_id));
+        }
+        
+        if let Some(points) = task.story_points {
+            markdown_content.push_str(&format!("**Story Points:** {}\n\n", points));
+        }
+        
+        if !task.labels.is_empty() {
+            markdown_content.push_str(&format!("**Labels:** {}\n\n", task.labels.join(", ")));
+        }
+        
+        markdown_content.push_str(&format!("**Created:** {}\n\n", task.created_at.format("%Y-%m-%d %H:%M:%S")));
+        markdown_content.push_str(&format!("**Updated:** {}\n\n", task.updated_at.format("%Y-%m-%d %H:%M:%S")));
+        
+        if let Some(comments) = comments.get(&task.id) {
+            markdown_content.push_str(&render_comments_markdown(comments));
         }
         
-        // Ensure the directory exists
-        if let Some(parent) = Path::new(path).parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| crate::core::errors::Error::IoError(e))?;
+        if let Some(history) = history {
+            markdown_content.push_str(&render_history_markdown(history.get(&task.id).map_or(&[], Vec::as_slice)));
         }
         
-        fs::write(path, markdown_content)
-            .map_err(|e| crate::core::errors::Error::IoError(e))?;
-            
-        Ok(())
+        markdown_content.push_str("---\n\n");
     }
     
-    async fn export_all_tasks_to_markdown(&self, path: &str) -> Result<()> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let tasks = conn.interact(move |conn| {
-            let mut stmt = conn.prepare("SELECT data FROM tasks")?;
-            let task_iter = stmt.query_map([], |row| {
-                let data: String = row.get(0)?;
-                Ok(data)
-            })?;
-            
-            let mut tasks = Vec::new();
-            for task_result in task_iter {
-                let data = task_result?;
-                let task: Task = serde_json::from_str(&data)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-                tasks.push(task);
+    markdown_content
+}
+
+fn render_comments_markdown(comments: &[Comment]) -> String {
+    let mut markdown_content = String::from("### Comments\n\n");
+    for comment in comments {
+        let reply = comment.reply_to.as_ref().map(|r| format!(" (reply to `{}`)", r)).unwrap_or_default();
+        markdown_content.push_str(&format!(
+            "- **{}** {} `{}`{}\n\n",
+            comment.author.as_deref().unwrap_or("unknown"),
+            comment.created_at.format("%Y-%m-%d %H:%M:%S"),
+            comment.id,
+            reply
+        ));
+        for line in comment.body.lines() {
+            // Indent the body under the list item so markdown keeps it inside the entry
+            if line.trim().is_empty() {
+                markdown_content.push('\n');
+            } else {
+                markdown_content.push_str(&format!("  {}\n", line));
             }
-            
-            Ok(tasks)
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))?;
-        
-        self.export_tasks_to_markdown(tasks, path).await
+        }
+        markdown_content.push('\n');
     }
+    markdown_content
+}
+
+fn render_history_markdown(history: &[HistoryEntry]) -> String {
+    let mut markdown_content = String::from("### History\n\n");
+    if history.is_empty() {
+        markdown_content.push_str("_No recorded changes._\n\n");
+        return markdown_content;
+    }
+    
+    let mut previous_version = None;
+    for entry in history {
+        if previous_version != Some(entry.version) {
+            markdown_content.push_str(&format!(
+                "- **v{}** {} {} by {}\n",
+                entry.version,
+                entry.changed_at.format("%Y-%m-%d %H:%M:%S"),
+                entry.change_type.to_string().to_lowercase(),
+                entry.changed_by.as_deref().unwrap_or("unknown")
+            ));
+            previous_version = Some(entry.version);
+        }
+        if entry.field.is_some() {
+            markdown_content.push_str(&format!("  - `{}`\n", entry.summary()));
+        }
+    }
+    markdown_content.push('\n');
+    markdown_content
+}
+
+fn write_file(path: &str, content: &str) -> Result<()> {
+    // Ensure the directory exists
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(crate::core::errors::Error::IoError)?;
+    }
+    
+    fs::write(path, content)
+        .map_err(crate::core::errors::Error::IoError)?;
+        
+    Ok(())
 }
\ No newline at end of file
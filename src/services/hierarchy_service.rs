@@ -0,0 +1,56 @@
+// llmdoc/src/services/hierarchy_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use rusqlite::Connection;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::models::task::Task;
+use crate::core::repositories::TaskRepository;
+use crate::core::task_hierarchy::{TaskHierarchy, TaskTree};
+
+#[async_trait]
+pub trait HierarchyServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// `task_id` and its descendants, with story points and progress rolled up.
+    async fn get_tree(&self, task_id: &str) -> Result<TaskTree>;
+    /// Every top-level task with its descendants.
+    async fn get_forest(&self) -> Result<Vec<TaskTree>>;
+}
+
+#[derive(Clone)]
+pub struct HierarchyService {
+    db_connection: Arc<DbConnection>,
+}
+
+impl HierarchyService {
+    /// Fails with a `ValidationError` if `task`'s parent or type breaks the Epic > Story > Task rules.
+    pub fn check_parent_in(conn: &Connection, task: &Task) -> Result<()> {
+        if task.parent_id.is_none() && TaskRepository::list_children(conn, &task.id)?.is_empty() {
+            return Ok(());
+        }
+        TaskHierarchy::new(&TaskRepository::list(conn)?)
+            .check(task)
+            .map_err(Error::ValidationError)
+    }
+}
+
+#[async_trait]
+impl HierarchyServiceTrait for HierarchyService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn get_tree(&self, task_id: &str) -> Result<TaskTree> {
+        let task_id = task_id.to_string();
+        self.db_connection.with_connection(move |conn| {
+            TaskHierarchy::new(&TaskRepository::list(conn)?)
+                .tree(&task_id)
+                .ok_or_else(|| Error::NotFound(format!("Task {} not found", task_id)))
+        }).await
+    }
+
+    async fn get_forest(&self) -> Result<Vec<TaskTree>> {
+        self.db_connection.with_connection(|conn| Ok(TaskHierarchy::new(&TaskRepository::list(conn)?).roots())).await
+    }
+}
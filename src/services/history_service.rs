@@ -0,0 +1,38 @@
+// llmdoc/src/services/history_service.rs
+
+use crate::core::errors::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::models::history::HistoryEntry;
+use crate::core::repositories::HistoryRepository;
+
+#[async_trait]
+pub trait HistoryServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    async fn get_history(&self, entity_id: &str) -> Result<Vec<HistoryEntry>>;
+    async fn get_history_for_type(&self, entity_type: &str) -> Result<Vec<HistoryEntry>>;
+}
+
+/// Read access to the change log. Entries are written by the repositories themselves.
+#[derive(Clone)]
+pub struct HistoryService {
+    db_connection: Arc<DbConnection>,
+}
+
+#[async_trait]
+impl HistoryServiceTrait for HistoryService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn get_history(&self, entity_id: &str) -> Result<Vec<HistoryEntry>> {
+        let entity_id = entity_id.to_string();
+        self.db_connection.with_connection(move |conn| HistoryRepository::list_for_entity(conn, &entity_id)).await
+    }
+
+    async fn get_history_for_type(&self, entity_type: &str) -> Result<Vec<HistoryEntry>> {
+        let entity_type = entity_type.to_string();
+        self.db_connection.with_connection(move |conn| HistoryRepository::list_for_type(conn, &entity_type)).await
+    }
+}
@@ -0,0 +1,82 @@
+// llmdoc/src/services/key_service.rs
+
+use crate::core::errors::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::repositories::key_repository::{ambiguous, LOOKUPS};
+use crate::core::repositories::{KeyRepository, Reference};
+use crate::core::store::{self, STORED_KINDS};
+
+#[async_trait]
+pub trait KeyServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// The ID of the `entity_type` entity that `input` names by ID, key or unique UUID prefix.
+    /// Input that names nothing is returned unchanged, so it is reported like any unknown ID.
+    async fn resolve(&self, entity_type: &str, input: &str) -> Result<String>;
+    async fn resolve_all(&self, entity_type: &str, inputs: Vec<String>) -> Result<Vec<String>>;
+    /// Like `resolve`, across every entity type and including deleted entities.
+    async fn resolve_any(&self, input: &str) -> Result<String>;
+    /// The type and ID of whatever `input` names, searching every entity type that has keys.
+    /// An exact ID anywhere beats a key, and a key beats a UUID prefix.
+    async fn find_any(&self, input: &str, include_deleted: bool) -> Result<Option<Reference>>;
+}
+
+#[derive(Clone)]
+pub struct KeyService {
+    db_connection: Arc<DbConnection>,
+}
+
+#[async_trait]
+impl KeyServiceTrait for KeyService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn resolve(&self, entity_type: &str, input: &str) -> Result<String> {
+        self.resolve_all(entity_type, vec![input.to_string()]).await.map(|mut ids| ids.remove(0))
+    }
+
+    async fn resolve_all(&self, entity_type: &str, inputs: Vec<String>) -> Result<Vec<String>> {
+        if let Some(kind) = store::stored_kind(entity_type) {
+            let mut ids = Vec::with_capacity(inputs.len());
+            for input in inputs {
+                ids.push(self.db_connection.resolve_stored(kind, &input, false).await?.unwrap_or(input));
+            }
+            return Ok(ids);
+        }
+        let entity_type = entity_type.to_string();
+        self.db_connection.with_connection(move |conn| {
+            inputs
+                .into_iter()
+                .map(|input| Ok(KeyRepository::resolve(conn, &entity_type, &input, false)?.unwrap_or(input)))
+                .collect()
+        }).await
+    }
+
+    async fn resolve_any(&self, input: &str) -> Result<String> {
+        Ok(self.find_any(input, true).await?.map_or_else(|| input.to_string(), |reference| reference.id))
+    }
+
+    async fn find_any(&self, input: &str, include_deleted: bool) -> Result<Option<Reference>> {
+        for lookup in LOOKUPS {
+            let owned = input.to_string();
+            let mut found = self
+                .db_connection
+                .with_connection(move |conn| KeyRepository::find_in_tables(conn, lookup, &owned, include_deleted))
+                .await?;
+            for kind in STORED_KINDS {
+                for id in self.db_connection.store().find_ids(kind, lookup, input, include_deleted).await? {
+                    found.push(Reference { entity_type: kind.entity_type.to_string(), id });
+                }
+            }
+            if found.len() > 1 {
+                return Err(ambiguous(input, found.iter().map(ToString::to_string)));
+            }
+            if let Some(reference) = found.pop() {
+                return Ok(Some(reference));
+            }
+        }
+        Ok(None)
+    }
+}
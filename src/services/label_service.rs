@@ -0,0 +1,329 @@
+// llmdoc/src/services/label_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::dependency_graph::is_finished;
+use crate::core::models::label::Label;
+use crate::core::models::task::Task;
+use crate::core::models::validation::{is_valid_color, is_valid_label, LABEL_COLORS};
+use crate::core::repositories::{LabelRepository, TaskRepository};
+
+/// How many live tasks use a label, as listed by `label stats`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LabelStats {
+    pub name: String,            // As spelled on the tasks
+    pub registered: bool,
+    pub color: Option<String>,
+    pub tasks: u32,
+    pub open: u32,
+    pub closed: u32,             // Done or cancelled
+}
+
+#[async_trait]
+pub trait LabelServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    async fn add_label(&self, label: Label) -> Result<Label>;
+    /// Changes the description and color of a registered label; `None` leaves a field as is.
+    async fn edit_label(&self, name: &str, description: Option<String>, color: Option<String>) -> Result<Label>;
+    /// Unregisters a label, leaving tasks alone. Returns how many tasks still carry it.
+    async fn remove_label(&self, name: &str) -> Result<usize>;
+    async fn list_labels(&self) -> Result<Vec<Label>>;
+    /// Renames a label in the registry and on every task, all or nothing. Returns the changed tasks.
+    async fn rename_label(&self, from: &str, to: &str, actor: Option<String>) -> Result<Vec<Task>>;
+    /// Replaces each of `sources` with `into` in the registry and on every task, all or nothing.
+    /// Returns the changed tasks.
+    async fn merge_labels(&self, sources: Vec<String>, into: &str, actor: Option<String>) -> Result<Vec<Task>>;
+    /// Usage of every registered label and every label found on a task, most used first.
+    async fn label_stats(&self) -> Result<Vec<LabelStats>>;
+}
+
+#[derive(Clone)]
+pub struct LabelService {
+    db_connection: Arc<DbConnection>,
+}
+
+impl LabelService {
+    /// Spells `task`'s labels as registered and drops duplicates that differ only in case.
+    /// With `strict`, fails with a `ValidationError` if any label is not registered.
+    pub fn check_labels_in(conn: &Connection, task: &mut Task, strict: bool) -> Result<()> {
+        let mut labels: Vec<String> = Vec::new();
+        let mut unknown = Vec::new();
+        for label in std::mem::take(&mut task.labels) {
+            let label = match LabelRepository::get(conn, &label)? {
+                Some(registered) => registered.name,
+                None => {
+                    unknown.push(label.clone());
+                    label
+                }
+            };
+            if !labels.iter().any(|l| l.eq_ignore_ascii_case(&label)) {
+                labels.push(label);
+            }
+        }
+        if strict && !unknown.is_empty() {
+            return Err(Error::ValidationError(format!(
+                "Unknown label(s) {}; register them with `label add` first",
+                unknown.join(", ")
+            )));
+        }
+        task.labels = labels;
+        Ok(())
+    }
+
+    /// Replaces any of `sources` on every task with `target`. Returns the changed tasks.
+    fn relabel_in(conn: &Connection, sources: &[String], target: &str, actor: Option<String>) -> Result<Vec<Task>> {
+        let mut ids: Vec<String> = Vec::new();
+        for source in sources {
+            for task in TaskRepository::list_with_label(conn, source)? {
+                if !ids.contains(&task.id) {
+                    ids.push(task.id);
+                }
+            }
+        }
+        let mut changed = Vec::new();
+        for id in ids {
+            let Some(mut task) = TaskRepository::get(conn, &id)? else {
+                continue;
+            };
+            let mut labels: Vec<String> = Vec::new();
+            for label in &task.labels {
+                let label = if sources.iter().any(|s| s.eq_ignore_ascii_case(label)) { target } else { label };
+                if !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                    labels.push(label.to_string());
+                }
+            }
+            if labels != task.labels {
+                task.labels = labels;
+                task.updated_at = chrono::Utc::now();
+                task.updated_by = actor.clone();
+                TaskRepository::update(conn, &mut task)?;
+                changed.push(task);
+            }
+        }
+        Ok(changed)
+    }
+
+    fn is_known_in(conn: &Connection, name: &str) -> Result<bool> {
+        Ok(LabelRepository::get(conn, name)?.is_some() || !TaskRepository::list_with_label(conn, name)?.is_empty())
+    }
+}
+
+fn check_name(name: &str) -> Result<String> {
+    if !is_valid_label(name) {
+        return Err(Error::ValidationError(format!("Invalid label '{}': must not be blank or contain commas", name)));
+    }
+    Ok(name.trim().to_string())
+}
+
+fn check_color(color: Option<String>) -> Result<Option<String>> {
+    match color {
+        Some(color) if !is_valid_color(&color) => Err(Error::ValidationError(format!(
+            "Invalid color '{}': use #rrggbb or one of {}",
+            color,
+            LABEL_COLORS.join(", ")
+        ))),
+        color => Ok(color.map(|c| c.to_lowercase())),
+    }
+}
+
+#[async_trait]
+impl LabelServiceTrait for LabelService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn add_label(&self, mut label: Label) -> Result<Label> {
+        label.name = check_name(&label.name)?;
+        label.color = check_color(label.color)?;
+        self.db_connection.transaction(move |tx| {
+            if let Some(existing) = LabelRepository::get(tx, &label.name)? {
+                return Err(Error::ValidationError(format!("Label {} already exists", existing.name)));
+            }
+            LabelRepository::insert(tx, &label)?;
+            Ok(label)
+        }).await
+    }
+
+    async fn edit_label(&self, name: &str, description: Option<String>, color: Option<String>) -> Result<Label> {
+        let name = name.to_string();
+        let color = check_color(color)?;
+        self.db_connection.transaction(move |tx| {
+            let mut label = LabelRepository::get(tx, &name)?
+                .ok_or_else(|| Error::NotFound(format!("Label {} not found", name)))?;
+            if description.is_some() {
+                label.description = description;
+            }
+            if color.is_some() {
+                label.color = color;
+            }
+            label.updated_at = chrono::Utc::now();
+            LabelRepository::update(tx, &name, &label)?;
+            Ok(label)
+        }).await
+    }
+
+    async fn remove_label(&self, name: &str) -> Result<usize> {
+        let name = name.to_string();
+        self.db_connection.transaction(move |tx| {
+            if !LabelRepository::delete(tx, &name)? {
+                return Err(Error::NotFound(format!("Label {} not found", name)));
+            }
+            Ok(TaskRepository::list_with_label(tx, &name)?.len())
+        }).await
+    }
+
+    async fn list_labels(&self) -> Result<Vec<Label>> {
+        self.db_connection.with_connection(|conn| LabelRepository::list(conn)).await
+    }
+
+    async fn rename_label(&self, from: &str, to: &str, actor: Option<String>) -> Result<Vec<Task>> {
+        let from = from.to_string();
+        let to = check_name(to)?;
+        self.db_connection.transaction(move |tx| {
+            if !Self::is_known_in(tx, &from)? {
+                return Err(Error::NotFound(format!("Label {} not found", from)));
+            }
+            if !to.eq_ignore_ascii_case(&from) {
+                if let Some(existing) = LabelRepository::get(tx, &to)? {
+                    return Err(Error::ValidationError(format!(
+                        "Label {} already exists; use `label merge` to combine the two",
+                        existing.name
+                    )));
+                }
+            }
+            if let Some(mut label) = LabelRepository::get(tx, &from)? {
+                label.name = to.clone();
+                label.updated_at = chrono::Utc::now();
+                LabelRepository::update(tx, &from, &label)?;
+            }
+            Self::relabel_in(tx, std::slice::from_ref(&from), &to, actor)
+        }).await
+    }
+
+    async fn merge_labels(&self, sources: Vec<String>, into: &str, actor: Option<String>) -> Result<Vec<Task>> {
+        let into = check_name(into)?;
+        if sources.is_empty() {
+            return Err(Error::ValidationError("Name at least one label to merge".to_string()));
+        }
+        self.db_connection.transaction(move |tx| {
+            let mut inherited = None;
+            for source in &sources {
+                if !Self::is_known_in(tx, source)? {
+                    return Err(Error::NotFound(format!("Label {} not found", source)));
+                }
+                if source.eq_ignore_ascii_case(&into) {
+                    continue;
+                }
+                if let Some(label) = LabelRepository::get(tx, source)? {
+                    LabelRepository::delete(tx, &label.name)?;
+                    inherited.get_or_insert(label);
+                }
+            }
+            let target = match LabelRepository::get(tx, &into)? {
+                Some(label) => label.name,
+                None => {
+                    // The merged label keeps the registration of the first registered source
+                    if let Some(source) = inherited {
+                        LabelRepository::insert(tx, &Label::new(into.clone(), source.description, source.color))?;
+                    }
+                    into
+                }
+            };
+            Self::relabel_in(tx, &sources, &target, actor)
+        }).await
+    }
+
+    async fn label_stats(&self) -> Result<Vec<LabelStats>> {
+        self.db_connection.with_connection(|conn| {
+            let mut stats: BTreeMap<String, LabelStats> = BTreeMap::new();
+            for label in LabelRepository::list(conn)? {
+                stats.insert(label.name.clone(), LabelStats {
+                    name: label.name,
+                    registered: true,
+                    color: label.color,
+                    tasks: 0,
+                    open: 0,
+                    closed: 0,
+                });
+            }
+            for task in TaskRepository::list(conn)? {
+                for label in &task.labels {
+                    let entry = stats.entry(label.clone()).or_insert_with(|| LabelStats {
+                        name: label.clone(),
+                        registered: false,
+                        color: None,
+                        tasks: 0,
+                        open: 0,
+                        closed: 0,
+                    });
+                    entry.tasks += 1;
+                    if is_finished(&task.status) {
+                        entry.closed += 1;
+                    } else {
+                        entry.open += 1;
+                    }
+                }
+            }
+            let mut stats: Vec<LabelStats> = stats.into_values().collect();
+            stats.sort_by(|a, b| b.tasks.cmp(&a.tasks).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+            Ok(stats)
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::{TaskStatus, TaskType};
+    use crate::services::task_service::{TaskService, TaskServiceTrait};
+
+    #[tokio::test]
+    async fn test_labels_are_registered_renamed_and_merged_on_every_task() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let tasks = TaskService::new(db.clone());
+        let service = LabelService::new(db.clone());
+        let labelled = |id: &str, labels: &[&str]| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskType::Task);
+            task.labels = labels.iter().map(|l| l.to_string()).collect();
+            task
+        };
+
+        service.add_label(Label::new("backend".to_string(), Some("Server side".to_string()), Some("Blue".to_string()))).await?;
+        assert!(service.add_label(Label::new("Backend".to_string(), None, None)).await.is_err());
+        assert!(service.add_label(Label::new("ui".to_string(), None, Some("pink".to_string()))).await.is_err());
+
+        let created = tasks.create_task(labelled("TASK-1", &["Backend", "api", "BACKEND"])).await?;
+        assert_eq!(created.labels, vec!["backend".to_string(), "api".to_string()]);
+        tasks.create_task(labelled("TASK-2", &["be", "api"])).await?;
+        tasks.create_task(labelled("TASK-3", &["server"])).await?;
+        tasks.bulk_update_status(vec!["TASK-3".to_string()], TaskStatus::Done, None).await?;
+
+        let strict = TaskService::new(db.clone()).with_strict_labels(true);
+        assert!(matches!(strict.create_task(labelled("TASK-4", &["unknown"])).await, Err(Error::ValidationError(_))));
+        assert_eq!(strict.create_task(labelled("TASK-4", &["BACKEND"])).await?.labels, vec!["backend".to_string()]);
+
+        let merged = service.merge_labels(vec!["be".to_string(), "server".to_string()], "Backend", None).await?;
+        assert_eq!(merged.len(), 2);
+        assert!(matches!(service.merge_labels(vec!["nope".to_string()], "backend", None).await, Err(Error::NotFound(_))));
+
+        let renamed = service.rename_label("backend", "server-side", Some("alice".to_string())).await?;
+        assert_eq!(renamed.len(), 4);
+        assert_eq!(renamed[0].updated_by.as_deref(), Some("alice"));
+        assert!(service.rename_label("api", "server-side", None).await.is_err());
+        let label = service.list_labels().await?.pop().unwrap();
+        assert_eq!((label.name.as_str(), label.color.as_deref()), ("server-side", Some("blue")));
+
+        let stats = service.label_stats().await?;
+        let row = |name: &str| stats.iter().find(|s| s.name == name).cloned().unwrap();
+        assert_eq!((row("server-side").tasks, row("server-side").open, row("server-side").closed), (4, 3, 1));
+        assert!(row("server-side").registered && !row("api").registered);
+        assert_eq!(stats.len(), 2);
+        Ok(())
+    }
+}
@@ -7,7 +7,18 @@ pub mod component_service;
 pub mod adr_service;
 pub mod user_story_service;
 pub mod search_service;
+pub mod stats_service;
+pub mod trash_service;
 pub mod export_service;
+pub mod history_service;
+pub mod dependency_service;
+pub mod hierarchy_service;
+pub mod time_tracking_service;
+pub mod comment_service;
+pub mod key_service;
+pub mod template_service;
+pub mod label_service;
+pub mod capacity_service;
 
 pub use sprint_service::SprintService;
 pub use task_service::TaskService;
@@ -15,7 +26,18 @@ pub use component_service::ComponentService;
 pub use adr_service::AdrService;
 pub use user_story_service::UserStoryService;
 pub use search_service::SearchService;
+pub use stats_service::StatsService;
+pub use trash_service::TrashService;
 pub use export_service::ExportService;
+pub use history_service::HistoryService;
+pub use dependency_service::DependencyService;
+pub use hierarchy_service::HierarchyService;
+pub use time_tracking_service::TimeTrackingService;
+pub use comment_service::CommentService;
+pub use key_service::KeyService;
+pub use template_service::TemplateService;
+pub use label_service::LabelService;
+pub use capacity_service::CapacityService;
 
 pub fn services_init_message() {
     tracing::debug!("Services module initialized (placeholder).");
@@ -4,12 +4,16 @@ use crate::core::errors::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use crate::core::database::DbConnection;
+use crate::core::models::comment::Comment;
 use crate::core::models::task::Task; // Example model to search
+use crate::core::repositories::{CommentRepository, TaskRepository};
 
 #[async_trait]
 pub trait SearchServiceTrait: Send + Sync {
     fn new(db_connection: Arc<DbConnection>) -> Self;
     async fn search_tasks(&self, query: &str) -> Result<Vec<Task>>;
+    /// Comments whose body contains every word of `query`, best match first.
+    async fn search_comments(&self, query: &str, limit: usize) -> Result<Vec<Comment>>;
     // Add other search methods as needed
 }
 
@@ -25,32 +29,12 @@ impl SearchServiceTrait for SearchService {
     }
 
     async fn search_tasks(&self, query: &str) -> Result<Vec<Task>> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let search_query = format!("%{}%", query);
-        
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT data FROM tasks WHERE data LIKE ?1"
-            )?;
-            let task_iter = stmt.query_map([&search_query], |row| {
-                let data: String = row.get(0)?;
-                Ok(data)
-            })?;
-            
-            let mut tasks = Vec::new();
-            for task_result in task_iter {
-                let data = task_result?;
-                let task: Task = serde_json::from_str(&data)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-                tasks.push(task);
-            }
-            
-            Ok(tasks)
-        })
-        .await
-        .map_err(|e| crate::core::errors::Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| crate::core::errors::Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+        let query = query.to_string();
+        self.db_connection.with_connection(move |conn| TaskRepository::search_text(conn, &query)).await
     }
-}
\ No newline at end of file
+
+    async fn search_comments(&self, query: &str, limit: usize) -> Result<Vec<Comment>> {
+        let query = query.to_string();
+        self.db_connection.with_connection(move |conn| CommentRepository::search(conn, &query, limit)).await
+    }
+}
@@ -2,35 +2,247 @@ use serde::Serialize;
 // llmdoc/src/services/sprint_service.rs
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{Days, NaiveDate, Utc};
 use std::sync::Arc;
+use crate::app_config::{CapacityConfig, KeysConfig, SprintsConfig};
 use crate::core::database::DbConnection;
 use crate::core::errors::{Error, Result};
-use crate::core::models::sprint::{Sprint, SprintStatus, Retrospective};
+use crate::core::burndown::{self, Burndown};
+use crate::core::dependency_graph::is_finished;
+use crate::core::forecast::{Forecast, ForecastTarget, SprintVelocity, VelocityReport};
+use crate::core::models::sprint::{CarryOver, Sprint, SprintStatus, Retrospective};
+use crate::core::models::task::{Task, TaskStatus};
+use crate::core::repositories::{HistoryRepository, Reference, SprintRepository, TaskRepository};
+use crate::core::task_hierarchy::{TaskHierarchy, TaskTree};
+use rusqlite::Connection;
+use crate::services::capacity_service::CapacityService;
+use crate::services::task_service::TaskService;
+use crate::services::time_tracking_service::TimeTrackingService;
 use tracing::{debug, instrument};
-use std::str::FromStr;
+
+/// Recorded as `updated_by` on tasks moved on when their sprint is completed.
+pub const CARRY_OVER_ACTOR: &str = "sprint carry-over";
 
 #[async_trait]
 pub trait SprintServiceTrait: Send + Sync {
+    /// Fails with a `ValidationError` if the sprint ends before it starts or overlaps another.
     async fn create_sprint(&self, sprint: Sprint) -> Result<Sprint>;
+    /// Creates back-to-back sprints as `plan` describes, all or none.
+    async fn plan_sprints(&self, plan: SprintPlan) -> Result<Vec<Sprint>>;
     async fn get_sprint_by_id(&self, id: &str) -> Result<Option<Sprint>>;
+    /// Saves `sprint` if the stored copy is still at `sprint.version`, returning it at the next version.
+    /// Fails with `Error::Conflict` if it was changed in the meantime, and with a `ValidationError`
+    /// if it would overlap another sprint or make a second sprint active.
     async fn update_sprint(&self, sprint: Sprint) -> Result<Sprint>;
-    async fn delete_sprint(&self, id: &str) -> Result<()>;
+    async fn delete_sprint(&self, id: &str) -> Result<Vec<Reference>>;
     async fn list_sprints(&self, status: Option<SprintStatus>) -> Result<Vec<Sprint>>;
     async fn get_current_sprint(&self) -> Result<Option<Sprint>>;
+    /// Fails with a `ValidationError` if another sprint is active.
     async fn start_sprint(&self, sprint_id: &str) -> Result<Sprint>;
-    async fn complete_sprint(&self, sprint_id: &str, retrospective: Option<Retrospective>) -> Result<Sprint>;
+    /// Completes the sprint, moving its unfinished tasks as `carry_to` says.
+    /// Fails with a `ValidationError` if capacity is enforced and they would take an assignee
+    /// over capacity in the destination sprint.
+    async fn complete_sprint(&self, sprint_id: &str, retrospective: Option<Retrospective>, carry_to: CarryOverTarget) -> Result<Sprint>;
     async fn get_sprint_report(&self, sprint_id: &str) -> Result<SprintReport>;
+    /// Velocity of completed sprints by end date, only the `last` few if given, with rolling
+    /// averages over `window` sprints.
+    async fn get_velocity(&self, last: Option<usize>, window: usize) -> Result<VelocityReport>;
+    /// How many sprints the open work of `target` may take, from `runs` simulations drawing on
+    /// the velocities of the `last` completed sprints (all of them if `None`).
+    async fn forecast(&self, target: ForecastTarget, last: Option<usize>, runs: u32, seed: Option<u64>) -> Result<Forecast>;
+}
+
+/// Where `complete_sprint` moves unfinished tasks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CarryOverTarget {
+    /// Leave them in the completed sprint.
+    Keep,
+    /// Take them out of any sprint.
+    Backlog,
+    /// The planned sprint starting soonest after the completed one.
+    Next,
+    Sprint(String),
+}
+
+/// A run of sprints of equal length, created by `plan_sprints`.
+#[derive(Debug, Clone)]
+pub struct SprintPlan {
+    pub start: Option<NaiveDate>, // Defaults to the day after the last sprint ends, or today
+    pub cadence_days: u32,
+    pub count: u32,
+    pub name_pattern: String,     // `{n}` is replaced with the sprint's number
+    pub first_number: Option<u32>, // Defaults to one more than the number of sprints
 }
 
 #[derive(Clone)]
 pub struct SprintService {
     db_connection: Arc<DbConnection>,
+    key_prefix: String,
+    carry_over_limit: u32,
+    capacity: CapacityConfig,
 }
 
 impl SprintService {
     pub fn new(db_connection: Arc<DbConnection>) -> Self {
-        Self { db_connection }
+        Self {
+            db_connection,
+            key_prefix: KeysConfig::default().sprint,
+            carry_over_limit: SprintsConfig::default().carry_over_limit,
+            capacity: CapacityConfig::default(),
+        }
+    }
+
+    /// Tasks carried over more than `limit` times are flagged in sprint reports.
+    pub fn with_carry_over_limit(mut self, limit: u32) -> Self {
+        self.carry_over_limit = limit;
+        self
+    }
+
+    /// Capacity settings; when enforced, completing a sprint fails if the tasks it carries over
+    /// take an assignee over capacity in the destination sprint.
+    pub fn with_capacity(mut self, capacity: CapacityConfig) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Fails if `sprint` ends before it starts or overlaps a sprint that is not cancelled.
+    /// Sprints may end at the moment the next one starts, and a cancelled sprint overlaps nothing.
+    fn check_schedule_in(conn: &Connection, sprint: &Sprint) -> Result<()> {
+        if sprint.end_date <= sprint.start_date {
+            return Err(Error::ValidationError(format!("'{}' must end after it starts", sprint.name)));
+        }
+        if sprint.status == SprintStatus::Cancelled {
+            return Ok(());
+        }
+        let overlapping = SprintRepository::list_with_tasks(conn, None)?.into_iter().find(|other| {
+            other.id != sprint.id
+                && other.status != SprintStatus::Cancelled
+                && other.start_date < sprint.end_date
+                && sprint.start_date < other.end_date
+        });
+        match overlapping {
+            Some(other) => Err(Error::ValidationError(format!(
+                "'{}' ({} to {}) overlaps {} ({} to {})",
+                sprint.name,
+                sprint.start_date.format("%Y-%m-%d"),
+                sprint.end_date.format("%Y-%m-%d"),
+                other.key.as_deref().unwrap_or(&other.name),
+                other.start_date.format("%Y-%m-%d"),
+                other.end_date.format("%Y-%m-%d")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if a sprint other than `sprint` is active.
+    fn check_no_other_active_in(conn: &Connection, sprint: &Sprint) -> Result<()> {
+        let active = SprintRepository::list_with_tasks(conn, Some(&SprintStatus::Active))?;
+        match active.iter().find(|other| other.id != sprint.id) {
+            Some(other) => Err(Error::ValidationError(format!(
+                "Sprint {} is already active; complete it before starting {}",
+                other.key.as_deref().unwrap_or(&other.name),
+                sprint.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The sprint unfinished tasks of `sprint` should move to: `Some(None)` for the backlog,
+    /// `None` to leave them where they are.
+    fn carry_over_destination_in(conn: &Connection, sprint: &Sprint, target: &CarryOverTarget) -> Result<Option<Option<String>>> {
+        match target {
+            CarryOverTarget::Keep => Ok(None),
+            CarryOverTarget::Backlog => Ok(Some(None)),
+            CarryOverTarget::Next => SprintRepository::list_with_tasks(conn, Some(&SprintStatus::Planning))?
+                .into_iter()
+                .find(|next| next.id != sprint.id && next.start_date >= sprint.start_date)
+                .map(|next| Some(Some(next.id)))
+                .ok_or_else(|| Error::NotFound(format!(
+                    "No planned sprint starts after {}; create one or carry over to the backlog",
+                    sprint.name
+                ))),
+            CarryOverTarget::Sprint(id) => {
+                let destination = SprintRepository::get(conn, id)?
+                    .ok_or_else(|| Error::NotFound(format!("Sprint {} not found", id)))?;
+                if destination.id == sprint.id {
+                    return Err(Error::ValidationError("Cannot carry tasks over into the sprint being completed".to_string()));
+                }
+                if matches!(destination.status, SprintStatus::Completed | SprintStatus::Cancelled) {
+                    return Err(Error::ValidationError(format!(
+                        "Cannot carry tasks over into sprint {}, which is {}",
+                        destination.name,
+                        destination.status.to_string().to_lowercase()
+                    )));
+                }
+                Ok(Some(Some(destination.id)))
+            }
+        }
+    }
+
+    /// Prefix for the keys of new sprints, instead of `SPRINT`.
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Story points of the done tasks in `tasks`.
+    pub fn completed_points(tasks: &[Task]) -> u32 {
+        tasks.iter().filter(|t| t.status == TaskStatus::Done).map(|t| u32::from(t.story_points.unwrap_or(0))).sum()
+    }
+
+    /// Completed sprints by end date, the `last` few if given. Sprints completed before
+    /// velocity was recorded are measured by their done tasks.
+    fn velocities_in(conn: &Connection, last: Option<usize>) -> Result<Vec<SprintVelocity>> {
+        let mut sprints = SprintRepository::list_with_tasks(conn, Some(&SprintStatus::Completed))?;
+        sprints.sort_by_key(|s| s.end_date);
+        let skip = last.map_or(0, |last| sprints.len().saturating_sub(last));
+        Ok(sprints
+            .into_iter()
+            .skip(skip)
+            .map(|sprint| SprintVelocity {
+                velocity: sprint.velocity.map_or_else(|| f64::from(Self::completed_points(&sprint.tasks)), f64::from),
+                sprint_id: sprint.id,
+                key: sprint.key,
+                name: sprint.name,
+                end_date: sprint.end_date,
+                rolling_average: 0.0,
+            })
+            .collect())
+    }
+
+    /// Open story points, open tasks and open tasks without points for `target`.
+    fn backlog_in(conn: &Connection, target: &ForecastTarget) -> Result<(u32, u32, u32)> {
+        let open: Vec<Task> = match target {
+            ForecastTarget::Epic(id) => {
+                let tasks = TaskRepository::list(conn)?;
+                let tree = TaskHierarchy::new(&tasks)
+                    .tree(id)
+                    .ok_or_else(|| Error::NotFound(format!("Task {} not found", id)))?;
+                let mut leaves = Vec::new();
+                collect_open_leaves(&tree, &mut leaves);
+                tasks.into_iter().filter(|t| leaves.contains(&t.id.as_str())).collect()
+            }
+            ForecastTarget::Label(label) => TaskRepository::list_with_label(conn, label)?
+                .into_iter()
+                .filter(|t| !is_finished(&t.status))
+                .collect(),
+        };
+        let points = open.iter().map(|t| u32::from(t.story_points.unwrap_or(0))).sum();
+        let unestimated = open.iter().filter(|t| t.story_points.is_none()).count() as u32;
+        Ok((points, open.len() as u32, unestimated))
+    }
+}
+
+/// Unfinished tasks at the bottom of `tree`, which is where the hierarchy counts work.
+fn collect_open_leaves<'a>(tree: &'a TaskTree, leaves: &mut Vec<&'a str>) {
+    if tree.children.is_empty() {
+        if !is_finished(&tree.status) {
+            leaves.push(&tree.id);
+        }
+        return;
+    }
+    for child in &tree.children {
+        collect_open_leaves(child, leaves);
     }
 }
 
@@ -38,371 +250,246 @@ impl SprintService {
 impl SprintServiceTrait for SprintService {
 
     #[instrument(skip(self, sprint), err)]
-    async fn create_sprint(&self, sprint: Sprint) -> Result<Sprint> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        let sprint_clone = sprint.clone();
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare(
-                "INSERT INTO sprints (id, name, description, start_date, end_date, goals, tasks, status, velocity, capacity, created_at, updated_at, retrospective) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-            )?;
-            
-            // Convert values to appropriate types for rusqlite
-            let name = sprint_clone.name.clone();
-            let description = sprint_clone.description.clone(); // Clone to avoid partial move
-            let start_date = sprint_clone.start_date.to_rfc3339();
-            let end_date = sprint_clone.end_date.to_rfc3339();
-            let goals = serde_json::to_string(&sprint_clone.goals)
-                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-            let tasks = serde_json::to_string(&sprint_clone.tasks)
-                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-            let status = sprint_clone.status.to_string();
-            let velocity = sprint_clone.velocity.map(|v| v.to_string());
-            let capacity = sprint_clone.capacity.map(|c| c.to_string());
-            let created_at = sprint_clone.created_at.to_rfc3339();
-            let updated_at = sprint_clone.updated_at.to_rfc3339();
-            let retrospective = sprint_clone.retrospective
-                .as_ref()
-                .and_then(|r| serde_json::to_string(r).ok());
-
-            // Convert all fields to owned values for SQLite
-            let id_val: String = sprint_clone.id.clone();
-            let name_val: String = name.clone();
-            let description_val = description.as_deref().unwrap_or(""); // Use as_deref on Option<String>
-            let start_date_val: String = start_date.clone();
-            let end_date_val: String = end_date.clone();
-            let goals_val: String = goals.clone();
-            let tasks_val: String = tasks.clone();
-            let status_val: String = status.clone();
-            let velocity_val: String = velocity.as_ref().map(|s| s.to_string()).unwrap_or_default();
-            let capacity_val: String = capacity.as_ref().map(|s| s.to_string()).unwrap_or_default();
-            let created_at_val: String = created_at.clone();
-            let updated_at_val: String = updated_at.clone();
-            let retrospective_val: String = retrospective.as_ref().map(|s| s.to_string()).unwrap_or_default();
-
-            stmt.execute(rusqlite::params![
-                &id_val,
-                &name_val,
-                &description_val,
-                &start_date_val,
-                &end_date_val,
-                &goals_val,
-                &tasks_val,
-                &status_val,
-                &velocity_val,
-                &capacity_val,
-                &created_at_val,
-                &updated_at_val,
-                &retrospective_val,
-            ])?;
-            debug!("Created sprint: {}", sprint_clone.id);
-            Ok(sprint_clone)
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e: rusqlite::Error| Error::DatabaseOperation(format!("Failed to create sprint: {}", e)))
+    async fn create_sprint(&self, mut sprint: Sprint) -> Result<Sprint> {
+        let key_prefix = self.key_prefix.clone();
+        self.db_connection.transaction(move |tx| {
+            Self::check_schedule_in(tx, &sprint)?;
+            SprintRepository::assign_key(tx, &mut sprint, &key_prefix)?;
+            SprintRepository::insert(tx, &sprint)?;
+            debug!("Created sprint: {}", sprint.id);
+            Ok(sprint)
+        }).await
     }
 
     #[instrument(skip(self), err)]
-    async fn get_sprint_by_id(&self, id: &str) -> Result<Option<Sprint>> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        let id_clone = id.to_string();
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, description, start_date, end_date, goals, tasks, status, velocity, capacity, created_at, updated_at, retrospective FROM sprints WHERE id = ?"
-            )?;
-            let sprint_row = stmt.query_row([id_clone], |row| {
-                let description: Option<String> = row.get(2)?;
-                let start_date_str: String = row.get(3)?;
-                let end_date_str: String = row.get(4)?;
-                let goals_str: String = row.get(5)?;
-                let tasks_str: String = row.get(6)?;
-                let status_str: String = row.get(7)?;
-                let velocity: Option<f32> = row.get(8)?;
-                let capacity: Option<f32> = row.get(9)?;
-                let created_at_str: String = row.get(10)?;
-                let updated_at_str: String = row.get(11)?;
-                let retrospective_str: Option<String> = row.get(12)?;
-
-                Ok(Sprint {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description,
-                    start_date: DateTime::parse_from_rfc3339(&start_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    end_date: DateTime::parse_from_rfc3339(&end_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    goals: serde_json::from_str(&goals_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                    tasks: serde_json::from_str(&tasks_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                    status: SprintStatus::from_str(&status_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::InvalidInput(e.to_string()))))?, // Corrected error mapping
-                    velocity,
-                    capacity,
-                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    retrospective: retrospective_str
-                        .map(|s| serde_json::from_str(&s))
-                        .transpose()
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                })
-            });
-            match sprint_row {
-                Ok(s) => Ok(Some(s)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                Err(e) => Err(e),
+    async fn plan_sprints(&self, plan: SprintPlan) -> Result<Vec<Sprint>> {
+        if plan.count == 0 || plan.cadence_days == 0 {
+            return Err(Error::ValidationError("Plan at least one sprint, at least one day long".to_string()));
+        }
+        if plan.count > 1 && !plan.name_pattern.contains("{n}") {
+            return Err(Error::ValidationError("The name pattern needs {n} to name more than one sprint".to_string()));
+        }
+        let key_prefix = self.key_prefix.clone();
+        self.db_connection.transaction(move |tx| {
+            let existing = SprintRepository::list_with_tasks(tx, None)?;
+            let start = match plan.start {
+                Some(start) => start,
+                None => existing
+                    .iter()
+                    .map(|s| s.end_date.date_naive() + Days::new(1))
+                    .max()
+                    .unwrap_or_else(|| Utc::now().date_naive()),
+            };
+            let first_number = plan.first_number.unwrap_or(existing.len() as u32 + 1);
+
+            let mut planned = Vec::new();
+            for index in 0..plan.count {
+                let offset = u64::from(index) * u64::from(plan.cadence_days);
+                let (first_day, last_day) = start
+                    .checked_add_days(Days::new(offset))
+                    .zip(start.checked_add_days(Days::new(offset + u64::from(plan.cadence_days) - 1)))
+                    .ok_or_else(|| Error::ValidationError("The planned sprints run past the last supported date".to_string()))?;
+                let mut sprint = Sprint::new(
+                    plan.name_pattern.replace("{n}", &(first_number + index).to_string()),
+                    first_day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+                    last_day.and_hms_opt(23, 59, 59).unwrap_or_default().and_utc(),
+                );
+                Self::check_schedule_in(tx, &sprint)?;
+                SprintRepository::assign_key(tx, &mut sprint, &key_prefix)?;
+                SprintRepository::insert(tx, &sprint)?;
+                planned.push(sprint);
             }
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e| Error::DatabaseOperation(format!("Failed to get sprint by ID: {}", e)))
+            debug!("Planned {} sprints", planned.len());
+            Ok(planned)
+        }).await
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_sprint_by_id(&self, id: &str) -> Result<Option<Sprint>> {
+        let id_owned = id.to_string();
+        self.db_connection.with_connection(move |conn| SprintRepository::get_with_tasks(conn, &id_owned)).await
     }
 
     #[instrument(skip(self, sprint), err)]
-    async fn update_sprint(&self, sprint: Sprint) -> Result<Sprint> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        let sprint_clone = sprint.clone();
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare(
-                "UPDATE sprints SET name = ?, description = ?, start_date = ?, end_date = ?, goals = ?, tasks = ?, status = ?, velocity = ?, capacity = ?, updated_at = ?, retrospective = ? WHERE id = ?"
-            )?;
-            // Convert numeric options to strings with explicit types
-            let velocity_value: String = sprint_clone.velocity.map(|v| v.to_string()).unwrap_or_default();
-            let capacity_value: String = sprint_clone.capacity.map(|c| c.to_string()).unwrap_or_default();
-            let retrospective_value: String = sprint_clone.retrospective
-                .as_ref()
-                .map(|r| serde_json::to_string(r))
-                .transpose()
-                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))? // Corrected error mapping
-                .unwrap_or_default();
-
-            stmt.execute(rusqlite::params![
-                &sprint_clone.name,
-                sprint_clone.description.as_deref().unwrap_or(""),
-                &sprint_clone.start_date.to_rfc3339(),
-                &sprint_clone.end_date.to_rfc3339(),
-                &serde_json::to_string(&sprint_clone.goals)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?, // Corrected error mapping
-                &serde_json::to_string(&sprint_clone.tasks)
-                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?, // Corrected error mapping
-                &sprint_clone.status.to_string(),
-                &velocity_value,
-                &capacity_value,
-                &sprint_clone.updated_at.to_rfc3339(),
-                &retrospective_value,
-                &sprint_clone.id,
-            ])?;
-            debug!("Updated sprint: {}", sprint_clone.id);
-            Ok(sprint_clone)
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e: rusqlite::Error| Error::DatabaseOperation(format!("Failed to update sprint: {}", e)))
+    async fn update_sprint(&self, mut sprint: Sprint) -> Result<Sprint> {
+        self.db_connection.transaction(move |tx| {
+            Self::check_schedule_in(tx, &sprint)?;
+            if sprint.status == SprintStatus::Active {
+                Self::check_no_other_active_in(tx, &sprint)?;
+            }
+            if !SprintRepository::update(tx, &mut sprint)? {
+                return Err(Error::NotFound(format!("Sprint with ID {} not found", sprint.id)));
+            }
+            debug!("Updated sprint: {}", sprint.id);
+            Ok(sprint)
+        }).await
     }
 
     #[instrument(skip(self), err)]
-    async fn delete_sprint(&self, id: &str) -> Result<()> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        let id_owned = id.to_string(); // Clone id to an owned String
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare("DELETE FROM sprints WHERE id = ?")?;
-            let id_str = id_owned.clone();
-            stmt.execute([&id_str])?;
-            debug!("Deleted sprint: {}", id_str);
-            Ok::<(), rusqlite::Error>(())
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e| Error::DatabaseOperation(format!("Failed to delete sprint: {}", e)))
+    async fn delete_sprint(&self, id: &str) -> Result<Vec<Reference>> {
+        let id_owned = id.to_string();
+        self.db_connection.with_connection(move |conn| {
+            if !SprintRepository::delete(conn, &id_owned)? {
+                return Err(Error::NotFound(format!("Sprint {} not found", id_owned)));
+            }
+            debug!("Deleted sprint: {}", id_owned);
+            Ok(())
+        }).await?;
+        self.db_connection.references_to(id).await
     }
 
     #[instrument(skip(self), err)]
     async fn get_current_sprint(&self) -> Result<Option<Sprint>> {
-        // Placeholder: In a real app, this would involve querying for an active sprint
-        // based on dates or a dedicated 'current' flag.
-        // For now, we'll just return the most recently created sprint as a mock "current".
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        conn.interact(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, description, start_date, end_date, goals, tasks, status, velocity, capacity, created_at, updated_at, retrospective FROM sprints ORDER BY created_at DESC LIMIT 1"
-            )?;
-            let sprint_row = stmt.query_row([], |row| {
-                let description: Option<String> = row.get(2)?;
-                let start_date_str: String = row.get(3)?;
-                let end_date_str: String = row.get(4)?;
-                let goals_str: String = row.get(5)?;
-                let tasks_str: String = row.get(6)?;
-                let status_str: String = row.get(7)?;
-                let velocity: Option<f32> = row.get(8)?;
-                let capacity: Option<f32> = row.get(9)?;
-                let created_at_str: String = row.get(10)?;
-                let updated_at_str: String = row.get(11)?;
-                let retrospective_str: Option<String> = row.get(12)?;
-
-                Ok(Sprint {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description,
-                    start_date: DateTime::parse_from_rfc3339(&start_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))?
-                        .with_timezone(&Utc),
-                    end_date: DateTime::parse_from_rfc3339(&end_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                        .with_timezone(&Utc),
-                    goals: serde_json::from_str(&goals_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?,
-                    tasks: serde_json::from_str(&tasks_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?,
-                    status: SprintStatus::from_str(&status_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::InvalidInput(e.to_string()))))?,
-                    velocity,
-                    capacity,
-                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                        .with_timezone(&Utc),
-                    updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                        .with_timezone(&Utc),
-                    retrospective: retrospective_str
-                        .map(|s| serde_json::from_str(&s))
-                        .transpose()
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?,
-                })
-            });
-            match sprint_row {
-                Ok(s) => Ok(Some(s)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                Err(e) => Err(e),
-            }
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e| Error::DatabaseOperation(format!("Failed to get current sprint: {}", e)))
+        // The current sprint is the most recently started active sprint.
+        self.db_connection.with_connection(|conn| {
+            let mut active = SprintRepository::list_with_tasks(conn, Some(&SprintStatus::Active))?;
+            Ok(active.pop())
+        }).await
     }
 
     #[instrument(skip(self), err)]
     async fn list_sprints(&self, status: Option<SprintStatus>) -> Result<Vec<Sprint>> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection from pool: {}", e)))?;
-        conn.interact(move |conn| {
-            let mut query = "SELECT id, name, description, start_date, end_date, goals, tasks, status, velocity, capacity, created_at, updated_at, retrospective FROM sprints".to_string();
-            let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-
-            let status_string;
-            if let Some(sprint_status) = status {
-                query.push_str(" WHERE status = ?");
-                status_string = sprint_status.to_string();
-                params.push(&status_string);
-            }
-
-            let mut stmt = conn.prepare(&query)?;
-            let sprints_iter = stmt.query_map(&*params, |row| {
-                let description: Option<String> = row.get(2)?;
-                let start_date_str: String = row.get(3)?;
-                let end_date_str: String = row.get(4)?;
-                let goals_str: String = row.get(5)?;
-                let tasks_str: String = row.get(6)?;
-                let status_str: String = row.get(7)?;
-                let velocity: Option<f32> = row.get(8)?;
-                let capacity: Option<f32> = row.get(9)?;
-                let created_at_str: String = row.get(10)?;
-                let updated_at_str: String = row.get(11)?;
-                let retrospective_str: Option<String> = row.get(12)?;
-
-                Ok(Sprint {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description,
-                    start_date: DateTime::parse_from_rfc3339(&start_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    end_date: DateTime::parse_from_rfc3339(&end_date_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    goals: serde_json::from_str(&goals_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                    tasks: serde_json::from_str(&tasks_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                    status: SprintStatus::from_str(&status_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::InvalidInput(e.to_string()))))?, // Corrected error mapping
-                    velocity,
-                    capacity,
-                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::Parse(e))))? // Corrected error mapping
-                        .with_timezone(&Utc),
-                    retrospective: retrospective_str
-                        .map(|s| serde_json::from_str(&s))
-                        .transpose()
-                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(Error::JsonError(e))))?, // Corrected error mapping
-                })
-            })?;
-
-            let mut sprints = Vec::new();
-            for sprint in sprints_iter {
-                sprints.push(sprint?);
-            }
-            Ok(sprints)
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Deadpool interact error: {:?}", e)))?
-        .map_err(|e: rusqlite::Error| Error::DatabaseOperation(format!("Failed to list sprints: {}", e)))
+        self.db_connection.with_connection(move |conn| SprintRepository::list_with_tasks(conn, status.as_ref())).await
     }
 
     #[instrument(skip(self), err)]
     async fn start_sprint(&self, sprint_id: &str) -> Result<Sprint> {
-        let mut sprint = self.get_sprint_by_id(sprint_id).await?
-            .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", sprint_id)))?;
-        sprint.status = SprintStatus::Active;
-        sprint.updated_at = Utc::now();
-        self.update_sprint(sprint).await
+        let id_owned = sprint_id.to_string();
+        self.db_connection.transaction(move |tx| {
+            let mut sprint = SprintRepository::get_with_tasks(tx, &id_owned)?
+                .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", id_owned)))?;
+            if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Cancelled) {
+                return Err(Error::ValidationError(format!(
+                    "Sprint {} is {} and cannot be started again",
+                    sprint.name,
+                    sprint.status.to_string().to_lowercase()
+                )));
+            }
+            Self::check_no_other_active_in(tx, &sprint)?;
+            sprint.status = SprintStatus::Active;
+            sprint.updated_at = Utc::now();
+            SprintRepository::update(tx, &mut sprint)?;
+            debug!("Started sprint: {}", sprint.id);
+            Ok(sprint)
+        }).await
     }
 
     #[instrument(skip(self, retrospective), err)]
-    async fn complete_sprint(&self, sprint_id: &str, retrospective: Option<Retrospective>) -> Result<Sprint> {
-        let mut sprint = self.get_sprint_by_id(sprint_id).await?
-            .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", sprint_id)))?;
-        sprint.status = SprintStatus::Completed;
-        sprint.retrospective = retrospective;
-        sprint.updated_at = Utc::now();
-        self.update_sprint(sprint).await
+    async fn complete_sprint(&self, sprint_id: &str, retrospective: Option<Retrospective>, carry_to: CarryOverTarget) -> Result<Sprint> {
+        // Read, check and write in one transaction so the sprint and the task list
+        // it reports on cannot change in between.
+        let id_owned = sprint_id.to_string();
+        let capacity = self.capacity.clone();
+        self.db_connection.transaction(move |tx| {
+            let mut sprint = SprintRepository::get_with_tasks(tx, &id_owned)?
+                .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", id_owned)))?;
+            if sprint.status != SprintStatus::Active {
+                return Err(Error::ValidationError(format!(
+                    "Sprint {} is {}; only an active sprint can be completed",
+                    sprint.name,
+                    sprint.status.to_string().to_lowercase()
+                )));
+            }
+            sprint.status = SprintStatus::Completed;
+            sprint.velocity = Some(Self::completed_points(&sprint.tasks) as f32);
+            sprint.retrospective = retrospective;
+            sprint.updated_at = Utc::now();
+
+            sprint.carry_over = None;
+            if let Some(destination) = Self::carry_over_destination_in(tx, &sprint, &carry_to)? {
+                let mut carry_over = CarryOver { to_sprint_id: destination.clone(), ..CarryOver::default() };
+                for mut task in sprint.tasks.iter().filter(|t| !is_finished(&t.status)).cloned() {
+                    task.sprint_id = destination.clone();
+                    task.carry_over_count += 1;
+                    task.updated_at = Utc::now();
+                    task.updated_by = Some(CARRY_OVER_ACTOR.to_string());
+                    // Its dates were planned for this sprint, which the destination cannot overlap
+                    TaskService::clear_dates_outside_sprint_in(tx, &mut task)?;
+                    TaskService::check_dates_in(tx, &task)?;
+                    TaskRepository::update(tx, &mut task)?;
+                    carry_over.points += u32::from(task.story_points.unwrap_or(0));
+                    carry_over.task_ids.push(task.id);
+                }
+                if let Some(destination) = &destination {
+                    let assignees: Vec<String> = sprint.tasks.iter()
+                        .filter(|t| !is_finished(&t.status))
+                        .filter_map(|t| t.assignee.clone())
+                        .collect();
+                    CapacityService::check_capacity_in(tx, destination, &assignees, &capacity)?;
+                }
+                sprint.tasks.retain(|t| is_finished(&t.status));
+                sprint.carry_over = Some(carry_over);
+            }
+            SprintRepository::update(tx, &mut sprint)?;
+            debug!("Completed sprint: {}", sprint.id);
+            Ok(sprint)
+        }).await
     }
 
     #[instrument(skip(self), err)]
     async fn get_sprint_report(&self, sprint_id: &str) -> Result<SprintReport> {
-        let sprint = self.get_sprint_by_id(sprint_id).await?.ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", sprint_id)))?;
-        
-        // TODO: Implement actual report generation logic
-        // Placeholder implementation - needs real data
-        Ok(SprintReport {
-            sprint_id: sprint.id,
-            sprint_name: sprint.name,
-            total_tasks: sprint.tasks.len() as u32,
-            completed_tasks: sprint.tasks.iter().filter(|t| t.completed_at.is_some()).count() as u32,
-            remaining_tasks: sprint.tasks.iter().filter(|t| t.completed_at.is_none()).count() as u32,
-            total_story_points: sprint.tasks.iter().map(|t| t.story_points.unwrap_or(0) as u32).sum(),
-            completed_story_points: sprint.tasks.iter().filter(|t| t.completed_at.is_some()).map(|t| t.story_points.unwrap_or(0) as u32).sum(),
-            remaining_story_points: sprint.tasks.iter().filter(|t| t.completed_at.is_none()).map(|t| t.story_points.unwrap_or(0) as u32).sum(),
-            retrospective: sprint.retrospective.clone(),
-        })
+        let id_owned = sprint_id.to_string();
+        let carry_over_limit = self.carry_over_limit;
+        self.db_connection.with_connection(move |conn| {
+            let sprint = SprintRepository::get_with_tasks(conn, &id_owned)?
+                .ok_or_else(|| Error::NotFound(format!("Sprint with ID {} not found", id_owned)))?;
+            let time = TimeTrackingService::summarize_in(conn, &sprint.tasks)?;
+            let today = chrono::Local::now().date_naive();
+            let burndown = burndown::compute(&sprint, &HistoryRepository::list_for_sprint_tasks(conn, &sprint.id)?, today);
+            let carry_over = sprint.carry_over.clone().unwrap_or_default();
+            let mut repeatedly_carried = Vec::new();
+            for id in sprint.tasks.iter().map(|t| t.id.as_str()).chain(carry_over.task_ids.iter().map(String::as_str)) {
+                if let Some(task) = TaskRepository::get(conn, id)?.filter(|t| t.carry_over_count > carry_over_limit) {
+                    repeatedly_carried.push(task.key.unwrap_or(task.id));
+                }
+            }
+
+            Ok(SprintReport {
+                sprint_id: sprint.id,
+                sprint_name: sprint.name,
+                total_tasks: sprint.tasks.len() as u32,
+                completed_tasks: sprint.tasks.iter().filter(|t| t.completed_at.is_some()).count() as u32,
+                remaining_tasks: sprint.tasks.iter().filter(|t| t.completed_at.is_none()).count() as u32,
+                overdue_tasks: sprint.tasks.iter().filter(|t| t.is_overdue(today)).count() as u32,
+                total_story_points: sprint.tasks.iter().map(|t| t.story_points.unwrap_or(0) as u32).sum(),
+                completed_story_points: sprint.tasks.iter().filter(|t| t.completed_at.is_some()).map(|t| t.story_points.unwrap_or(0) as u32).sum(),
+                remaining_story_points: sprint.tasks.iter().filter(|t| t.completed_at.is_none()).map(|t| t.story_points.unwrap_or(0) as u32).sum(),
+                original_estimate_minutes: time.original_estimate_minutes,
+                remaining_estimate_minutes: time.remaining_estimate_minutes,
+                logged_minutes: time.logged_minutes,
+                velocity: sprint.velocity,
+                carried_over_tasks: carry_over.task_ids.len() as u32,
+                carried_over_points: carry_over.points,
+                carried_over_to: sprint.carry_over.as_ref().map(|c| c.to_sprint_id.clone().unwrap_or_else(|| "backlog".to_string())),
+                repeatedly_carried,
+                retrospective: sprint.retrospective,
+                burndown,
+            })
+        }).await
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_velocity(&self, last: Option<usize>, window: usize) -> Result<VelocityReport> {
+        self.db_connection
+            .with_connection(move |conn| Ok(VelocityReport::new(Self::velocities_in(conn, last)?, window)))
+            .await
+    }
+
+    #[instrument(skip(self), err)]
+    async fn forecast(&self, target: ForecastTarget, last: Option<usize>, runs: u32, seed: Option<u64>) -> Result<Forecast> {
+        let seed = seed.unwrap_or_else(|| Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64);
+        self.db_connection.with_connection(move |conn| {
+            let velocities = Self::velocities_in(conn, last)?.into_iter().map(|s| s.velocity).collect();
+            let (points, open, unestimated) = Self::backlog_in(conn, &target)?;
+            let mut forecast = Forecast::simulate(target, points, velocities, runs, seed)?;
+            forecast.open_tasks = open;
+            forecast.unestimated_tasks = unestimated;
+            Ok(forecast)
+        }).await
     }
 
 }
 
-// Placeholder for SprintReport struct
 #[derive(Debug, Serialize)]
 pub struct SprintReport {
     pub sprint_id: String,
@@ -410,8 +497,211 @@ pub struct SprintReport {
     pub total_tasks: u32,
     pub completed_tasks: u32,
     pub remaining_tasks: u32,
+    pub overdue_tasks: u32,            // Open tasks past their due date
     pub total_story_points: u32,
     pub completed_story_points: u32,
     pub remaining_story_points: u32,
+    pub original_estimate_minutes: u32,
+    pub remaining_estimate_minutes: u32,
+    pub logged_minutes: u32,
+    pub velocity: Option<f32>,         // Story points done, recorded when the sprint is completed
+    pub carried_over_tasks: u32,       // Unfinished tasks moved on when the sprint was completed
+    pub carried_over_points: u32,
+    pub carried_over_to: Option<String>, // Sprint ID, or "backlog"
+    pub repeatedly_carried: Vec<String>, // Tasks carried over more than the configured limit
     pub retrospective: Option<Retrospective>,
-}
\ No newline at end of file
+    pub burndown: Burndown,            // Day-by-day scope, completed and remaining work
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::{Task, TaskStatus, TaskType};
+    use crate::services::task_service::TaskServiceTrait;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_complete_sprint_carries_unfinished_tasks_over() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = SprintService::new(db.clone()).with_carry_over_limit(1);
+        let start = Utc::now();
+        let first = service.create_sprint(Sprint::new("S1".to_string(), start, start + Duration::days(14))).await?;
+        let second = service.create_sprint(Sprint::new("S2".to_string(), start + Duration::days(14), start + Duration::days(28))).await?;
+        let third = service.create_sprint(Sprint::new("S3".to_string(), start + Duration::days(28), start + Duration::days(42))).await?;
+
+        let tasks = TaskService::new(db.clone());
+        let ids: Vec<String> = (1..=3).map(|n| format!("TASK-{}", n)).collect();
+        for id in &ids {
+            let mut task = Task::new(id.clone(), id.clone(), TaskType::Task);
+            task.story_points = Some(2);
+            tasks.create_task(task).await?;
+        }
+        tasks.bulk_assign_sprint(ids.clone(), first.id.clone(), None).await?;
+        tasks.bulk_update_status(vec![ids[0].clone()], TaskStatus::Done, None).await?;
+        // Dates within the first sprint: only the last day still fits the second
+        let (first_day, last_day) = (first.start_date.date_naive(), first.end_date.date_naive());
+        for (id, start_date, due_date) in [(&ids[1], first_day, first_day + Duration::days(5)), (&ids[2], first_day, last_day)] {
+            let mut task = tasks.get_task_by_id(id.clone()).await?.unwrap();
+            (task.start_date, task.due_date) = (Some(start_date), Some(due_date));
+            tasks.update_task(task).await?;
+        }
+
+        assert!(matches!(service.complete_sprint(&first.id, None, CarryOverTarget::Next).await, Err(Error::ValidationError(_))));
+        service.start_sprint(&first.id).await?;
+        let completed = service.complete_sprint(&first.id, None, CarryOverTarget::Next).await?;
+        let carry_over = completed.carry_over.expect("carry-over recorded");
+        assert_eq!((carry_over.to_sprint_id.as_deref(), carry_over.task_ids.len(), carry_over.points), (Some(second.id.as_str()), 2, 4));
+        let report = service.get_sprint_report(&first.id).await?;
+        assert_eq!((report.carried_over_tasks, report.carried_over_points, report.completed_tasks), (2, 4, 1));
+        assert!(report.repeatedly_carried.is_empty());
+        let moved = tasks.get_task_by_id(ids[1].clone()).await?.unwrap();
+        assert_eq!((moved.sprint_id.as_deref(), moved.start_date, moved.due_date), (Some(second.id.as_str()), None, None));
+        assert_eq!(moved.updated_by.as_deref(), Some(CARRY_OVER_ACTOR));
+        let moved = tasks.get_task_by_id(ids[2].clone()).await?.unwrap();
+        assert_eq!((moved.start_date, moved.due_date), (None, Some(last_day)));
+
+        // Completing twice would overwrite the carry-over record
+        assert!(matches!(service.complete_sprint(&first.id, None, CarryOverTarget::Backlog).await, Err(Error::ValidationError(_))));
+        assert_eq!(service.get_sprint_report(&first.id).await?.carried_over_tasks, 2);
+        service.start_sprint(&second.id).await?;
+
+        // A completed sprint cannot take carried-over tasks
+        let result = service.complete_sprint(&second.id, None, CarryOverTarget::Sprint(first.id.clone())).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        service.complete_sprint(&second.id, None, CarryOverTarget::Sprint(third.id.clone())).await?;
+        let report = service.get_sprint_report(&second.id).await?;
+        assert_eq!(report.repeatedly_carried, vec![ids[1].clone(), ids[2].clone()]);
+        service.start_sprint(&third.id).await?;
+
+        let result = service.complete_sprint(&third.id, None, CarryOverTarget::Next).await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        let completed = service.complete_sprint(&third.id, None, CarryOverTarget::Backlog).await?;
+        assert_eq!(completed.carry_over.unwrap().to_sprint_id, None);
+        let task = tasks.get_task_by_id(ids[2].clone()).await?.unwrap();
+        assert_eq!((task.sprint_id, task.carry_over_count), (None, 3));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_carry_over_is_checked_against_destination_capacity() -> Result<()> {
+        use crate::services::capacity_service::CapacityServiceTrait;
+
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let config = CapacityConfig { points_per_day: 1.0, focus_factor: 1.0, enforce: true };
+        let service = SprintService::new(db.clone()).with_capacity(config.clone());
+        let start = Utc::now();
+        let first = service.create_sprint(Sprint::new("S1".to_string(), start, start + Duration::days(14))).await?;
+        let second = service.create_sprint(Sprint::new("S2".to_string(), start + Duration::days(14), start + Duration::days(28))).await?;
+        let capacity = CapacityService::new(db.clone()).with_config(config);
+        capacity.set_availability(&second.id, "alice", Some(2.0), None, None).await?;
+
+        let tasks = TaskService::new(db.clone());
+        let mut task = Task::new("TASK-1".to_string(), "Big".to_string(), TaskType::Task);
+        (task.assignee, task.story_points, task.sprint_id) = (Some("alice".to_string()), Some(3), Some(first.id.clone()));
+        tasks.create_task(task).await?;
+        service.start_sprint(&first.id).await?;
+
+        // Three points do not fit in two days, so the sprint stays active with its task
+        let result = service.complete_sprint(&first.id, None, CarryOverTarget::Next).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        let task = tasks.get_task_by_id("TASK-1".to_string()).await?.unwrap();
+        assert_eq!((task.sprint_id.as_deref(), task.carry_over_count), (Some(first.id.as_str()), 0));
+        assert_eq!(service.get_sprint_by_id(&first.id).await?.unwrap().status, SprintStatus::Active);
+
+        capacity.set_availability(&second.id, "alice", Some(5.0), None, None).await?;
+        service.complete_sprint(&first.id, None, CarryOverTarget::Next).await?;
+        assert_eq!(capacity.get_capacity(&second.id).await?.members[0].load_points, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plan_sprints_and_schedule_validation() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = SprintService::new(db.clone());
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        let plan = SprintPlan {
+            start: Some(date("2026-11-02")),
+            cadence_days: 14,
+            count: 3,
+            name_pattern: "Sprint {n}".to_string(),
+            first_number: None,
+        };
+        let planned = service.plan_sprints(plan.clone()).await?;
+        let names: Vec<&str> = planned.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Sprint 1", "Sprint 2", "Sprint 3"]);
+        assert_eq!(planned[1].start_date.date_naive(), date("2026-11-16"));
+        assert_eq!(planned[1].end_date.date_naive(), date("2026-11-29"));
+
+        // Without {n} every sprint in the plan would get the same name
+        let unnumbered = SprintPlan { name_pattern: "Sprint".to_string(), start: Some(date("2027-06-01")), ..plan.clone() };
+        assert!(matches!(service.plan_sprints(unnumbered.clone()).await, Err(Error::ValidationError(_))));
+        let endless = SprintPlan { cadence_days: u32::MAX, count: 1, ..unnumbered.clone() };
+        assert!(matches!(service.plan_sprints(endless).await, Err(Error::ValidationError(_))));
+        let single = service.plan_sprints(SprintPlan { count: 1, ..unnumbered }).await?;
+        assert_eq!(single[0].name, "Sprint");
+        service.delete_sprint(&single[0].id).await?;
+
+        // Overlapping any planned sprint fails the whole plan
+        let result = service.plan_sprints(SprintPlan { start: Some(date("2026-12-10")), ..plan.clone() }).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        assert_eq!(service.list_sprints(None).await?.len(), 3);
+        let next = service.plan_sprints(SprintPlan { start: None, count: 1, ..plan }).await?;
+        assert_eq!((next[0].name.as_str(), next[0].start_date.date_naive()), ("Sprint 4", date("2026-12-14")));
+
+        let overlapping = Sprint::new("Late".to_string(), planned[2].start_date, planned[2].end_date);
+        assert!(matches!(service.create_sprint(overlapping).await, Err(Error::ValidationError(_))));
+        let backwards = Sprint::new("Backwards".to_string(), planned[2].end_date, planned[2].start_date);
+        assert!(matches!(service.create_sprint(backwards).await, Err(Error::ValidationError(_))));
+
+        service.start_sprint(&planned[0].id).await?;
+        service.start_sprint(&planned[0].id).await?;
+        assert!(matches!(service.start_sprint(&planned[1].id).await, Err(Error::ValidationError(_))));
+        service.complete_sprint(&planned[0].id, None, CarryOverTarget::Keep).await?;
+        assert_eq!(service.start_sprint(&planned[1].id).await?.status, SprintStatus::Active);
+
+        // Editing goes through the same checks as creating and starting
+        let mut moved = service.get_sprint_by_id(&planned[2].id).await?.unwrap();
+        moved.start_date = planned[1].start_date;
+        assert!(matches!(service.update_sprint(moved.clone()).await, Err(Error::ValidationError(_))));
+        moved.start_date = planned[2].start_date;
+        moved.status = SprintStatus::Active;
+        assert!(matches!(service.update_sprint(moved.clone()).await, Err(Error::ValidationError(_))));
+        moved.status = SprintStatus::Cancelled;
+        moved.start_date = planned[1].start_date;
+        let cancelled = service.update_sprint(moved).await?;
+        let mut renamed = service.get_sprint_by_id(&planned[1].id).await?.unwrap();
+        renamed.name = "Renamed".to_string();
+        assert_eq!(service.update_sprint(renamed).await?.name, "Renamed");
+        assert_eq!(cancelled.status, SprintStatus::Cancelled);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_finished_sprints_cannot_restart_or_take_tasks() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = SprintService::new(db.clone());
+        let start = Utc::now();
+        let done = service.create_sprint(Sprint::new("Done".to_string(), start, start + Duration::days(14))).await?;
+        let mut cancelled = service.create_sprint(Sprint::new("Off".to_string(), start + Duration::days(14), start + Duration::days(28))).await?;
+        cancelled.status = SprintStatus::Cancelled;
+        let cancelled = service.update_sprint(cancelled).await?;
+        service.start_sprint(&done.id).await?;
+        service.complete_sprint(&done.id, None, CarryOverTarget::Keep).await?;
+
+        for sprint in [&done, &cancelled] {
+            assert!(matches!(service.start_sprint(&sprint.id).await, Err(Error::ValidationError(_))));
+            assert!(matches!(service.complete_sprint(&sprint.id, None, CarryOverTarget::Keep).await, Err(Error::ValidationError(_))));
+        }
+
+        let tasks = TaskService::new(db.clone());
+        let mut task = tasks.create_task(Task::new("TASK-1".to_string(), "Late".to_string(), TaskType::Task)).await?;
+        let result = tasks.bulk_assign_sprint(vec![task.id.clone()], done.id.clone(), None).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        task.sprint_id = Some(cancelled.id.clone());
+        assert!(matches!(tasks.update_task(task.clone()).await, Err(Error::ValidationError(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+// llmdoc/src/services/stats_service.rs
+
+use crate::core::errors::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use crate::core::backup;
+use crate::core::database::DbConnection;
+use crate::core::models::{Adr, Component, UserStory};
+use crate::core::repositories::{Entity, EntityKind, Record};
+use crate::core::schema;
+
+/// Entity tables written by SQL jobs and the typed column each one is broken down by.
+const ENTITY_BREAKDOWNS: &[(&str, &str, &str)] = &[
+    ("task", "tasks", "status"),
+    ("sprint", "sprints", "status"),
+];
+
+/// The typed column each entity type kept by the store is broken down by.
+const STORED_BREAKDOWNS: &[(&str, &str)] = &[("adr", "status"), ("component", "component_type"), ("user_story", "priority")];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EntityStats {
+    pub entity_type: String,
+    pub total: u64,   // Live rows
+    pub deleted: u64, // Rows in the trash
+    pub breakdown_by: String,
+    pub breakdown: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StorageStats {
+    pub journal_mode: String,
+    pub page_size: u64,
+    pub page_count: u64,
+    pub freelist_count: u64,
+    pub database_bytes: u64,
+    pub free_bytes: u64,
+    pub wal_bytes: Option<u64>, // None when there is no WAL file
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DatabaseStats {
+    pub schema_version: u32,
+    pub entities: Vec<EntityStats>,
+    pub history_entries: u64,
+    pub storage: StorageStats,
+    /// Share of live tasks with a stored embedding, or `None` if no embeddings table exists yet.
+    pub embedding_coverage: Option<f64>,
+    pub last_backup: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait StatsServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    async fn get_stats(&self) -> Result<DatabaseStats>;
+}
+
+#[derive(Clone)]
+pub struct StatsService {
+    db_connection: Arc<DbConnection>,
+}
+
+#[async_trait]
+impl StatsServiceTrait for StatsService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn get_stats(&self) -> Result<DatabaseStats> {
+        let (schema_version, mut entities, history_entries, mut storage, embedding_coverage) = self
+            .db_connection
+            .with_connection(|conn| {
+                let entities = ENTITY_BREAKDOWNS
+                    .iter()
+                    .map(|(entity_type, table, column)| entity_stats(conn, entity_type, table, column))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((
+                    schema::current_version(conn)?,
+                    entities,
+                    count(conn, "SELECT COUNT(*) FROM entity_history")?,
+                    storage_stats(conn)?,
+                    embedding_coverage(conn)?,
+                ))
+            })
+            .await?;
+        for (entity_type, column) in STORED_BREAKDOWNS {
+            entities.push(match *entity_type {
+                "adr" => stored_entity_stats::<Adr>(&self.db_connection, column).await?,
+                "component" => stored_entity_stats::<Component>(&self.db_connection, column).await?,
+                _ => stored_entity_stats::<UserStory>(&self.db_connection, column).await?,
+            });
+        }
+
+        if let Some(path) = self.db_connection.database_path() {
+            let wal = path.with_file_name(format!("{}-wal", path.file_name().unwrap_or_default().to_string_lossy()));
+            storage.wal_bytes = std::fs::metadata(wal).ok().map(|m| m.len());
+        }
+
+        let last_backup = match self.db_connection.backup_dir() {
+            Some(dir) => backup::list_backups(&dir)?.first().map(|(record, _)| record.created_at),
+            None => None,
+        };
+
+        Ok(DatabaseStats { schema_version, entities, history_entries, storage, embedding_coverage, last_backup })
+    }
+}
+
+fn entity_stats(conn: &Connection, entity_type: &str, table: &str, column: &str) -> Result<EntityStats> {
+    let mut breakdown = BTreeMap::new();
+    let sql = format!("SELECT {column}, COUNT(*) FROM {table} WHERE deleted_at IS NULL GROUP BY {column}");
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i64>(1)?)))?;
+    for row in rows {
+        let (value, n) = row?;
+        breakdown.insert(value.unwrap_or_else(|| "(none)".to_string()), n as u64);
+    }
+
+    Ok(EntityStats {
+        entity_type: entity_type.to_string(),
+        total: breakdown.values().sum(),
+        deleted: count(conn, &format!("SELECT COUNT(*) FROM {table} WHERE deleted_at IS NOT NULL"))?,
+        breakdown_by: column.to_string(),
+        breakdown,
+    })
+}
+
+/// Like [`entity_stats`], for an entity type kept by the store.
+async fn stored_entity_stats<E: Entity>(db: &DbConnection, column: &str) -> Result<EntityStats> {
+    let kind = EntityKind::of::<E>();
+    let mut breakdown = BTreeMap::new();
+    for entity in db.list_entities::<E>().await? {
+        let value = Record::of(&entity)?.column_text(&kind, column);
+        *breakdown.entry(value.unwrap_or_else(|| "(none)".to_string())).or_insert(0) += 1;
+    }
+
+    Ok(EntityStats {
+        entity_type: kind.entity_type.to_string(),
+        total: breakdown.values().sum(),
+        deleted: db.list_deleted_entities::<E>().await?.len() as u64,
+        breakdown_by: column.to_string(),
+        breakdown,
+    })
+}
+
+fn storage_stats(conn: &Connection) -> Result<StorageStats> {
+    let page_size = count(conn, "PRAGMA page_size")?;
+    let page_count = count(conn, "PRAGMA page_count")?;
+    let freelist_count = count(conn, "PRAGMA freelist_count")?;
+    let journal_mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+    Ok(StorageStats {
+        journal_mode,
+        page_size,
+        page_count,
+        freelist_count,
+        database_bytes: page_size * page_count,
+        free_bytes: page_size * freelist_count,
+        wal_bytes: None,
+    })
+}
+
+fn embedding_coverage(conn: &Connection) -> Result<Option<f64>> {
+    let has_table = count(conn, "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'embeddings'")? > 0;
+    if !has_table {
+        return Ok(None);
+    }
+    let tasks = count(conn, "SELECT COUNT(*) FROM tasks WHERE deleted_at IS NULL")?;
+    if tasks == 0 {
+        return Ok(Some(0.0));
+    }
+    let embedded = count(
+        conn,
+        "SELECT COUNT(DISTINCT e.entity_id) FROM embeddings e
+         JOIN tasks t ON t.id = e.entity_id WHERE t.deleted_at IS NULL",
+    )?;
+    Ok(Some(embedded as f64 * 100.0 / tasks as f64))
+}
+
+fn count(conn: &Connection, sql: &str) -> Result<u64> {
+    let n: i64 = conn.query_row(sql, [], |row| row.get(0))?;
+    Ok(n as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Task, TaskStatus, TaskType};
+    use crate::core::repositories::TaskRepository;
+
+    #[test]
+    fn test_entity_stats_counts_live_and_deleted_rows() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        schema::migrate_up(&mut conn, None)?;
+        let mut done = Task::new("TASK-1".to_string(), "Done".to_string(), TaskType::Task);
+        done.set_status(TaskStatus::Done, None);
+        TaskRepository::insert(&conn, &done)?;
+        TaskRepository::insert(&conn, &Task::new("TASK-2".to_string(), "Todo".to_string(), TaskType::Task))?;
+        TaskRepository::insert(&conn, &Task::new("TASK-3".to_string(), "Gone".to_string(), TaskType::Task))?;
+        TaskRepository::delete(&conn, "TASK-3")?;
+
+        let stats = entity_stats(&conn, "task", "tasks", "status")?;
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.deleted, 1);
+        assert_eq!(stats.breakdown.get("Done"), Some(&1));
+        assert_eq!(stats.breakdown.get("Todo"), Some(&1));
+        assert_eq!(embedding_coverage(&conn)?, None);
+        Ok(())
+    }
+}
@@ -4,66 +4,477 @@ use crate::core::errors::{Error, Result};
 use async_trait::async_trait;
 use std::sync::Arc;
 use crate::core::database::DbConnection;
-use crate::core::models::task::Task;
+use crate::core::models::history::HistoryEntry;
+use crate::core::models::sprint::SprintStatus;
+use crate::core::models::task::{Task, TaskStatus};
+use crate::core::models::task_filter::TaskFilter;
+use crate::core::workflow::Workflow;
+use crate::app_config::{CapacityConfig, KeysConfig};
+use crate::core::repositories::{HistoryRepository, Reference, SprintRepository, TaskRepository};
+use crate::services::capacity_service::CapacityService;
+use crate::services::dependency_service::DependencyService;
+use crate::services::hierarchy_service::HierarchyService;
+use crate::services::label_service::LabelService;
+use rusqlite::Connection;
 
 #[async_trait]
 pub trait TaskServiceTrait: Send + Sync {
     async fn create_task(&self, task: Task) -> Result<Task>;
     async fn get_task_by_id(&self, id: String) -> Result<Option<Task>>;
+    /// Saves `task` if the stored copy is still at `task.version`, returning it at the next version.
+    /// Fails with `Error::Conflict` if it was changed in the meantime, and with a `ValidationError`
+    /// if its status change is not allowed by the workflow.
     async fn update_task(&self, task: Task) -> Result<Task>;
-    async fn delete_task(&self, id: String) -> Result<()>;
+    async fn delete_task(&self, id: String) -> Result<Vec<Reference>>;
     async fn list_tasks(&self) -> Result<Vec<Task>>;
+    /// Tasks matching `filter`, sorted and paginated as it asks.
+    async fn find_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>>;
+    /// Number of tasks matching `filter`, ignoring its pagination.
+    async fn count_tasks(&self, filter: TaskFilter) -> Result<u64>;
+    async fn get_task_history(&self, id: String) -> Result<Vec<HistoryEntry>>;
+    /// Sets the status of every task, or of none if any of them cannot be updated
+    /// or the workflow does not allow the change.
+    async fn bulk_update_status(&self, ids: Vec<String>, status: TaskStatus, actor: Option<String>) -> Result<Vec<Task>>;
+    /// Moves every task into the sprint, or none of them if any ID is unknown or, when
+    /// capacity is enforced, the move takes an assignee over capacity.
+    async fn bulk_assign_sprint(&self, ids: Vec<String>, sprint_id: String, actor: Option<String>) -> Result<Vec<Task>>;
 }
 
 #[derive(Clone)]
 pub struct TaskService {
     db_connection: Arc<DbConnection>,
+    workflow: Arc<Workflow>,
+    key_prefix: String,
+    strict_labels: bool,
+    capacity: CapacityConfig,
 }
 
 impl TaskService {
+    /// A service enforcing the default workflow.
     pub fn new(db_connection: Arc<DbConnection>) -> Self {
-        Self { db_connection }
+        Self {
+            db_connection,
+            workflow: Arc::new(Workflow::default()),
+            key_prefix: KeysConfig::default().task,
+            strict_labels: false,
+            capacity: CapacityConfig::default(),
+        }
+    }
+
+    pub fn with_workflow(mut self, workflow: Workflow) -> Self {
+        self.workflow = Arc::new(workflow);
+        self
+    }
+
+    /// Prefix for the keys of new tasks, instead of `TASK`.
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Rejects tasks carrying labels that are not in the label registry.
+    pub fn with_strict_labels(mut self, strict: bool) -> Self {
+        self.strict_labels = strict;
+        self
+    }
+
+    /// Capacity settings; when enforced, sprint changes that take an assignee over capacity are rejected.
+    pub fn with_capacity(mut self, capacity: CapacityConfig) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Checks the capacity of `task`'s assignee in its sprint, once it is written.
+    fn check_capacity_in(conn: &Connection, task: &Task, capacity: &CapacityConfig) -> Result<()> {
+        match (&task.sprint_id, &task.assignee) {
+            (Some(sprint_id), Some(assignee)) => {
+                CapacityService::check_capacity_in(conn, sprint_id, std::slice::from_ref(assignee), capacity)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Changes one task's status on `conn`, for use inside a [`DbConnection::transaction`].
+    /// Dependent tasks are blocked or unblocked to match.
+    pub fn set_status_in(conn: &Connection, workflow: &Workflow, id: &str, status: TaskStatus, actor: Option<String>) -> Result<Task> {
+        let mut task = TaskRepository::get(conn, id)?
+            .ok_or_else(|| Error::NotFound(format!("Task {} not found", id)))?;
+        let from = task.status.clone();
+        task.set_status(status, actor);
+        workflow.check_transition(&from, &task)?;
+        TaskRepository::update(conn, &mut task)?;
+        Self::sync_dependencies_in(conn, task)
+    }
+
+    /// Fails with a `ValidationError` if `task` starts after it is due, or if its start or
+    /// due date falls outside the window of its sprint.
+    pub fn check_dates_in(conn: &Connection, task: &Task) -> Result<()> {
+        if let (Some(start), Some(due)) = (task.start_date, task.due_date) {
+            if start > due {
+                return Err(Error::ValidationError(format!("Task {} starts on {}, after it is due on {}", task.id, start, due)));
+            }
+        }
+        let Some(sprint_id) = task.sprint_id.as_deref().filter(|_| task.start_date.is_some() || task.due_date.is_some()) else {
+            return Ok(());
+        };
+        // A missing sprint is a dangling reference, reported by `db verify` rather than here
+        let Some(sprint) = SprintRepository::get(conn, sprint_id)? else {
+            return Ok(());
+        };
+        let (first, last) = (sprint.start_date.date_naive(), sprint.end_date.date_naive());
+        for (what, date) in [("start date", task.start_date), ("due date", task.due_date)] {
+            if let Some(date) = date.filter(|date| *date < first || *date > last) {
+                return Err(Error::ValidationError(format!(
+                    "Task {} {} {} is outside sprint {} ({} to {})",
+                    task.id, what, date, sprint.name, first, last
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears whichever of `task`'s start and due dates fall outside the window of its sprint,
+    /// as when it is carried over into a later sprint.
+    pub fn clear_dates_outside_sprint_in(conn: &Connection, task: &mut Task) -> Result<()> {
+        let Some(sprint) = task.sprint_id.as_deref().map(|id| SprintRepository::get(conn, id)).transpose()?.flatten() else {
+            return Ok(());
+        };
+        let (first, last) = (sprint.start_date.date_naive(), sprint.end_date.date_naive());
+        let outside = |date: &Option<chrono::NaiveDate>| date.is_some_and(|date| date < first || date > last);
+        if outside(&task.start_date) {
+            task.start_date = None;
+        }
+        if outside(&task.due_date) {
+            task.due_date = None;
+        }
+        Ok(())
+    }
+
+    /// Fails with a `ValidationError` if the sprint is completed or cancelled, so no longer takes tasks.
+    pub fn check_sprint_open_in(conn: &Connection, sprint_id: &str) -> Result<()> {
+        match SprintRepository::get(conn, sprint_id)? {
+            Some(sprint) if matches!(sprint.status, SprintStatus::Completed | SprintStatus::Cancelled) => {
+                Err(Error::ValidationError(format!(
+                    "Sprint {} is {}; tasks can no longer be added to it",
+                    sprint.name,
+                    sprint.status.to_string().to_lowercase()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies automatic blocking after `task` was written, and returns it as now stored.
+    fn sync_dependencies_in(conn: &Connection, task: Task) -> Result<Task> {
+        let changed = DependencyService::sync_blocking_in(conn, std::slice::from_ref(&task.id))?;
+        Ok(changed.into_iter().find(|t| t.id == task.id).unwrap_or(task))
+    }
+
+    /// Moves one task into a sprint on `conn`, for use inside a [`DbConnection::transaction`].
+    pub fn assign_sprint_in(conn: &Connection, id: &str, sprint_id: &str, actor: Option<String>) -> Result<Task> {
+        let mut task = TaskRepository::get(conn, id)?
+            .ok_or_else(|| Error::NotFound(format!("Task {} not found", id)))?;
+        if task.sprint_id.as_deref() != Some(sprint_id) {
+            Self::check_sprint_open_in(conn, sprint_id)?;
+        }
+        task.sprint_id = Some(sprint_id.to_string());
+        task.updated_at = chrono::Utc::now();
+        task.updated_by = actor;
+        Self::check_dates_in(conn, &task)?;
+        TaskRepository::update(conn, &mut task)?;
+        Ok(task)
     }
 }
 
 #[async_trait]
 impl TaskServiceTrait for TaskService {
-    async fn create_task(&self, task: Task) -> Result<Task> {
-        let conn = self.db_connection.pool.get().await
-            .map_err(|e| Error::DatabaseOperation(format!("Failed to get DB connection: {}", e)))?;
-        
-        let task_json = serde_json::to_string(&task)
-            .map_err(|e| Error::JsonError(e))?;
-        
-        conn.interact(move |conn| {
-            conn.execute(
-                "INSERT INTO tasks (task_id, data) VALUES (?1, ?2)",
-                rusqlite::params![&task.id, &task_json],
-            )?;
-            Ok(task)
-        })
-        .await
-        .map_err(|e| Error::DatabaseOperation(format!("Interact error: {}", e)))?
-        .map_err(|e: rusqlite::Error| Error::DatabaseOperation(format!("SQL execution error: {}", e)))
+    async fn create_task(&self, mut task: Task) -> Result<Task> {
+        self.workflow.check_initial(&task)?;
+        if task.status == TaskStatus::Done && task.completed_at.is_none() {
+            task.completed_at = Some(task.updated_at);
+        }
+        if task.id.trim().is_empty() {
+            task.id = crate::utils::new_uuid_v4_str();
+        }
+        let key_prefix = self.key_prefix.clone();
+        let strict_labels = self.strict_labels;
+        let capacity = self.capacity.clone();
+        self.db_connection.transaction(move |tx| {
+            if let Some(sprint_id) = task.sprint_id.as_deref() {
+                Self::check_sprint_open_in(tx, sprint_id)?;
+            }
+            HierarchyService::check_parent_in(tx, &task)?;
+            DependencyService::check_cycles_in(tx, &task)?;
+            Self::check_dates_in(tx, &task)?;
+            LabelService::check_labels_in(tx, &mut task, strict_labels)?;
+            TaskRepository::assign_key(tx, &mut task, &key_prefix)?;
+            TaskRepository::insert(tx, &task)?;
+            Self::check_capacity_in(tx, &task, &capacity)?;
+            Self::sync_dependencies_in(tx, task)
+        }).await
     }
 
-    async fn get_task_by_id(&self, _id: String) -> Result<Option<Task>> {
-        // Implementation placeholder
-        Err(Error::OperationFailed("Not implemented".to_string()))
+    async fn get_task_by_id(&self, id: String) -> Result<Option<Task>> {
+        self.db_connection.with_connection(move |conn| TaskRepository::get(conn, &id)).await
     }
 
-    async fn update_task(&self, _task: Task) -> Result<Task> {
-        // Implementation placeholder
-        Err(Error::OperationFailed("Not implemented".to_string()))
+    async fn update_task(&self, mut task: Task) -> Result<Task> {
+        let workflow = self.workflow.clone();
+        let strict_labels = self.strict_labels;
+        let capacity = self.capacity.clone();
+        self.db_connection.transaction(move |tx| {
+            let stored = TaskRepository::get(tx, &task.id)?
+                .ok_or_else(|| Error::NotFound(format!("Task {} not found", task.id)))?;
+            if task.status != stored.status {
+                workflow.check_transition(&stored.status, &task)?;
+                // Goes through set_status so completed_at follows the new status
+                task.set_status(task.status.clone(), task.updated_by.clone());
+            }
+            if let Some(sprint_id) = task.sprint_id.as_deref().filter(|id| stored.sprint_id.as_deref() != Some(*id)) {
+                Self::check_sprint_open_in(tx, sprint_id)?;
+            }
+            HierarchyService::check_parent_in(tx, &task)?;
+            DependencyService::check_cycles_in(tx, &task)?;
+            Self::check_dates_in(tx, &task)?;
+            LabelService::check_labels_in(tx, &mut task, strict_labels)?;
+            if !TaskRepository::update(tx, &mut task)? {
+                return Err(Error::NotFound(format!("Task {} not found", task.id)));
+            }
+            if (&task.sprint_id, &task.assignee, task.story_points) != (&stored.sprint_id, &stored.assignee, stored.story_points) {
+                Self::check_capacity_in(tx, &task, &capacity)?;
+            }
+            Self::sync_dependencies_in(tx, task)
+        }).await
     }
 
-    async fn delete_task(&self, _id: String) -> Result<()> {
-        // Implementation placeholder
-        Err(Error::OperationFailed("Not implemented".to_string()))
+    async fn delete_task(&self, id: String) -> Result<Vec<Reference>> {
+        let task_id = id.clone();
+        self.db_connection.transaction(move |tx| {
+            if !TaskRepository::delete(tx, &task_id)? {
+                return Err(Error::NotFound(format!("Task {} not found", task_id)));
+            }
+            // A deleted dependency no longer holds anything up
+            DependencyService::sync_blocking_in(tx, std::slice::from_ref(&task_id))
+        }).await?;
+        self.db_connection.references_to(&id).await
     }
 
     async fn list_tasks(&self) -> Result<Vec<Task>> {
-        // Implementation placeholder
-        Err(Error::OperationFailed("Not implemented".to_string()))
+        self.db_connection.with_connection(|conn| TaskRepository::list(conn)).await
+    }
+
+    async fn find_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>> {
+        self.db_connection.with_connection(move |conn| TaskRepository::find(conn, &filter)).await
     }
-}
\ No newline at end of file
+
+    async fn count_tasks(&self, filter: TaskFilter) -> Result<u64> {
+        self.db_connection.with_connection(move |conn| TaskRepository::count_matching(conn, &filter)).await
+    }
+
+    async fn get_task_history(&self, id: String) -> Result<Vec<HistoryEntry>> {
+        self.db_connection.with_connection(move |conn| HistoryRepository::list_for(conn, "task", &id)).await
+    }
+
+    async fn bulk_update_status(&self, ids: Vec<String>, status: TaskStatus, actor: Option<String>) -> Result<Vec<Task>> {
+        let workflow = self.workflow.clone();
+        self.db_connection.transaction(move |tx| {
+            ids.iter()
+                .map(|id| Self::set_status_in(tx, &workflow, id, status.clone(), actor.clone()))
+                .collect()
+        }).await
+    }
+
+    async fn bulk_assign_sprint(&self, ids: Vec<String>, sprint_id: String, actor: Option<String>) -> Result<Vec<Task>> {
+        let capacity = self.capacity.clone();
+        self.db_connection.transaction(move |tx| {
+            if !SprintRepository::exists(tx, &sprint_id)? {
+                return Err(Error::NotFound(format!("Sprint {} not found", sprint_id)));
+            }
+            let tasks = ids.iter()
+                .map(|id| Self::assign_sprint_in(tx, id, &sprint_id, actor.clone()))
+                .collect::<Result<Vec<Task>>>()?;
+            let assignees: Vec<String> = tasks.iter().filter_map(|t| t.assignee.clone()).collect();
+            CapacityService::check_capacity_in(tx, &sprint_id, &assignees, &capacity)?;
+            Ok(tasks)
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::TaskType;
+
+    #[tokio::test]
+    async fn test_bulk_update_rolls_back_when_any_task_is_missing() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = TaskService::new(db);
+        service.create_task(Task::new("TASK-1".to_string(), "One".to_string(), TaskType::Task)).await?;
+        service.create_task(Task::new("TASK-2".to_string(), "Two".to_string(), TaskType::Task)).await?;
+
+        let ids = vec!["TASK-1".to_string(), "TASK-404".to_string()];
+        let result = service.bulk_update_status(ids, TaskStatus::Done, None).await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        let task = service.get_task_by_id("TASK-1".to_string()).await?.unwrap();
+        assert_eq!(task.status, TaskStatus::Todo);
+        assert_eq!(service.get_task_history("TASK-1".to_string()).await?.len(), 1);
+
+        let ids = vec!["TASK-1".to_string(), "TASK-2".to_string()];
+        let updated = service.bulk_update_status(ids, TaskStatus::Done, None).await?;
+        assert!(updated.iter().all(|t| t.status == TaskStatus::Done && t.completed_at.is_some()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dependencies_block_until_finished_and_reject_cycles() -> Result<()> {
+        let service = TaskService::new(Arc::new(DbConnection::in_memory()?));
+        service.db_connection.run_migrations().await?;
+        service.create_task(Task::new("TASK-1".to_string(), "Schema".to_string(), TaskType::Task)).await?;
+        let mut api = Task::new("TASK-2".to_string(), "API".to_string(), TaskType::Task);
+        api.dependencies = vec!["TASK-1".to_string()];
+        let api = service.create_task(api).await?;
+        assert_eq!(api.status, TaskStatus::Blocked);
+
+        let mut schema = service.get_task_by_id("TASK-1".to_string()).await?.unwrap();
+        schema.dependencies = vec!["TASK-2".to_string()];
+        assert!(matches!(service.update_task(schema).await, Err(Error::ValidationError(_))));
+
+        service.bulk_update_status(vec!["TASK-1".to_string()], TaskStatus::Done, None).await?;
+        let api = service.get_task_by_id("TASK-2".to_string()).await?.unwrap();
+        assert_eq!(api.status, TaskStatus::Todo);
+        assert_eq!(api.updated_by.as_deref(), Some(crate::services::dependency_service::DEPENDENCY_ACTOR));
+
+        // Work already under way is blocked by a new dependency and resumes once it is done
+        service.bulk_update_status(vec!["TASK-2".to_string()], TaskStatus::InProgress, None).await?;
+        service.create_task(Task::new("TASK-3".to_string(), "Auth".to_string(), TaskType::Task)).await?;
+        let mut api = service.get_task_by_id("TASK-2".to_string()).await?.unwrap();
+        api.dependencies.push("TASK-3".to_string());
+        let api = service.update_task(api).await?;
+        assert_eq!((api.status, api.status_before_block), (TaskStatus::Blocked, Some(TaskStatus::InProgress)));
+        service.bulk_update_status(vec!["TASK-3".to_string()], TaskStatus::Done, None).await?;
+        let api = service.get_task_by_id("TASK-2".to_string()).await?.unwrap();
+        assert_eq!((api.status, api.status_before_block), (TaskStatus::InProgress, None));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_follow_the_workflow() -> Result<()> {
+        let mut config = crate::app_config::WorkflowConfig::default();
+        config.required_fields.insert("in_progress".to_string(), vec!["assignee".to_string()]);
+        let service = TaskService::new(Arc::new(DbConnection::in_memory()?)).with_workflow(Workflow::from_config(&config)?);
+        service.db_connection.run_migrations().await?;
+        let mut task = service.create_task(Task::new("TASK-1".to_string(), "One".to_string(), TaskType::Task)).await?;
+
+        task.status = TaskStatus::InProgress;
+        assert!(matches!(service.update_task(task.clone()).await, Err(Error::ValidationError(_))));
+        task.assignee = Some("alice".to_string());
+        task.status = TaskStatus::Done;
+        let mut task = service.update_task(task).await?;
+        assert!(task.completed_at.is_some());
+
+        task.status = TaskStatus::Cancelled;
+        let result = service.update_task(task).await;
+        assert!(matches!(result, Err(Error::ValidationError(ref msg)) if msg.contains("allowed next states: Todo, InProgress")));
+        let result = service.bulk_update_status(vec!["TASK-1".to_string()], TaskStatus::Blocked, None).await;
+        assert!(matches!(result, Err(Error::ValidationError(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dates_stay_within_the_sprint_and_drive_overdue_filters() -> Result<()> {
+        use crate::core::models::sprint::Sprint;
+        use crate::services::sprint_service::{SprintService, SprintServiceTrait};
+        use chrono::{Days, Local, NaiveDate};
+
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = TaskService::new(db.clone());
+        let today = Local::now().date_naive();
+        let day = |offset: i64| today + chrono::Duration::days(offset);
+        let at = |date: NaiveDate, hour: u32| date.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        let sprint = Sprint::new("Next sprint".to_string(), at(day(10), 9), at(day(20), 17));
+        let sprint = SprintService::new(db).create_sprint(sprint).await?;
+
+        let mut task = Task::new("TASK-1".to_string(), "Planned".to_string(), TaskType::Task);
+        task.sprint_id = Some(sprint.id.clone());
+        task.start_date = Some(day(10));
+        task.due_date = Some(day(21));
+        assert!(matches!(service.create_task(task.clone()).await, Err(Error::ValidationError(_))));
+        task.start_date = Some(day(15));
+        task.due_date = Some(day(14));
+        assert!(matches!(service.create_task(task.clone()).await, Err(Error::ValidationError(_))));
+        task.due_date = Some(day(20));
+        service.create_task(task).await?;
+
+        let mut late = Task::new("TASK-2".to_string(), "Late".to_string(), TaskType::Task);
+        late.due_date = today.checked_sub_days(Days::new(2));
+        service.create_task(late).await?;
+        let mut soon = Task::new("TASK-3".to_string(), "Soon".to_string(), TaskType::Task);
+        soon.due_date = today.checked_add_days(Days::new(2));
+        let soon = service.create_task(soon).await?;
+        assert!(matches!(
+            service.bulk_assign_sprint(vec![soon.id.clone()], sprint.id.clone(), None).await,
+            Err(Error::ValidationError(_))
+        ));
+
+        let due_by = |days: i64| TaskFilter { due_by: Some(day(days)), ..Default::default() };
+        let ids = |tasks: Vec<Task>| tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(ids(service.find_tasks(due_by(-1)).await?), vec!["TASK-2".to_string()]);
+        assert_eq!(service.count_tasks(due_by(3)).await?, 2);
+        service.bulk_update_status(vec!["TASK-2".to_string()], TaskStatus::Done, None).await?;
+        assert_eq!(ids(service.find_tasks(due_by(3)).await?), vec!["TASK-3".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_closed_sprints_take_no_tasks_and_updates_keep_dates_inside() -> Result<()> {
+        use crate::core::models::sprint::{Sprint, SprintStatus};
+        use crate::services::sprint_service::{CarryOverTarget, SprintService, SprintServiceTrait};
+        use chrono::NaiveDate;
+
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let service = TaskService::new(db.clone());
+        let sprints = SprintService::new(db);
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        let at = |d: &str| date(d).and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let closed = sprints.create_sprint(Sprint::new("Closed".to_string(), at("2026-03-02"), at("2026-03-14"))).await?;
+        let mut cancelled = sprints.create_sprint(Sprint::new("Off".to_string(), at("2026-03-16"), at("2026-03-28"))).await?;
+        let open = sprints.create_sprint(Sprint::new("Open".to_string(), at("2026-03-30"), at("2026-04-11"))).await?;
+        sprints.start_sprint(&closed.id).await?;
+        sprints.complete_sprint(&closed.id, None, CarryOverTarget::Keep).await?;
+        cancelled.status = SprintStatus::Cancelled;
+        sprints.update_sprint(cancelled.clone()).await?;
+
+        for sprint_id in [&closed.id, &cancelled.id] {
+            let mut task = Task::new("TASK-1".to_string(), "Late".to_string(), TaskType::Task);
+            task.sprint_id = Some(sprint_id.clone());
+            assert!(matches!(service.create_task(task).await, Err(Error::ValidationError(_))));
+        }
+        assert!(service.list_tasks().await?.is_empty());
+
+        let mut task = Task::new("TASK-1".to_string(), "Planned".to_string(), TaskType::Task);
+        task.sprint_id = Some(open.id.clone());
+        task.due_date = Some(date("2026-04-10"));
+        let task = service.create_task(task).await?;
+        for sprint_id in [&closed.id, &cancelled.id] {
+            let moved = Task { sprint_id: Some(sprint_id.clone()), due_date: None, ..task.clone() };
+            assert!(matches!(service.update_task(moved).await, Err(Error::ValidationError(_))));
+        }
+
+        // Either date outside the sprint window is rejected, including a day past its end
+        for (start, due) in [(Some(date("2026-03-29")), None), (None, Some(date("2026-04-12")))] {
+            let moved = Task { start_date: start, due_date: due, ..task.clone() };
+            assert!(matches!(service.update_task(moved).await, Err(Error::ValidationError(_))));
+        }
+        let stored = service.get_task_by_id(task.id.clone()).await?.unwrap();
+        assert_eq!((stored.sprint_id, stored.start_date, stored.due_date), (Some(open.id.clone()), None, Some(date("2026-04-10"))));
+
+        // Leaving the sprint lifts its window
+        let unplanned = Task { sprint_id: None, due_date: Some(date("2026-05-01")), ..stored };
+        let unplanned = service.update_task(unplanned).await?;
+        assert_eq!(unplanned.due_date, Some(date("2026-05-01")));
+        Ok(())
+    }
+}
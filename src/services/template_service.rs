@@ -0,0 +1,102 @@
+// llmdoc/src/services/template_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::models::task_template::TaskTemplate;
+
+#[async_trait]
+pub trait TemplateServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// The built-in templates and those in the templates directory, by name. A file
+    /// named like a built-in template replaces it.
+    async fn list_templates(&self) -> Result<Vec<TaskTemplate>>;
+    async fn get_template(&self, name: &str) -> Result<TaskTemplate>;
+}
+
+#[derive(Clone)]
+pub struct TemplateService {
+    templates_dir: Option<PathBuf>,
+}
+
+impl TemplateService {
+    /// Reads templates from `dir` instead of the one next to the database.
+    pub fn with_templates_dir(mut self, dir: PathBuf) -> Self {
+        self.templates_dir = Some(dir);
+        self
+    }
+
+    fn load_dir(&self, templates: &mut BTreeMap<String, TaskTemplate>) -> Result<()> {
+        let Some(dir) = self.templates_dir.as_ref().filter(|dir| dir.is_dir()) else {
+            return Ok(());
+        };
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        paths.sort();
+        for path in paths {
+            let (Some(name), Some(extension)) = (
+                path.file_stem().and_then(|s| s.to_str()),
+                path.extension().and_then(|s| s.to_str()),
+            ) else {
+                continue;
+            };
+            if !["toml", "json"].contains(&extension.to_lowercase().as_str()) {
+                continue;
+            }
+            let mut template = TaskTemplate::parse(name, &std::fs::read_to_string(&path)?, extension)?;
+            template.source = path.display().to_string();
+            templates.insert(name.to_string(), template);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TemplateServiceTrait for TemplateService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { templates_dir: db_connection.templates_dir() }
+    }
+
+    async fn list_templates(&self) -> Result<Vec<TaskTemplate>> {
+        let mut templates: BTreeMap<String, TaskTemplate> =
+            TaskTemplate::builtin().into_iter().map(|t| (t.name.clone(), t)).collect();
+        self.load_dir(&mut templates)?;
+        Ok(templates.into_values().collect())
+    }
+
+    async fn get_template(&self, name: &str) -> Result<TaskTemplate> {
+        let templates = self.list_templates().await?;
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        let not_found = Error::NotFound(format!("Template {} not found (available: {})", name, names.join(", ")));
+        templates.into_iter().find(|t| t.name == name).ok_or(not_found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_files_add_to_and_replace_builtin_templates() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("bug.toml"), "title = \"BUG: {{title}}\"\ntask_type = \"bug\"\n")?;
+        std::fs::write(dir.path().join("release.json"), r#"{"title": "Release {{version}}", "labels": ["release"]}"#)?;
+        std::fs::write(dir.path().join("README.md"), "Not a template")?;
+        let service = TemplateService::new(Arc::new(DbConnection::in_memory()?)).with_templates_dir(dir.path().to_path_buf());
+
+        let names: Vec<String> = service.list_templates().await?.into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["bug", "chore", "release", "spike"]);
+        let bug = service.get_template("bug").await?;
+        assert!(bug.source.ends_with("bug.toml"));
+        assert_eq!(service.get_template("release").await?.required_placeholders()?, vec!["version".to_string()]);
+        assert!(matches!(service.get_template("feature").await, Err(Error::NotFound(_))));
+
+        std::fs::write(dir.path().join("broken.toml"), "priority = \"urgent\"\n")?;
+        assert!(matches!(service.list_templates().await, Err(Error::ValidationError(_))));
+        Ok(())
+    }
+}
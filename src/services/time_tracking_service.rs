@@ -0,0 +1,233 @@
+// llmdoc/src/services/time_tracking_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::dependency_graph::is_finished;
+use crate::core::models::task::Task;
+use crate::core::models::work_log::WorkLogEntry;
+use crate::core::repositories::{TaskRepository, WorkLogRepository};
+
+/// How `get_report` groups tasks and logged time.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGrouping {
+    Task,
+    Sprint,
+    /// Estimates by task assignee, logged time by the author of each entry.
+    Assignee,
+}
+
+impl std::str::FromStr for TimeGrouping {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "task" => Ok(TimeGrouping::Task),
+            "sprint" => Ok(TimeGrouping::Sprint),
+            "assignee" | "author" => Ok(TimeGrouping::Assignee),
+            _ => Err(Error::InvalidInput(format!("Unknown grouping: {}", s))),
+        }
+    }
+}
+
+/// Estimated against logged time for a task, sprint, assignee or the whole report.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimeSummary {
+    pub key: String,
+    pub tasks: u32,
+    pub original_estimate_minutes: u32,
+    pub remaining_estimate_minutes: u32,
+    pub logged_minutes: u32,
+}
+
+impl TimeSummary {
+    fn add_estimates(&mut self, task: &Task, logged: u32) {
+        self.tasks += 1;
+        self.original_estimate_minutes += task.original_estimate_minutes.unwrap_or(0);
+        self.remaining_estimate_minutes += remaining_minutes(task, logged);
+    }
+
+    /// Logged plus remaining time minus the original estimate; positive means over estimate.
+    pub fn variance_minutes(&self) -> i64 {
+        i64::from(self.logged_minutes) + i64::from(self.remaining_estimate_minutes) - i64::from(self.original_estimate_minutes)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeReport {
+    pub group_by: TimeGrouping,
+    pub rows: Vec<TimeSummary>,
+    pub total: TimeSummary,
+}
+
+/// Time still to be spent on `task`: its remaining estimate if set, otherwise what is
+/// left of the original estimate. Finished tasks have none left.
+pub fn remaining_minutes(task: &Task, logged: u32) -> u32 {
+    if is_finished(&task.status) {
+        return 0;
+    }
+    task.remaining_estimate_minutes
+        .or_else(|| task.original_estimate_minutes.map(|original| original.saturating_sub(logged)))
+        .unwrap_or(0)
+}
+
+#[async_trait]
+pub trait TimeTrackingServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    /// Records `entry` and counts the task's remaining estimate down by it, or sets it to
+    /// `remaining_minutes` when given. Returns the stored entry and the task as now stored.
+    async fn log_work(&self, entry: WorkLogEntry, remaining_minutes: Option<u32>) -> Result<(WorkLogEntry, Task)>;
+    async fn get_work_log(&self, task_id: &str) -> Result<Vec<WorkLogEntry>>;
+    /// Estimated against logged time, grouped as asked, optionally for one sprint only.
+    async fn get_report(&self, group_by: TimeGrouping, sprint_id: Option<String>) -> Result<TimeReport>;
+}
+
+#[derive(Clone)]
+pub struct TimeTrackingService {
+    db_connection: Arc<DbConnection>,
+}
+
+impl TimeTrackingService {
+    /// Estimated against logged time across `tasks`, for use inside another service's unit of work.
+    pub fn summarize_in(conn: &Connection, tasks: &[Task]) -> Result<TimeSummary> {
+        let mut summary = TimeSummary::default();
+        for task in tasks {
+            let logged = WorkLogRepository::total_for_task(conn, &task.id)?;
+            summary.add_estimates(task, logged);
+            summary.logged_minutes += logged;
+        }
+        Ok(summary)
+    }
+}
+
+#[async_trait]
+impl TimeTrackingServiceTrait for TimeTrackingService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn log_work(&self, mut entry: WorkLogEntry, remaining_minutes: Option<u32>) -> Result<(WorkLogEntry, Task)> {
+        if entry.duration_minutes == 0 {
+            return Err(Error::ValidationError("Logged time must be more than zero".to_string()));
+        }
+        self.db_connection.transaction(move |tx| {
+            let mut task = TaskRepository::get(tx, &entry.task_id)?
+                .ok_or_else(|| Error::NotFound(format!("Task {} not found", entry.task_id)))?;
+            entry.id = WorkLogRepository::insert(tx, &entry)?;
+
+            let remaining = match (remaining_minutes, task.remaining_estimate_minutes, task.original_estimate_minutes) {
+                (Some(remaining), _, _) => Some(remaining),
+                (None, Some(remaining), _) => Some(remaining.saturating_sub(entry.duration_minutes)),
+                (None, None, Some(original)) => Some(original.saturating_sub(WorkLogRepository::total_for_task(tx, &task.id)?)),
+                (None, None, None) => None,
+            };
+            if remaining != task.remaining_estimate_minutes {
+                task.remaining_estimate_minutes = remaining;
+                task.updated_at = chrono::Utc::now();
+                task.updated_by = entry.author.clone();
+                TaskRepository::update(tx, &mut task)?;
+            }
+            Ok((entry, task))
+        }).await
+    }
+
+    async fn get_work_log(&self, task_id: &str) -> Result<Vec<WorkLogEntry>> {
+        let task_id = task_id.to_string();
+        self.db_connection.with_connection(move |conn| {
+            if !TaskRepository::exists(conn, &task_id)? {
+                return Err(Error::NotFound(format!("Task {} not found", task_id)));
+            }
+            WorkLogRepository::list_for_task(conn, &task_id)
+        }).await
+    }
+
+    async fn get_report(&self, group_by: TimeGrouping, sprint_id: Option<String>) -> Result<TimeReport> {
+        self.db_connection.with_connection(move |conn| {
+            let tasks: Vec<Task> = match &sprint_id {
+                Some(sprint_id) => TaskRepository::list_by_sprint(conn, sprint_id)?,
+                None => TaskRepository::list(conn)?,
+            };
+            let tasks: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+            let entries: Vec<WorkLogEntry> = WorkLogRepository::list_all(conn)?
+                .into_iter()
+                .filter(|e| tasks.contains_key(e.task_id.as_str()))
+                .collect();
+
+            let mut logged_by_task: HashMap<&str, u32> = HashMap::new();
+            for entry in &entries {
+                *logged_by_task.entry(entry.task_id.as_str()).or_default() += entry.duration_minutes;
+            }
+
+            let key_for_task = |task: &Task| match group_by {
+                TimeGrouping::Task => task.id.clone(),
+                TimeGrouping::Sprint => task.sprint_id.clone().unwrap_or_else(|| "(no sprint)".to_string()),
+                TimeGrouping::Assignee => task.assignee.clone().unwrap_or_else(|| "(unassigned)".to_string()),
+            };
+            let mut rows: BTreeMap<String, TimeSummary> = BTreeMap::new();
+            for task in tasks.values() {
+                let key = key_for_task(task);
+                let logged = logged_by_task.get(task.id.as_str()).copied().unwrap_or(0);
+                rows.entry(key.clone()).or_insert_with(|| TimeSummary { key, ..Default::default() }).add_estimates(task, logged);
+            }
+            for entry in &entries {
+                let key = match group_by {
+                    TimeGrouping::Assignee => entry.author.clone().unwrap_or_else(|| "(unknown)".to_string()),
+                    _ => key_for_task(tasks[entry.task_id.as_str()]),
+                };
+                rows.entry(key.clone()).or_insert_with(|| TimeSummary { key, ..Default::default() }).logged_minutes += entry.duration_minutes;
+            }
+
+            let mut total = TimeSummary { key: "Total".to_string(), ..Default::default() };
+            for row in rows.values() {
+                total.tasks += row.tasks;
+                total.original_estimate_minutes += row.original_estimate_minutes;
+                total.remaining_estimate_minutes += row.remaining_estimate_minutes;
+                total.logged_minutes += row.logged_minutes;
+            }
+            Ok(TimeReport { group_by, rows: rows.into_values().collect(), total })
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::task::TaskType;
+    use crate::services::task_service::{TaskService, TaskServiceTrait};
+
+    #[tokio::test]
+    async fn test_logging_counts_down_remaining_and_feeds_reports() -> Result<()> {
+        let db = Arc::new(DbConnection::in_memory()?);
+        db.run_migrations().await?;
+        let tasks = TaskService::new(db.clone());
+        let service = TimeTrackingService::new(db);
+
+        let mut task = Task::new("TASK-1".to_string(), "Estimated".to_string(), TaskType::Task);
+        task.assignee = Some("alice".to_string());
+        task.original_estimate_minutes = Some(300);
+        tasks.create_task(task).await?;
+        tasks.create_task(Task::new("TASK-2".to_string(), "Unestimated".to_string(), TaskType::Task)).await?;
+
+        let (_, task) = service.log_work(WorkLogEntry::new("TASK-1".to_string(), 120, Some("alice".to_string()), None), None).await?;
+        assert_eq!(task.remaining_estimate_minutes, Some(180));
+        let (_, task) = service.log_work(WorkLogEntry::new("TASK-1".to_string(), 60, Some("bob".to_string()), None), None).await?;
+        assert_eq!(task.remaining_estimate_minutes, Some(120));
+        let (_, task) = service.log_work(WorkLogEntry::new("TASK-2".to_string(), 30, None, None), Some(15)).await?;
+        assert_eq!(task.remaining_estimate_minutes, Some(15));
+        assert!(service.log_work(WorkLogEntry::new("TASK-404".to_string(), 30, None, None), None).await.is_err());
+        assert_eq!(service.get_work_log("TASK-1").await?.len(), 2);
+
+        let report = service.get_report(TimeGrouping::Assignee, None).await?;
+        let row = |key: &str| report.rows.iter().find(|r| r.key == key).cloned().unwrap();
+        assert_eq!((row("alice").original_estimate_minutes, row("alice").logged_minutes), (300, 120));
+        assert_eq!(row("bob").logged_minutes, 60);
+        assert_eq!(report.total.logged_minutes, 210);
+        assert_eq!(report.total.remaining_estimate_minutes, 135);
+        Ok(())
+    }
+}
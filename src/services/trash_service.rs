@@ -0,0 +1,107 @@
+// llmdoc/src/services/trash_service.rs
+
+use crate::core::errors::{Error, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use crate::core::database::DbConnection;
+use crate::core::models::{Adr, Component, UserStory};
+use crate::core::repositories::{Tombstone, TrashRepository};
+use crate::core::store::{self, STORED_KINDS};
+
+#[async_trait]
+pub trait TrashServiceTrait: Send + Sync {
+    fn new(db_connection: Arc<DbConnection>) -> Self;
+    async fn list_trash(&self, entity_type: Option<String>) -> Result<Vec<Tombstone>>;
+    /// Restores a deleted entity by ID and returns its entity type.
+    async fn restore(&self, id: &str) -> Result<String>;
+    async fn purge(&self, id: &str) -> Result<Tombstone>;
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<Tombstone>>;
+}
+
+#[derive(Clone)]
+pub struct TrashService {
+    db_connection: Arc<DbConnection>,
+}
+
+impl TrashService {
+    /// Deleted entities kept by the store, of every stored kind (or just `entity_type`).
+    async fn list_stored(&self, entity_type: Option<&str>) -> Result<Vec<Tombstone>> {
+        let mut tombstones = Vec::new();
+        for kind in STORED_KINDS {
+            if entity_type.is_some_and(|t| t != kind.entity_type) {
+                continue;
+            }
+            tombstones.extend(match kind.entity_type {
+                "adr" => self.db_connection.list_deleted_entities::<Adr>().await?,
+                "component" => self.db_connection.list_deleted_entities::<Component>().await?,
+                _ => self.db_connection.list_deleted_entities::<UserStory>().await?,
+            });
+        }
+        Ok(tombstones)
+    }
+
+    /// Permanently removes a deleted entity, from the store or from its table.
+    async fn purge_tombstone(&self, tombstone: &Tombstone) -> Result<bool> {
+        match store::stored_kind(&tombstone.entity_type) {
+            Some(kind) => self.db_connection.store().purge(kind, &tombstone.id).await,
+            None => {
+                let (entity_type, id) = (tombstone.entity_type.clone(), tombstone.id.clone());
+                self.db_connection.with_connection(move |conn| TrashRepository::purge(conn, &entity_type, &id)).await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TrashServiceTrait for TrashService {
+    fn new(db_connection: Arc<DbConnection>) -> Self {
+        Self { db_connection }
+    }
+
+    async fn list_trash(&self, entity_type: Option<String>) -> Result<Vec<Tombstone>> {
+        let table_type = entity_type.clone();
+        let mut tombstones = self
+            .db_connection
+            .with_connection(move |conn| TrashRepository::list(conn, table_type.as_deref()))
+            .await?;
+        tombstones.extend(self.list_stored(entity_type.as_deref()).await?);
+        tombstones.sort_by(|a, b| a.deleted_at.cmp(&b.deleted_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(tombstones)
+    }
+
+    async fn restore(&self, id: &str) -> Result<String> {
+        let owned = id.to_string();
+        if let Some(entity_type) = self.db_connection.with_connection(move |conn| TrashRepository::restore(conn, &owned)).await? {
+            return Ok(entity_type.to_string());
+        }
+        for kind in STORED_KINDS {
+            if self.db_connection.store().restore(kind, id).await? {
+                return Ok(kind.entity_type.to_string());
+            }
+        }
+        Err(Error::NotFound(format!("No deleted item with ID {}", id)))
+    }
+
+    async fn purge(&self, id: &str) -> Result<Tombstone> {
+        let tombstone = self
+            .list_trash(None)
+            .await?
+            .into_iter()
+            .find(|t| t.id == id)
+            .ok_or_else(|| Error::NotFound(format!("No deleted item with ID {}", id)))?;
+        self.purge_tombstone(&tombstone).await?;
+        Ok(tombstone)
+    }
+
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<Tombstone>> {
+        let mut purged = Vec::new();
+        for tombstone in self.list_trash(None).await? {
+            if tombstone.deleted_at < cutoff && self.purge_tombstone(&tombstone).await? {
+                purged.push(tombstone);
+            }
+        }
+        Ok(purged)
+    }
+}
+